@@ -0,0 +1,148 @@
+// 活动日志相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 活动类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivityType {
+    CardCreated,
+    CardUpdated,
+    CardMoved,
+    CardDeleted,
+    CardCompleted,
+    CardUncompleted,
+    CardArchived,
+    CardUnarchived,
+    ColumnCreated,
+    ColumnUpdated,
+    ColumnDeleted,
+    ColumnArchived,
+    ColumnUnarchived,
+    ProjectCreated,
+    ProjectUpdated,
+    ProjectReordered,
+    ProjectArchived,
+    ProjectUnarchived,
+}
+
+impl ActivityType {
+    /// 数据库中存储的类型名
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActivityType::CardCreated => "card_created",
+            ActivityType::CardUpdated => "card_updated",
+            ActivityType::CardMoved => "card_moved",
+            ActivityType::CardDeleted => "card_deleted",
+            ActivityType::CardCompleted => "card_completed",
+            ActivityType::CardUncompleted => "card_uncompleted",
+            ActivityType::CardArchived => "card_archived",
+            ActivityType::CardUnarchived => "card_unarchived",
+            ActivityType::ColumnCreated => "column_created",
+            ActivityType::ColumnUpdated => "column_updated",
+            ActivityType::ColumnDeleted => "column_deleted",
+            ActivityType::ColumnArchived => "column_archived",
+            ActivityType::ColumnUnarchived => "column_unarchived",
+            ActivityType::ProjectCreated => "project_created",
+            ActivityType::ProjectUpdated => "project_updated",
+            ActivityType::ProjectReordered => "project_reordered",
+            ActivityType::ProjectArchived => "project_archived",
+            ActivityType::ProjectUnarchived => "project_unarchived",
+        }
+    }
+
+    /// 从数据库中的类型名解析
+    pub fn parse(value: &str) -> Option<Self> {
+        let activity_type = match value {
+            "card_created" => ActivityType::CardCreated,
+            "card_updated" => ActivityType::CardUpdated,
+            "card_moved" => ActivityType::CardMoved,
+            "card_deleted" => ActivityType::CardDeleted,
+            "card_completed" => ActivityType::CardCompleted,
+            "card_uncompleted" => ActivityType::CardUncompleted,
+            "card_archived" => ActivityType::CardArchived,
+            "card_unarchived" => ActivityType::CardUnarchived,
+            "column_created" => ActivityType::ColumnCreated,
+            "column_updated" => ActivityType::ColumnUpdated,
+            "column_deleted" => ActivityType::ColumnDeleted,
+            "column_archived" => ActivityType::ColumnArchived,
+            "column_unarchived" => ActivityType::ColumnUnarchived,
+            "project_created" => ActivityType::ProjectCreated,
+            "project_updated" => ActivityType::ProjectUpdated,
+            "project_reordered" => ActivityType::ProjectReordered,
+            "project_archived" => ActivityType::ProjectArchived,
+            "project_unarchived" => ActivityType::ProjectUnarchived,
+            _ => return None,
+        };
+        Some(activity_type)
+    }
+}
+
+/// 活动记录数据结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Activity {
+    pub id: String,
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    #[serde(rename = "type")]
+    pub activity_type: ActivityType,
+    #[serde(rename = "cardId")]
+    pub card_id: Option<String>,
+    #[serde(rename = "columnId")]
+    pub column_id: Option<String>,
+    #[serde(rename = "fromColumnId")]
+    pub from_column_id: Option<String>,
+    #[serde(rename = "toColumnId")]
+    pub to_column_id: Option<String>,
+    pub title: String,
+    pub description: Option<String>,
+    pub timestamp: String,
+}
+
+/// 活动查询参数 (分页 + 过滤)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActivityQuery {
+    /// 只返回这些类型的活动, 为空时不过滤
+    #[serde(default)]
+    pub types: Vec<ActivityType>,
+    /// 起始时间 (RFC 3339, 包含)
+    pub since: Option<String>,
+    /// 截止时间 (RFC 3339, 不包含)
+    pub until: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// 活动分页结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityPage {
+    pub activities: Vec<Activity>,
+    pub total: i64,
+    #[serde(rename = "hasMore")]
+    pub has_more: bool,
+}
+
+/// 获取项目活动记录
+#[tauri::command]
+pub async fn get_project_activities(
+    project_id: String,
+    query: Option<ActivityQuery>,
+    app_handle: tauri::AppHandle,
+) -> Result<ActivityPage, CommandError> {
+    let query = query.unwrap_or_default();
+    db::activities::get_project_activities(&app_handle, &project_id, &query)
+        .map_err(CommandError::from)
+}
+
+/// 获取卡片活动记录
+#[tauri::command]
+pub async fn get_card_activities(
+    card_id: String,
+    query: Option<ActivityQuery>,
+    app_handle: tauri::AppHandle,
+) -> Result<ActivityPage, CommandError> {
+    let query = query.unwrap_or_default();
+    db::activities::get_card_activities(&app_handle, &card_id, &query)
+        .map_err(CommandError::from)
+}
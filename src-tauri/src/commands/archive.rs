@@ -0,0 +1,111 @@
+// 归档相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 可归档的数据类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveKind {
+    Card,
+    Column,
+    Project,
+}
+
+impl ArchiveKind {
+    /// 数据库中存储的类型名
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ArchiveKind::Card => "card",
+            ArchiveKind::Column => "column",
+            ArchiveKind::Project => "project",
+        }
+    }
+
+    /// 从数据库中的类型名解析
+    pub fn parse(value: &str) -> Option<Self> {
+        let kind = match value {
+            "card" => ArchiveKind::Card,
+            "column" => ArchiveKind::Column,
+            "project" => ArchiveKind::Project,
+            _ => return None,
+        };
+        Some(kind)
+    }
+}
+
+/// 已归档数据查询参数 (分页 + 过滤)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArchiveQuery {
+    /// 限定项目, 为空时查询全部项目
+    #[serde(rename = "projectId", default)]
+    pub project_id: Option<String>,
+    /// 只返回该类型, 为空时不过滤
+    #[serde(default)]
+    pub kind: Option<ArchiveKind>,
+    /// 按标题 (卡片和项目还包括描述) 模糊匹配
+    #[serde(default)]
+    pub search: Option<String>,
+    pub limit: Option<u32>,
+    pub offset: Option<u32>,
+}
+
+/// 已归档的卡片、列或项目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedItem {
+    pub kind: ArchiveKind,
+    pub id: String,
+    /// 卡片或列的标题, 项目名称
+    pub title: String,
+    pub description: Option<String>,
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    #[serde(rename = "projectName")]
+    pub project_name: String,
+    /// 卡片所在列
+    #[serde(rename = "columnId")]
+    pub column_id: Option<String>,
+    #[serde(rename = "columnTitle")]
+    pub column_title: Option<String>,
+    #[serde(rename = "archivedAt")]
+    pub archived_at: String,
+}
+
+/// 已归档数据分页结果, 按归档时间倒序
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivePage {
+    pub items: Vec<ArchivedItem>,
+    pub total: i64,
+    #[serde(rename = "hasMore")]
+    pub has_more: bool,
+}
+
+/// 归档卡片、列或项目
+#[tauri::command]
+pub async fn archive_item(
+    kind: ArchiveKind,
+    id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    db::archive::archive_item(&app_handle, kind, &id).map_err(CommandError::from)
+}
+
+/// 取消归档, 恢复到原来的位置
+#[tauri::command]
+pub async fn unarchive_item(
+    kind: ArchiveKind,
+    id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    db::archive::unarchive_item(&app_handle, kind, &id).map_err(CommandError::from)
+}
+
+/// 查询已归档的数据
+#[tauri::command]
+pub async fn list_archived(
+    query: Option<ArchiveQuery>,
+    app_handle: tauri::AppHandle,
+) -> Result<ArchivePage, CommandError> {
+    let query = query.unwrap_or_default();
+    db::archive::list_archived(&app_handle, &query).map_err(CommandError::from)
+}
@@ -0,0 +1,65 @@
+// 卡片附件相关命令
+use super::{CommandError, ErrorCode};
+use crate::db;
+use serde::{Deserialize, Serialize};
+use tauri_plugin_opener::OpenerExt;
+
+/// 附件数据结构, 文件内容按 SHA-256 存放在应用数据目录的附件库中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: String,
+    #[serde(rename = "cardId")]
+    pub card_id: String,
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    pub size: i64,
+    #[serde(rename = "blobHash")]
+    pub blob_hash: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+}
+
+/// 获取卡片的附件
+#[tauri::command]
+pub async fn list_attachments(
+    card_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<Attachment>, CommandError> {
+    db::attachments::list_attachments(&app_handle, &card_id)
+        .map_err(CommandError::from)
+}
+
+/// 添加附件 (复制本地文件到附件库)
+#[tauri::command]
+pub async fn add_attachment(
+    card_id: String,
+    file_path: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Attachment, CommandError> {
+    db::attachments::add_attachment(&app_handle, &card_id, std::path::Path::new(&file_path))
+        .map_err(CommandError::from)
+}
+
+/// 使用系统默认程序打开附件
+#[tauri::command]
+pub async fn open_attachment(
+    attachment_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    let path = db::attachments::prepare_open_path(&app_handle, &attachment_id)
+        .map_err(CommandError::from)?;
+    app_handle
+        .opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| CommandError::new(ErrorCode::Io, e.to_string()))
+}
+
+/// 移除附件
+#[tauri::command]
+pub async fn remove_attachment(
+    attachment_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    db::attachments::remove_attachment(&app_handle, &attachment_id)
+        .map_err(CommandError::from)
+}
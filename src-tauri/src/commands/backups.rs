@@ -0,0 +1,35 @@
+// 数据库备份相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 数据库备份文件信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupInfo {
+    #[serde(rename = "fileName")]
+    pub file_name: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    pub size: u64,
+}
+
+/// 获取所有备份 (最新的在前)
+#[tauri::command]
+pub async fn list_backups(app_handle: tauri::AppHandle) -> Result<Vec<BackupInfo>, CommandError> {
+    db::backups::list_backups(&app_handle).map_err(CommandError::from)
+}
+
+/// 立即备份数据库
+#[tauri::command]
+pub async fn create_backup(app_handle: tauri::AppHandle) -> Result<BackupInfo, CommandError> {
+    db::backups::create_backup(&app_handle).map_err(CommandError::from)
+}
+
+/// 从备份恢复数据库 (恢复前会先备份当前数据), 完成后前端需重新加载数据
+#[tauri::command]
+pub async fn restore_backup(
+    file_name: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    db::backups::restore_backup(&app_handle, &file_name).map_err(CommandError::from)
+}
@@ -0,0 +1,78 @@
+// 卡片检查清单相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 检查清单条目数据结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChecklistItem {
+    pub id: String,
+    #[serde(rename = "cardId")]
+    pub card_id: String,
+    pub text: String,
+    pub done: bool,
+    pub position: i32,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+/// 检查清单进度 (随卡片返回)
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ChecklistProgress {
+    pub done: u32,
+    pub total: u32,
+}
+
+/// 获取卡片的检查清单
+#[tauri::command]
+pub async fn get_checklist(
+    card_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<ChecklistItem>, CommandError> {
+    db::checklists::get_checklist(&app_handle, &card_id)
+        .map_err(CommandError::from)
+}
+
+/// 添加检查清单条目 (追加到末尾)
+#[tauri::command]
+pub async fn add_checklist_item(
+    card_id: String,
+    text: String,
+    app_handle: tauri::AppHandle,
+) -> Result<ChecklistItem, CommandError> {
+    db::checklists::add_checklist_item(&app_handle, &card_id, &text)
+        .map_err(CommandError::from)
+}
+
+/// 切换检查清单条目的完成状态
+#[tauri::command]
+pub async fn toggle_checklist_item(
+    item_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<ChecklistItem, CommandError> {
+    db::checklists::toggle_checklist_item(&app_handle, &item_id)
+        .map_err(CommandError::from)
+}
+
+/// 按传入 ID 顺序保存检查清单排序
+#[tauri::command]
+pub async fn reorder_checklist_items(
+    card_id: String,
+    item_ids: Vec<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<ChecklistItem>, CommandError> {
+    db::checklists::reorder_checklist_items(&app_handle, &card_id, &item_ids)
+        .map_err(CommandError::from)
+}
+
+/// 删除检查清单条目
+#[tauri::command]
+pub async fn delete_checklist_item(
+    item_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    db::checklists::delete_checklist_item(&app_handle, &item_id)
+        .map_err(CommandError::from)
+}
@@ -0,0 +1,59 @@
+// 卡片评论相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 评论数据结构 (正文为 Markdown)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Comment {
+    pub id: String,
+    #[serde(rename = "cardId")]
+    pub card_id: String,
+    pub body: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+/// 获取卡片的评论 (按时间正序)
+#[tauri::command]
+pub async fn list_comments(
+    card_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<Comment>, CommandError> {
+    db::comments::list_comments(&app_handle, &card_id)
+        .map_err(CommandError::from)
+}
+
+/// 添加评论
+#[tauri::command]
+pub async fn add_comment(
+    card_id: String,
+    body: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Comment, CommandError> {
+    db::comments::add_comment(&app_handle, &card_id, &body)
+        .map_err(CommandError::from)
+}
+
+/// 编辑评论
+#[tauri::command]
+pub async fn edit_comment(
+    comment_id: String,
+    body: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Comment, CommandError> {
+    db::comments::edit_comment(&app_handle, &comment_id, &body)
+        .map_err(CommandError::from)
+}
+
+/// 删除评论
+#[tauri::command]
+pub async fn delete_comment(
+    comment_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    db::comments::delete_comment(&app_handle, &comment_id)
+        .map_err(CommandError::from)
+}
@@ -0,0 +1,51 @@
+// 项目导出相关命令
+use super::checklists::ChecklistItem;
+use super::comments::Comment;
+use super::kanban::Board;
+use super::projects::Project;
+use super::tags::Tag;
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// 带版本号的完整项目文档, 可通过 import_project 重新导入
+    Json,
+    /// 每张卡片一行
+    Csv,
+    /// 每列一个标题, 卡片为任务列表项
+    Markdown,
+}
+
+/// JSON 导出文档 (附件文件不包含在内)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectExport {
+    /// 固定为 "captaintodo-project"
+    pub format: String,
+    pub version: u32,
+    #[serde(rename = "exportedAt")]
+    pub exported_at: String,
+    pub project: Project,
+    pub board: Board,
+    #[serde(default)]
+    pub tags: Vec<Tag>,
+    #[serde(rename = "checklistItems", default)]
+    pub checklist_items: Vec<ChecklistItem>,
+    #[serde(default)]
+    pub comments: Vec<Comment>,
+}
+
+/// 将项目导出到指定文件
+#[tauri::command]
+pub async fn export_project(
+    project_id: String,
+    format: ExportFormat,
+    file_path: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    db::export::export_project(&app_handle, &project_id, format, std::path::Path::new(&file_path))
+        .map_err(CommandError::from)
+}
@@ -0,0 +1,45 @@
+// 撤销/重做相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 撤销历史条目
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub id: i64,
+    /// 作用域: 项目ID, 或项目列表级操作使用的 `db::history::PROJECT_LIST_SCOPE`
+    pub scope: String,
+    pub label: String,
+    /// 是否已被撤销 (可重做)
+    pub undone: bool,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+}
+
+/// 撤销作用域内最近一次操作, 没有可撤销的操作时返回 None
+#[tauri::command]
+pub async fn undo(
+    project_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<HistoryEntry>, CommandError> {
+    db::history::undo(&app_handle, &project_id).map_err(CommandError::from)
+}
+
+/// 重做作用域内最近一次被撤销的操作, 没有可重做的操作时返回 None
+#[tauri::command]
+pub async fn redo(
+    project_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Option<HistoryEntry>, CommandError> {
+    db::history::redo(&app_handle, &project_id).map_err(CommandError::from)
+}
+
+/// 获取作用域内的撤销历史 (最新的在前)
+#[tauri::command]
+pub async fn get_undo_history(
+    project_id: String,
+    limit: Option<u32>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<HistoryEntry>, CommandError> {
+    db::history::get_undo_history(&app_handle, &project_id, limit).map_err(CommandError::from)
+}
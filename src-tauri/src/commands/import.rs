@@ -0,0 +1,96 @@
+// 项目导入相关命令
+use super::kanban::{Card, Column};
+use super::projects::Project;
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// Trello 导入结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrelloImportReport {
+    pub project: Project,
+    pub columns: u32,
+    pub cards: u32,
+    pub tags: u32,
+    #[serde(rename = "checklistItems")]
+    pub checklist_items: u32,
+    pub comments: u32,
+    /// 未导入内容的说明
+    pub skipped: Vec<String>,
+}
+
+/// CSV 导入的字段映射, 值为 CSV 表头名称, 未映射的字段留空
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvFieldMapping {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// 列名称, 项目中不存在的列会自动创建; 未映射或为空时放入第一列
+    #[serde(default)]
+    pub column: Option<String>,
+    #[serde(default)]
+    pub priority: Option<String>,
+    #[serde(rename = "startDate", default)]
+    pub start_date: Option<String>,
+    #[serde(rename = "dueDate", default)]
+    pub due_date: Option<String>,
+}
+
+/// CSV 中无法导入的行
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvRowError {
+    /// CSV 文件中的行号 (表头为第 1 行)
+    pub line: u64,
+    pub message: String,
+}
+
+/// CSV 导入结果; 试运行时为将要创建的内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvImportReport {
+    #[serde(rename = "dryRun")]
+    pub dry_run: bool,
+    #[serde(rename = "createdColumns")]
+    pub created_columns: Vec<Column>,
+    pub cards: Vec<Card>,
+    /// 存在错误时不会写入任何数据
+    pub errors: Vec<CsvRowError>,
+}
+
+/// 从 export_project 导出的 JSON 文档创建新项目
+#[tauri::command]
+pub async fn import_project(
+    file_path: String,
+    app_handle: tauri::AppHandle,
+) -> Result<Project, CommandError> {
+    db::import::import_project(&app_handle, std::path::Path::new(&file_path))
+        .map_err(CommandError::from)
+}
+
+/// 从 Trello 看板导出的 JSON 文件创建新项目
+#[tauri::command]
+pub async fn import_trello(
+    file_path: String,
+    app_handle: tauri::AppHandle,
+) -> Result<TrelloImportReport, CommandError> {
+    db::import::import_trello(&app_handle, std::path::Path::new(&file_path))
+        .map_err(CommandError::from)
+}
+
+/// 按字段映射将 CSV 文件中的行导入为项目卡片, dry_run 为 true 时只返回将要创建的内容
+#[tauri::command]
+pub async fn import_csv(
+    project_id: String,
+    file_path: String,
+    mapping: CsvFieldMapping,
+    dry_run: bool,
+    app_handle: tauri::AppHandle,
+) -> Result<CsvImportReport, CommandError> {
+    db::import::import_csv(
+        &app_handle,
+        &project_id,
+        std::path::Path::new(&file_path),
+        &mapping,
+        dry_run,
+    )
+    .map_err(CommandError::from)
+}
@@ -1,6 +1,10 @@
 // 看板相关命令
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveTime, SecondsFormat, TimeZone, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
 use crate::db;
+use super::checklists::ChecklistProgress;
+use super::CommandError;
 
 /// 卡片数据结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,35 +14,234 @@ pub struct Card {
     pub description: Option<String>,
     #[serde(rename = "columnId")]
     pub column_id: String,
+    /// 在所在列中的序号 (由排序键计算, 写入时表示目标位置)
     pub position: i32,
     pub completed: Option<bool>,
-    pub priority: Option<String>,
+    pub priority: Option<Priority>,
+    /// 标签ID列表; 更新时为 None 表示保持卡片原有标签不变
+    #[serde(rename = "tagIds", default)]
+    pub tag_ids: Option<Vec<String>>,
+    /// 检查清单进度 (只读, 由后端计算)
+    #[serde(rename = "checklistProgress", default)]
+    pub checklist_progress: Option<ChecklistProgress>,
+    /// 评论数 (只读, 由后端计算)
+    #[serde(rename = "commentCount", default)]
+    pub comment_count: Option<u32>,
+    /// 重复设置, 为 None 表示不重复
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    /// 放入后所在列超出软性在制品上限 (只读, 仅创建、更新和移动卡片时返回)
+    #[serde(rename = "wipExceeded", default)]
+    pub wip_exceeded: Option<bool>,
     #[serde(rename = "startDate")]
-    pub start_date: Option<String>,
+    pub start_date: Option<CardDate>,
     #[serde(rename = "dueDate")]
-    pub due_date: Option<String>,
+    pub due_date: Option<CardDate>,
+    /// 版本号, 每次修改后递增; 更新时需传入读取时的版本号
+    #[serde(default)]
+    pub version: i64,
+    /// 归档时间, 为 None 表示未归档 (只读, 通过归档命令修改)
+    #[serde(rename = "archivedAt", default)]
+    pub archived_at: Option<String>,
     #[serde(rename = "createdAt")]
     pub created_at: String,
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
 }
 
+/// 卡片优先级, 值为当前优先级刻度中某一级的标识 (默认刻度为 low/normal/urgent/critical);
+/// 按在刻度中的位次排序
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Priority(String);
+
+impl Priority {
+    /// 按当前刻度解析优先级标识, 不在刻度中时返回 None
+    pub fn parse(value: &str) -> Option<Self> {
+        db::priorities::current_rank(value).map(|_| Priority(value.to_string()))
+    }
+
+    /// 数据库中存储的优先级标识
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// 在当前刻度中的位次, 最低一级为 0
+    pub fn rank(&self) -> usize {
+        db::priorities::current_rank(&self.0).unwrap_or(0)
+    }
+}
+
+impl Ord for Priority {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.rank().cmp(&other.rank()).then_with(|| self.0.cmp(&other.0))
+    }
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'de> Deserialize<'de> for Priority {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        Priority::parse(&value)
+            .ok_or_else(|| serde::de::Error::custom(format!("priority {} is not in the priority scale", value)))
+    }
+}
+
+/// 卡片的开始或截止日期: 精确到时刻的时间 (RFC 3339) 或全天日期 (YYYY-MM-DD)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardDate {
+    DateTime(DateTime<FixedOffset>),
+    Date(NaiveDate),
+}
+
+impl CardDate {
+    /// 解析 RFC 3339 时间或 YYYY-MM-DD 日期
+    pub fn parse(value: &str) -> Option<Self> {
+        if let Ok(dt) = DateTime::parse_from_rfc3339(value) {
+            return Some(CardDate::DateTime(dt));
+        }
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").ok().map(CardDate::Date)
+    }
+
+    /// 按本地时区取日期
+    pub fn local_date(&self) -> NaiveDate {
+        match self {
+            CardDate::DateTime(dt) => dt.with_timezone(&Local).date_naive(),
+            CardDate::Date(date) => *date,
+        }
+    }
+
+    /// 开始的时刻; 纯日期取当天本地零点 (零点因夏令时不存在时返回 None)
+    pub fn start_instant(&self) -> Option<DateTime<Utc>> {
+        match self {
+            CardDate::DateTime(dt) => Some(dt.with_timezone(&Utc)),
+            CardDate::Date(date) => Local
+                .from_local_datetime(&date.and_time(NaiveTime::MIN))
+                .earliest()
+                .map(|dt| dt.with_timezone(&Utc)),
+        }
+    }
+
+    /// 是否不晚于另一个日期; 任一方只有日期时按本地日期比较
+    pub fn not_after(&self, other: &CardDate) -> bool {
+        match (self, other) {
+            (CardDate::DateTime(a), CardDate::DateTime(b)) => a <= b,
+            _ => self.local_date() <= other.local_date(),
+        }
+    }
+}
+
+impl fmt::Display for CardDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CardDate::DateTime(dt) => f.write_str(&dt.to_rfc3339_opts(SecondsFormat::Millis, true)),
+            CardDate::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+        }
+    }
+}
+
+impl Serialize for CardDate {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for CardDate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        CardDate::parse(&value)
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid date: {}", value)))
+    }
+}
+
+/// 卡片重复设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recurrence {
+    /// RRULE 子集, 如 "FREQ=WEEKLY;INTERVAL=1;BYDAY=MO"
+    pub rule: String,
+    /// 下一张卡片放入的列, 为空时放入第一列
+    #[serde(rename = "columnId", default)]
+    pub column_id: Option<String>,
+    /// 已生成的下一张卡片ID (只读, 由后端维护)
+    #[serde(rename = "nextCardId", default)]
+    pub next_card_id: Option<String>,
+}
+
+impl Recurrence {
+    /// 由 cards 表中的三个重复字段组装
+    pub fn from_columns(
+        rule: Option<String>,
+        column_id: Option<String>,
+        next_card_id: Option<String>,
+    ) -> Option<Self> {
+        rule.map(|rule| Recurrence { rule, column_id, next_card_id })
+    }
+}
+
 /// 列数据结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Column {
     pub id: String,
     pub title: String,
+    /// 在项目中的序号 (由排序键计算, 写入时表示目标位置)
     pub position: i32,
     #[serde(rename = "cardIds")]
     pub card_ids: Vec<String>,
     #[serde(rename = "backgroundColor")]
     pub background_color: Option<String>,
+    /// 在制品上限 (列中卡片数上限), 为 None 表示不限制
+    #[serde(rename = "wipLimit", default)]
+    pub wip_limit: Option<u32>,
+    /// 超出在制品上限时的处理方式
+    #[serde(rename = "wipMode", default)]
+    pub wip_mode: WipMode,
+    /// 版本号, 每次修改后递增; 更新时需传入读取时的版本号
+    #[serde(default)]
+    pub version: i64,
+    /// 归档时间, 为 None 表示未归档 (只读, 通过归档命令修改)
+    #[serde(rename = "archivedAt", default)]
+    pub archived_at: Option<String>,
     #[serde(rename = "createdAt")]
     pub created_at: String,
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
 }
 
+/// 在制品上限模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WipMode {
+    /// 软限制: 允许放入, 但在返回的卡片中标记超出
+    #[default]
+    Soft,
+    /// 硬限制: 拒绝放入
+    Hard,
+}
+
+impl WipMode {
+    /// 数据库中存储的模式名
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WipMode::Soft => "soft",
+            WipMode::Hard => "hard",
+        }
+    }
+
+    /// 从数据库中的模式名解析
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "soft" => Some(WipMode::Soft),
+            "hard" => Some(WipMode::Hard),
+            _ => None,
+        }
+    }
+}
+
 /// 看板数据结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Board {
@@ -52,18 +255,59 @@ pub struct Board {
     pub updated_at: String,
 }
 
-/// 获取看板数据
+/// 保存看板时的变更统计
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BoardSaveSummary {
+    #[serde(rename = "columnsCreated")]
+    pub columns_created: u32,
+    #[serde(rename = "columnsUpdated")]
+    pub columns_updated: u32,
+    #[serde(rename = "columnsDeleted")]
+    pub columns_deleted: u32,
+    #[serde(rename = "cardsCreated")]
+    pub cards_created: u32,
+    #[serde(rename = "cardsUpdated")]
+    pub cards_updated: u32,
+    #[serde(rename = "cardsDeleted")]
+    pub cards_deleted: u32,
+}
+
+impl BoardSaveSummary {
+    pub fn is_empty(&self) -> bool {
+        self.columns_created + self.columns_updated + self.columns_deleted
+            + self.cards_created + self.cards_updated + self.cards_deleted
+            == 0
+    }
+}
+
+/// 保存看板时移除的卡片及客户端读取时的版本号
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RemovedCard {
+    pub id: String,
+    pub version: i64,
+}
+
+/// 获取看板数据, 默认不包含已归档的列和卡片
 #[tauri::command]
-pub async fn get_board(project_id: String, app_handle: tauri::AppHandle) -> Result<Board, String> {
-    db::kanban::get_board(&app_handle, &project_id)
-        .map_err(|e| e.to_string())
+pub async fn get_board(
+    project_id: String,
+    include_archived: Option<bool>,
+    app_handle: tauri::AppHandle,
+) -> Result<Board, CommandError> {
+    db::kanban::get_board(&app_handle, &project_id, include_archived.unwrap_or(false))
+        .map_err(CommandError::from)
 }
 
-/// 保存看板数据
+/// 保存看板数据, 只写入有变化的列和卡片; 看板中移除的卡片需在 removed_cards 中给出读取时的版本号
 #[tauri::command]
-pub async fn save_board(project_id: String, board: Board, app_handle: tauri::AppHandle) -> Result<(), String> {
-    db::kanban::save_board(&app_handle, &project_id, &board)
-        .map_err(|e| e.to_string())
+pub async fn save_board(
+    project_id: String,
+    board: Board,
+    removed_cards: Option<Vec<RemovedCard>>,
+    app_handle: tauri::AppHandle,
+) -> Result<BoardSaveSummary, CommandError> {
+    db::kanban::save_board(&app_handle, &project_id, &board, &removed_cards.unwrap_or_default())
+        .map_err(CommandError::from)
 }
 
 /// 创建卡片
@@ -72,9 +316,9 @@ pub async fn create_card(
     project_id: String,
     card: Card,
     app_handle: tauri::AppHandle,
-) -> Result<Card, String> {
+) -> Result<Card, CommandError> {
     db::kanban::create_card(&app_handle, &project_id, &card)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// 更新卡片
@@ -83,9 +327,8 @@ pub async fn update_card(
     project_id: String,
     card: Card,
     app_handle: tauri::AppHandle,
-) -> Result<Card, String> {
-    db::kanban::update_card(&app_handle, &project_id, &card)
-        .map_err(|e| e.to_string())
+) -> Result<Card, CommandError> {
+    db::kanban::update_card(&app_handle, &project_id, &card).map_err(CommandError::from)
 }
 
 /// 删除卡片
@@ -94,29 +337,50 @@ pub async fn delete_card(
     project_id: String,
     card_id: String,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     db::kanban::delete_card(&app_handle, &project_id, &card_id)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// 移动卡片参数
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MoveCardParams {
     pub card_id: String,
+    /// 仅供参考, 源列以数据库中卡片所在的列为准
     pub from_column_id: String,
     pub to_column_id: String,
+    /// 在目标列中的位置, 超出范围时放到列首或列尾
     pub new_position: i32,
 }
 
+/// 列中卡片的顺序
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnCardOrder {
+    #[serde(rename = "columnId")]
+    pub column_id: String,
+    #[serde(rename = "cardIds")]
+    pub card_ids: Vec<String>,
+}
+
+/// 移动卡片结果, 包含移动后的卡片及源列和目标列的卡片顺序 (同列移动时两者相同)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MoveCardResult {
+    pub card: Card,
+    #[serde(rename = "fromColumn")]
+    pub from_column: ColumnCardOrder,
+    #[serde(rename = "toColumn")]
+    pub to_column: ColumnCardOrder,
+}
+
 /// 移动卡片
 #[tauri::command]
 pub async fn move_card(
     project_id: String,
     params: MoveCardParams,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<MoveCardResult, CommandError> {
     db::kanban::move_card(&app_handle, &project_id, &params)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// 创建列
@@ -125,9 +389,9 @@ pub async fn create_column(
     project_id: String,
     column: Column,
     app_handle: tauri::AppHandle,
-) -> Result<Column, String> {
+) -> Result<Column, CommandError> {
     db::kanban::create_column(&app_handle, &project_id, &column)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
 /// 更新列
@@ -136,9 +400,8 @@ pub async fn update_column(
     project_id: String,
     column: Column,
     app_handle: tauri::AppHandle,
-) -> Result<Column, String> {
-    db::kanban::update_column(&app_handle, &project_id, &column)
-        .map_err(|e| e.to_string())
+) -> Result<Column, CommandError> {
+    db::kanban::update_column(&app_handle, &project_id, &column).map_err(CommandError::from)
 }
 
 /// 删除列
@@ -147,7 +410,7 @@ pub async fn delete_column(
     project_id: String,
     column_id: String,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     db::kanban::delete_column(&app_handle, &project_id, &column_id)
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
@@ -0,0 +1,131 @@
+// 流动指标相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 流动指标查询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MetricsQuery {
+    /// 统计范围起点 (RFC 3339, 包含), 默认为截止时间前 90 天
+    #[serde(default)]
+    pub since: Option<String>,
+    /// 统计范围终点 (RFC 3339, 不包含), 默认为当前时间
+    #[serde(default)]
+    pub until: Option<String>,
+    /// 开始列: 卡片首次进入该列或其后的列时开始计算周期时间, 默认为第二列
+    #[serde(rename = "startColumnId", default)]
+    pub start_column_id: Option<String>,
+    /// 完成列: 卡片最后进入该列的时间为完成时间, 默认为最后一列
+    #[serde(rename = "endColumnId", default)]
+    pub end_column_id: Option<String>,
+}
+
+/// 时长分布 (单位: 天)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DurationStats {
+    pub count: usize,
+    pub average: Option<f64>,
+    pub p50: Option<f64>,
+    pub p85: Option<f64>,
+    pub p95: Option<f64>,
+}
+
+/// 每周完成数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyThroughput {
+    /// 周一的日期 (YYYY-MM-DD, 本地时区)
+    #[serde(rename = "weekStart")]
+    pub week_start: String,
+    pub count: usize,
+}
+
+/// 进行中的卡片及其已停留时间
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgingCard {
+    #[serde(rename = "cardId")]
+    pub card_id: String,
+    pub title: String,
+    #[serde(rename = "columnId")]
+    pub column_id: String,
+    #[serde(rename = "startedAt")]
+    pub started_at: String,
+    /// 自开始以来的天数
+    #[serde(rename = "ageDays")]
+    pub age_days: f64,
+}
+
+/// 项目流动指标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectMetrics {
+    pub since: String,
+    pub until: String,
+    #[serde(rename = "startColumnId")]
+    pub start_column_id: String,
+    #[serde(rename = "endColumnId")]
+    pub end_column_id: String,
+    /// 前置时间: 创建到完成
+    #[serde(rename = "leadTime")]
+    pub lead_time: DurationStats,
+    /// 周期时间: 开始到完成
+    #[serde(rename = "cycleTime")]
+    pub cycle_time: DurationStats,
+    /// 统计范围内每周完成的卡片数
+    pub throughput: Vec<WeeklyThroughput>,
+    /// 当前处于开始列与完成列之间的卡片, 按停留时间倒序
+    #[serde(rename = "agingWip")]
+    pub aging_wip: Vec<AgingCard>,
+}
+
+/// 获取项目流动指标
+#[tauri::command]
+pub async fn get_project_metrics(
+    project_id: String,
+    query: Option<MetricsQuery>,
+    app_handle: tauri::AppHandle,
+) -> Result<ProjectMetrics, CommandError> {
+    let query = query.unwrap_or_default();
+    db::metrics::get_project_metrics(&app_handle, &project_id, &query, chrono::Utc::now())
+        .map_err(CommandError::from)
+}
+
+/// 累积流图查询参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CumulativeFlowQuery {
+    /// 起始日期 (YYYY-MM-DD, 包含), 默认为结束日期前 29 天
+    #[serde(default)]
+    pub since: Option<String>,
+    /// 结束日期 (YYYY-MM-DD, 包含), 默认为今天
+    #[serde(default)]
+    pub until: Option<String>,
+}
+
+/// 单列每天的卡片数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnFlowSeries {
+    #[serde(rename = "columnId")]
+    pub column_id: String,
+    pub title: String,
+    /// 与 days 一一对应
+    pub counts: Vec<u32>,
+}
+
+/// 累积流图数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CumulativeFlow {
+    /// 日期序列 (YYYY-MM-DD, 本地时区)
+    pub days: Vec<String>,
+    /// 按看板顺序排列的各列数据
+    pub columns: Vec<ColumnFlowSeries>,
+}
+
+/// 获取项目每天各列的卡片数
+#[tauri::command]
+pub async fn get_cumulative_flow(
+    project_id: String,
+    query: Option<CumulativeFlowQuery>,
+    app_handle: tauri::AppHandle,
+) -> Result<CumulativeFlow, CommandError> {
+    let query = query.unwrap_or_default();
+    db::metrics::get_cumulative_flow(&app_handle, &project_id, &query, chrono::Local::now().date_naive())
+        .map_err(CommandError::from)
+}
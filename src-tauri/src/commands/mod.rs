@@ -1,5 +1,118 @@
 // Tauri Commands 模块
 // 处理前端 invoke() 调用
 
+pub mod activities;
+pub mod archive;
+pub mod attachments;
+pub mod backups;
+pub mod checklists;
+pub mod comments;
+pub mod export;
+pub mod history;
+pub mod import;
 pub mod kanban;
+pub mod metrics;
+pub mod priorities;
 pub mod projects;
+pub mod reminders;
+pub mod search;
+pub mod tags;
+pub mod trash;
+
+use crate::db::{ConflictCopy, DbError};
+use rusqlite::ErrorCode as SqliteCode;
+use serde::Serialize;
+
+/// 命令错误码, 前端据此区分错误类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ErrorCode {
+    /// 数据不存在
+    NotFound,
+    /// 版本冲突, 数据已被修改
+    Conflict,
+    /// 参数不合法或违反约束
+    Validation,
+    /// 引用的数据不存在或仍被引用
+    ForeignKey,
+    /// 列已达到硬性在制品上限
+    WipLimit,
+    /// 数据库被占用
+    Busy,
+    /// 数据库未初始化
+    NotInitialized,
+    /// 文件读写失败
+    Io,
+    /// 其他内部错误
+    Internal,
+}
+
+/// 返回给前端的命令错误
+#[derive(Debug, Serialize)]
+pub struct CommandError {
+    pub code: ErrorCode,
+    /// 错误描述, 用于日志和调试
+    pub message: String,
+    /// 相关数据类型, 如 "card"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entity: Option<&'static str>,
+    /// 相关数据ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// 版本冲突时数据库中的当前数据
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<ConflictCopy>,
+}
+
+impl CommandError {
+    fn new(code: ErrorCode, message: String) -> Self {
+        CommandError { code, message, entity: None, id: None, current: None }
+    }
+}
+
+impl From<DbError> for CommandError {
+    fn from(error: DbError) -> Self {
+        let message = error.to_string();
+        match error {
+            DbError::NotFound { entity, id } => CommandError {
+                entity: Some(entity),
+                id: Some(id),
+                ..CommandError::new(ErrorCode::NotFound, message)
+            },
+            DbError::Conflict(copy) => CommandError {
+                entity: Some(copy.entity()),
+                id: Some(copy.id().to_string()),
+                current: Some(*copy),
+                ..CommandError::new(ErrorCode::Conflict, message)
+            },
+            DbError::WipLimitExceeded { column_id, .. } => CommandError {
+                entity: Some("column"),
+                id: Some(column_id),
+                ..CommandError::new(ErrorCode::WipLimit, message)
+            },
+            DbError::Validation(_) => CommandError::new(ErrorCode::Validation, message),
+            DbError::NotInitialized => CommandError::new(ErrorCode::NotInitialized, message),
+            DbError::Io(_) => CommandError::new(ErrorCode::Io, message),
+            DbError::Sqlite(e) => CommandError::new(sqlite_error_code(&e), message),
+            DbError::Serde(_) | DbError::LockPoisoned | DbError::UnsupportedSchema { .. } => {
+                CommandError::new(ErrorCode::Internal, message)
+            }
+        }
+    }
+}
+
+fn sqlite_error_code(error: &rusqlite::Error) -> ErrorCode {
+    match error {
+        rusqlite::Error::QueryReturnedNoRows => ErrorCode::NotFound,
+        rusqlite::Error::SqliteFailure(e, _) => match e.code {
+            SqliteCode::ConstraintViolation
+                if e.extended_code == rusqlite::ffi::SQLITE_CONSTRAINT_FOREIGNKEY =>
+            {
+                ErrorCode::ForeignKey
+            }
+            SqliteCode::ConstraintViolation => ErrorCode::Validation,
+            SqliteCode::DatabaseBusy | SqliteCode::DatabaseLocked => ErrorCode::Busy,
+            _ => ErrorCode::Internal,
+        },
+        _ => ErrorCode::Internal,
+    }
+}
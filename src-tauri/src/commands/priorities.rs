@@ -0,0 +1,58 @@
+// 优先级刻度相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 优先级刻度中的一级
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriorityLevel {
+    /// 卡片中保存的标识 (小写字母、数字、- 或 _), 改名时保持不变
+    pub value: String,
+    /// 界面上显示的名称
+    pub label: String,
+}
+
+/// 优先级刻度: 可改名、增加和调整顺序的优先级列表
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PriorityScale {
+    /// 优先级, 从低到高; 卡片只能设置为其中之一
+    pub levels: Vec<PriorityLevel>,
+}
+
+impl PriorityScale {
+    /// 按标识查找优先级
+    pub fn level(&self, value: &str) -> Option<&PriorityLevel> {
+        self.levels.iter().find(|level| level.value == value)
+    }
+
+    /// 按标识 (不区分大小写) 或名称查找优先级
+    pub fn find(&self, value: &str) -> Option<&PriorityLevel> {
+        let lower = value.to_lowercase();
+        self.levels.iter().find(|level| level.value == lower || level.label == value)
+    }
+}
+
+impl Default for PriorityScale {
+    fn default() -> Self {
+        let levels = [("low", "较低"), ("normal", "普通"), ("urgent", "紧急"), ("critical", "非常紧急")]
+            .into_iter()
+            .map(|(value, label)| PriorityLevel { value: value.to_string(), label: label.to_string() })
+            .collect();
+        Self { levels }
+    }
+}
+
+/// 获取优先级刻度
+#[tauri::command]
+pub async fn get_priority_scale(app_handle: tauri::AppHandle) -> Result<PriorityScale, CommandError> {
+    db::priorities::get_priority_scale(&app_handle).map_err(CommandError::from)
+}
+
+/// 更新优先级刻度, 不能移除仍在使用的优先级
+#[tauri::command]
+pub async fn update_priority_scale(
+    scale: PriorityScale,
+    app_handle: tauri::AppHandle,
+) -> Result<PriorityScale, CommandError> {
+    db::priorities::update_priority_scale(&app_handle, &scale).map_err(CommandError::from)
+}
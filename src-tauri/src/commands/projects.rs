@@ -1,4 +1,5 @@
 // 项目管理相关命令
+use super::CommandError;
 use crate::db;
 use serde::{Deserialize, Serialize};
 
@@ -8,17 +9,28 @@ pub struct Project {
     pub id: String,
     pub name: String,
     pub description: Option<String>,
+    /// 在项目列表中的序号 (由排序键计算, 写入时表示目标位置)
     pub position: i32,
+    /// 版本号, 每次修改后递增; 更新时需传入读取时的版本号
+    #[serde(default)]
+    pub version: i64,
+    /// 归档时间, 为 None 表示未归档 (只读, 通过归档命令修改)
+    #[serde(rename = "archivedAt", default)]
+    pub archived_at: Option<String>,
     #[serde(rename = "createdAt")]
     pub created_at: String,
     #[serde(rename = "updatedAt")]
     pub updated_at: String,
 }
 
-/// 获取所有项目
+/// 获取所有项目, 默认不包含已归档的项目
 #[tauri::command]
-pub async fn get_projects(app_handle: tauri::AppHandle) -> Result<Vec<Project>, String> {
-    db::projects::get_all_projects(&app_handle).map_err(|e| e.to_string())
+pub async fn get_projects(
+    include_archived: Option<bool>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<Project>, CommandError> {
+    db::projects::get_all_projects(&app_handle, include_archived.unwrap_or(false))
+        .map_err(CommandError::from)
 }
 
 /// 创建项目
@@ -26,8 +38,8 @@ pub async fn get_projects(app_handle: tauri::AppHandle) -> Result<Vec<Project>,
 pub async fn create_project(
     project: Project,
     app_handle: tauri::AppHandle,
-) -> Result<Project, String> {
-    db::projects::create_project(&app_handle, &project).map_err(|e| e.to_string())
+) -> Result<Project, CommandError> {
+    db::projects::create_project(&app_handle, &project).map_err(CommandError::from)
 }
 
 /// 更新项目
@@ -35,8 +47,8 @@ pub async fn create_project(
 pub async fn update_project(
     project: Project,
     app_handle: tauri::AppHandle,
-) -> Result<Project, String> {
-    db::projects::update_project(&app_handle, &project).map_err(|e| e.to_string())
+) -> Result<Project, CommandError> {
+    db::projects::update_project(&app_handle, &project).map_err(CommandError::from)
 }
 
 /// 删除项目
@@ -44,8 +56,8 @@ pub async fn update_project(
 pub async fn delete_project(
     project_id: String,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
-    db::projects::delete_project(&app_handle, &project_id).map_err(|e| e.to_string())
+) -> Result<(), CommandError> {
+    db::projects::delete_project(&app_handle, &project_id).map_err(CommandError::from)
 }
 
 /// 保存项目排序
@@ -53,14 +65,14 @@ pub async fn delete_project(
 pub async fn reorder_projects(
     project_ids: Vec<String>,
     app_handle: tauri::AppHandle,
-) -> Result<Vec<Project>, String> {
-    db::projects::reorder_projects(&app_handle, &project_ids).map_err(|e| e.to_string())
+) -> Result<Vec<Project>, CommandError> {
+    db::projects::reorder_projects(&app_handle, &project_ids).map_err(CommandError::from)
 }
 
 /// 获取当前选中的项目ID
 #[tauri::command]
-pub async fn get_current_project(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
-    db::projects::get_current_project(&app_handle).map_err(|e| e.to_string())
+pub async fn get_current_project(app_handle: tauri::AppHandle) -> Result<Option<String>, CommandError> {
+    db::projects::get_current_project(&app_handle).map_err(CommandError::from)
 }
 
 /// 设置当前项目
@@ -68,6 +80,6 @@ pub async fn get_current_project(app_handle: tauri::AppHandle) -> Result<Option<
 pub async fn set_current_project(
     project_id: String,
     app_handle: tauri::AppHandle,
-) -> Result<(), String> {
-    db::projects::set_current_project(&app_handle, &project_id).map_err(|e| e.to_string())
+) -> Result<(), CommandError> {
+    db::projects::set_current_project(&app_handle, &project_id).map_err(CommandError::from)
 }
@@ -0,0 +1,39 @@
+// 到期提醒相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 到期提醒设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReminderSettings {
+    pub enabled: bool,
+    /// 提前提醒的分钟数, 0 表示到期时提醒
+    #[serde(rename = "leadMinutes")]
+    pub lead_minutes: Vec<u32>,
+}
+
+impl Default for ReminderSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            lead_minutes: vec![24 * 60, 60, 0],
+        }
+    }
+}
+
+/// 获取到期提醒设置
+#[tauri::command]
+pub async fn get_reminder_settings(app_handle: tauri::AppHandle) -> Result<ReminderSettings, CommandError> {
+    db::reminders::get_reminder_settings(&app_handle)
+        .map_err(CommandError::from)
+}
+
+/// 更新到期提醒设置
+#[tauri::command]
+pub async fn update_reminder_settings(
+    settings: ReminderSettings,
+    app_handle: tauri::AppHandle,
+) -> Result<ReminderSettings, CommandError> {
+    db::reminders::update_reminder_settings(&app_handle, &settings)
+        .map_err(CommandError::from)
+}
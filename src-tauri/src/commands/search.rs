@@ -0,0 +1,55 @@
+// 卡片搜索相关命令
+use super::CommandError;
+use crate::commands::kanban::{Card, Priority};
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 卡片搜索参数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchCardsParams {
+    /// 搜索关键词, 多个词以空格分隔 (需全部命中)
+    pub query: String,
+    /// 限定项目, 为空时搜索全部项目
+    pub project_id: Option<String>,
+    /// 只返回这些优先级的卡片, 为空时不过滤
+    #[serde(default)]
+    pub priorities: Vec<Priority>,
+    /// 只返回不低于该优先级 (按刻度顺序) 的卡片
+    #[serde(default)]
+    pub min_priority: Option<Priority>,
+    pub completed: Option<bool>,
+    /// 截止日期下限 (RFC 3339 时间或 YYYY-MM-DD 日期, 包含); 纯日期从当天本地零点开始
+    pub due_from: Option<String>,
+    /// 截止日期上限 (RFC 3339 时间或 YYYY-MM-DD 日期, 包含); 纯日期到当天结束, 不能早于下限
+    pub due_to: Option<String>,
+    pub limit: Option<u32>,
+}
+
+/// 卡片搜索结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardSearchResult {
+    pub card: Card,
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    #[serde(rename = "projectName")]
+    pub project_name: String,
+    #[serde(rename = "columnTitle")]
+    pub column_title: String,
+    /// 标题 (已转义 HTML), 命中部分以 `<mark></mark>` 包裹
+    #[serde(rename = "titleHighlight")]
+    pub title_highlight: String,
+    /// 描述摘要 (已转义 HTML), 命中部分以 `<mark></mark>` 包裹
+    #[serde(rename = "descriptionSnippet")]
+    pub description_snippet: Option<String>,
+    /// 相关度, 越大越相关
+    pub score: f64,
+}
+
+/// 跨项目搜索卡片
+#[tauri::command]
+pub async fn search_cards(
+    params: SearchCardsParams,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<CardSearchResult>, CommandError> {
+    db::search::search_cards(&app_handle, &params).map_err(CommandError::from)
+}
@@ -0,0 +1,57 @@
+// 标签相关命令
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 标签数据结构
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Tag {
+    pub id: String,
+    pub name: String,
+    pub color: String,
+    pub position: i32,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+}
+
+/// 获取项目的所有标签
+#[tauri::command]
+pub async fn get_tags(project_id: String, app_handle: tauri::AppHandle) -> Result<Vec<Tag>, CommandError> {
+    db::tags::get_tags(&app_handle, &project_id)
+        .map_err(CommandError::from)
+}
+
+/// 创建标签
+#[tauri::command]
+pub async fn create_tag(
+    project_id: String,
+    tag: Tag,
+    app_handle: tauri::AppHandle,
+) -> Result<Tag, CommandError> {
+    db::tags::create_tag(&app_handle, &project_id, &tag)
+        .map_err(CommandError::from)
+}
+
+/// 更新标签
+#[tauri::command]
+pub async fn update_tag(
+    project_id: String,
+    tag: Tag,
+    app_handle: tauri::AppHandle,
+) -> Result<Tag, CommandError> {
+    db::tags::update_tag(&app_handle, &project_id, &tag)
+        .map_err(CommandError::from)
+}
+
+/// 删除标签 (同时从所有卡片上移除)
+#[tauri::command]
+pub async fn delete_tag(
+    project_id: String,
+    tag_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), CommandError> {
+    db::tags::delete_tag(&app_handle, &project_id, &tag_id)
+        .map_err(CommandError::from)
+}
@@ -0,0 +1,71 @@
+// 回收站相关命令
+use super::archive::ArchiveKind;
+use super::CommandError;
+use crate::db;
+use serde::{Deserialize, Serialize};
+
+/// 回收站中的卡片、列或项目 (类型与归档相同)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItem {
+    pub id: String,
+    pub kind: ArchiveKind,
+    /// 被删除的卡片、列或项目的ID
+    #[serde(rename = "entityId")]
+    pub entity_id: String,
+    #[serde(rename = "projectId")]
+    pub project_id: String,
+    /// 删除时的项目名称
+    #[serde(rename = "projectName")]
+    pub project_name: String,
+    /// 卡片或列的标题, 项目名称
+    pub title: String,
+    #[serde(rename = "deletedAt")]
+    pub deleted_at: String,
+}
+
+/// 回收站设置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashSettings {
+    /// 删除后保留的天数, 超过后自动清除
+    #[serde(rename = "retentionDays")]
+    pub retention_days: u32,
+}
+
+impl Default for TrashSettings {
+    fn default() -> Self {
+        Self { retention_days: 30 }
+    }
+}
+
+/// 获取回收站内容, 按删除时间倒序; 指定项目时只返回该项目的内容
+#[tauri::command]
+pub async fn list_trash(
+    project_id: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<TrashItem>, CommandError> {
+    db::trash::list_trash(&app_handle, project_id.as_deref()).map_err(CommandError::from)
+}
+
+/// 从回收站恢复到原来的列和位置
+#[tauri::command]
+pub async fn restore_from_trash(
+    trash_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<TrashItem, CommandError> {
+    db::trash::restore_from_trash(&app_handle, &trash_id).map_err(CommandError::from)
+}
+
+/// 获取回收站设置
+#[tauri::command]
+pub async fn get_trash_settings(app_handle: tauri::AppHandle) -> Result<TrashSettings, CommandError> {
+    db::trash::get_trash_settings(&app_handle).map_err(CommandError::from)
+}
+
+/// 更新回收站设置
+#[tauri::command]
+pub async fn update_trash_settings(
+    settings: TrashSettings,
+    app_handle: tauri::AppHandle,
+) -> Result<TrashSettings, CommandError> {
+    db::trash::update_trash_settings(&app_handle, &settings).map_err(CommandError::from)
+}
@@ -0,0 +1,228 @@
+// 活动日志相关数据库操作
+use super::{with_connection, DbError};
+use crate::commands::activities::{Activity, ActivityPage, ActivityQuery, ActivityType};
+use chrono::{DateTime, Utc};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Connection;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 200;
+
+impl ToSql for ActivityType {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for ActivityType {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let text = value.as_str()?;
+        ActivityType::parse(text).ok_or_else(|| {
+            FromSqlError::Other(format!("unknown activity type: {}", text).into())
+        })
+    }
+}
+
+/// 待写入的活动记录
+pub struct NewActivity<'a> {
+    project_id: &'a str,
+    activity_type: ActivityType,
+    title: &'a str,
+    card_id: Option<&'a str>,
+    column_id: Option<&'a str>,
+    from_column_id: Option<&'a str>,
+    to_column_id: Option<&'a str>,
+    description: Option<String>,
+}
+
+impl<'a> NewActivity<'a> {
+    pub fn new(project_id: &'a str, activity_type: ActivityType, title: &'a str) -> Self {
+        Self {
+            project_id,
+            activity_type,
+            title,
+            card_id: None,
+            column_id: None,
+            from_column_id: None,
+            to_column_id: None,
+            description: None,
+        }
+    }
+
+    pub fn card(mut self, card_id: &'a str) -> Self {
+        self.card_id = Some(card_id);
+        self
+    }
+
+    pub fn column(mut self, column_id: &'a str) -> Self {
+        self.column_id = Some(column_id);
+        self
+    }
+
+    pub fn moved(mut self, from_column_id: &'a str, to_column_id: &'a str) -> Self {
+        self.from_column_id = Some(from_column_id);
+        self.to_column_id = Some(to_column_id);
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+}
+
+/// 追加一条活动记录 (应在调用方的事务中执行)
+pub fn record_activity(conn: &Connection, activity: &NewActivity<'_>) -> Result<(), DbError> {
+    conn.execute(
+        "INSERT INTO activities (id, project_id, type, card_id, column_id, from_column_id, to_column_id, title, description, timestamp)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            Uuid::new_v4().to_string(),
+            activity.project_id,
+            activity.activity_type,
+            activity.card_id,
+            activity.column_id,
+            activity.from_column_id,
+            activity.to_column_id,
+            activity.title,
+            activity.description,
+            Utc::now().to_rfc3339(),
+        ],
+    )?;
+    Ok(())
+}
+
+/// 获取项目活动记录
+pub fn get_project_activities(
+    app_handle: &AppHandle,
+    project_id: &str,
+    query: &ActivityQuery,
+) -> Result<ActivityPage, DbError> {
+    with_connection(app_handle, |conn| {
+        query_activities_impl(conn, "project_id", project_id, query)
+    })
+}
+
+/// 获取项目的全部活动记录
+pub(crate) fn get_all_project_activities(conn: &Connection, project_id: &str) -> Result<Vec<Activity>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, project_id, type, card_id, column_id, from_column_id, to_column_id, title, description, timestamp
+         FROM activities WHERE project_id = ? ORDER BY rowid",
+    )?;
+    let activities = stmt.query_map([project_id], activity_from_row)?.collect::<Result<Vec<_>, _>>()?;
+    Ok(activities)
+}
+
+/// 写回活动记录, 已存在的记录保持不变
+pub(crate) fn restore_activities(conn: &Connection, activities: &[Activity]) -> Result<(), DbError> {
+    for activity in activities {
+        conn.execute(
+            "INSERT OR IGNORE INTO activities (id, project_id, type, card_id, column_id, from_column_id, to_column_id, title, description, timestamp)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                activity.id,
+                activity.project_id,
+                activity.activity_type,
+                activity.card_id,
+                activity.column_id,
+                activity.from_column_id,
+                activity.to_column_id,
+                activity.title,
+                activity.description,
+                activity.timestamp,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// 获取卡片活动记录
+pub fn get_card_activities(
+    app_handle: &AppHandle,
+    card_id: &str,
+    query: &ActivityQuery,
+) -> Result<ActivityPage, DbError> {
+    with_connection(app_handle, |conn| {
+        query_activities_impl(conn, "card_id", card_id, query)
+    })
+}
+
+fn query_activities_impl(
+    conn: &Connection,
+    scope_column: &'static str,
+    scope_id: &str,
+    query: &ActivityQuery,
+) -> Result<ActivityPage, DbError> {
+    let mut conditions = vec![format!("{} = ?", scope_column)];
+    let mut params: Vec<Box<dyn ToSql>> = vec![Box::new(scope_id.to_string())];
+
+    if !query.types.is_empty() {
+        let placeholders = vec!["?"; query.types.len()].join(", ");
+        conditions.push(format!("type IN ({})", placeholders));
+        for activity_type in &query.types {
+            params.push(Box::new(*activity_type));
+        }
+    }
+    if let Some(since) = &query.since {
+        conditions.push("timestamp >= ?".to_string());
+        params.push(Box::new(normalize_timestamp(since)?));
+    }
+    if let Some(until) = &query.until {
+        conditions.push("timestamp < ?".to_string());
+        params.push(Box::new(normalize_timestamp(until)?));
+    }
+
+    let where_clause = conditions.join(" AND ");
+
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM activities WHERE {}", where_clause),
+        rusqlite::params_from_iter(params.iter()),
+        |row| row.get(0),
+    )?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0);
+    params.push(Box::new(limit));
+    params.push(Box::new(offset));
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, project_id, type, card_id, column_id, from_column_id, to_column_id, title, description, timestamp
+         FROM activities WHERE {} ORDER BY timestamp DESC, rowid DESC LIMIT ? OFFSET ?",
+        where_clause
+    ))?;
+
+    let activities_iter = stmt.query_map(rusqlite::params_from_iter(params.iter()), activity_from_row)?;
+
+    let activities: Vec<Activity> = activities_iter.filter_map(|a| a.ok()).collect();
+    let has_more = i64::from(offset) + (activities.len() as i64) < total;
+
+    Ok(ActivityPage {
+        activities,
+        total,
+        has_more,
+    })
+}
+
+fn activity_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<Activity> {
+    Ok(Activity {
+        id: row.get(0)?,
+        project_id: row.get(1)?,
+        activity_type: row.get(2)?,
+        card_id: row.get(3)?,
+        column_id: row.get(4)?,
+        from_column_id: row.get(5)?,
+        to_column_id: row.get(6)?,
+        title: row.get(7)?,
+        description: row.get(8)?,
+        timestamp: row.get(9)?,
+    })
+}
+
+/// 将查询时间统一为与写入时相同的 UTC RFC 3339 格式, 保证按字符串比较有效
+fn normalize_timestamp(value: &str) -> Result<String, DbError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc).to_rfc3339())
+        .map_err(|_| DbError::Validation(format!("invalid timestamp: {}", value)))
+}
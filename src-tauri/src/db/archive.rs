@@ -0,0 +1,220 @@
+// 归档相关数据库操作
+// 归档只设置 archived_at, 不删除数据; 看板、项目列表、搜索和提醒都会跳过已归档的行。
+use super::activities::{record_activity, NewActivity};
+use super::history::{record_history, UndoOp, PROJECT_LIST_SCOPE};
+use super::projects::clear_current_project;
+use super::search::escape_like;
+use super::{with_connection, with_transaction, DbError};
+use crate::commands::activities::ActivityType;
+use crate::commands::archive::{ArchiveKind, ArchivePage, ArchiveQuery, ArchivedItem};
+use chrono::Utc;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+use rusqlite::Connection;
+use tauri::AppHandle;
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 200;
+
+impl ToSql for ArchiveKind {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for ArchiveKind {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let text = value.as_str()?;
+        ArchiveKind::parse(text)
+            .ok_or_else(|| FromSqlError::Other(format!("unknown archive kind: {}", text).into()))
+    }
+}
+
+impl ArchiveKind {
+    fn table(&self) -> &'static str {
+        match self {
+            ArchiveKind::Card => "cards",
+            ArchiveKind::Column => "columns",
+            ArchiveKind::Project => "projects",
+        }
+    }
+}
+
+/// 归档卡片、列或项目; 已归档时不做任何修改
+pub fn archive_item(app_handle: &AppHandle, kind: ArchiveKind, id: &str) -> Result<(), DbError> {
+    let id = id.to_string();
+    with_transaction(app_handle, |conn| {
+        set_archived(conn, kind, &id, Some(Utc::now().to_rfc3339()))
+    })
+}
+
+/// 取消归档; 未归档时不做任何修改
+pub fn unarchive_item(app_handle: &AppHandle, kind: ArchiveKind, id: &str) -> Result<(), DbError> {
+    let id = id.to_string();
+    with_transaction(app_handle, |conn| set_archived(conn, kind, &id, None))
+}
+
+fn set_archived(
+    conn: &Connection,
+    kind: ArchiveKind,
+    id: &str,
+    archived_at: Option<String>,
+) -> Result<(), DbError> {
+    let name_column = if kind == ArchiveKind::Project { "name" } else { "title" };
+    let project_column = if kind == ArchiveKind::Project { "id" } else { "project_id" };
+    let result = conn.query_row(
+        &format!(
+            "SELECT {}, {}, archived_at FROM {} WHERE id = ?",
+            project_column,
+            name_column,
+            kind.table()
+        ),
+        [id],
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, Option<String>>(2)?)),
+    );
+    let (project_id, title, previous) = match result {
+        Ok(found) => found,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Err(DbError::not_found(kind.as_str(), id)),
+        Err(e) => return Err(DbError::from(e)),
+    };
+    if previous.is_some() == archived_at.is_some() {
+        return Ok(());
+    }
+
+    set_archived_at(conn, kind, id, archived_at.as_deref())?;
+
+    let archiving = archived_at.is_some();
+    let activity_type = match (kind, archiving) {
+        (ArchiveKind::Card, true) => ActivityType::CardArchived,
+        (ArchiveKind::Card, false) => ActivityType::CardUnarchived,
+        (ArchiveKind::Column, true) => ActivityType::ColumnArchived,
+        (ArchiveKind::Column, false) => ActivityType::ColumnUnarchived,
+        (ArchiveKind::Project, true) => ActivityType::ProjectArchived,
+        (ArchiveKind::Project, false) => ActivityType::ProjectUnarchived,
+    };
+    let activity = NewActivity::new(&project_id, activity_type, &title);
+    let activity = match kind {
+        ArchiveKind::Card => activity.card(id),
+        ArchiveKind::Column => activity.column(id),
+        ArchiveKind::Project => activity,
+    };
+    record_activity(conn, &activity)?;
+
+    // 归档当前项目时清除当前项目设置
+    if kind == ArchiveKind::Project && archiving {
+        clear_current_project(conn, id)?;
+    }
+
+    let scope = if kind == ArchiveKind::Project { PROJECT_LIST_SCOPE } else { &project_id };
+    let action = if archiving { "归档" } else { "取消归档" };
+    let noun = match kind {
+        ArchiveKind::Card => "卡片",
+        ArchiveKind::Column => "列",
+        ArchiveKind::Project => "项目",
+    };
+    record_history(
+        conn,
+        scope,
+        format!("{}{}「{}」", action, noun, title),
+        vec![UndoOp::SetArchived { kind, id: id.to_string(), archived_at: previous }],
+        vec![UndoOp::SetArchived { kind, id: id.to_string(), archived_at }],
+    )
+}
+
+/// 写入归档时间 (撤销/重做时直接调用, 不记录活动)
+pub(crate) fn set_archived_at(
+    conn: &Connection,
+    kind: ArchiveKind,
+    id: &str,
+    archived_at: Option<&str>,
+) -> Result<(), DbError> {
+    conn.execute(
+        &format!(
+            "UPDATE {} SET archived_at = ?, updated_at = ?, version = version + 1 WHERE id = ?",
+            kind.table()
+        ),
+        rusqlite::params![archived_at, Utc::now().to_rfc3339(), id],
+    )?;
+    Ok(())
+}
+
+/// 查询已归档的数据
+pub fn list_archived(app_handle: &AppHandle, query: &ArchiveQuery) -> Result<ArchivePage, DbError> {
+    with_connection(app_handle, |conn| list_archived_impl(conn, query))
+}
+
+fn list_archived_impl(conn: &Connection, query: &ArchiveQuery) -> Result<ArchivePage, DbError> {
+    let mut conditions = vec!["1 = 1".to_string()];
+    let mut params: Vec<Box<dyn ToSql>> = Vec::new();
+
+    if let Some(kind) = query.kind {
+        conditions.push("kind = ?".to_string());
+        params.push(Box::new(kind));
+    }
+    if let Some(project_id) = &query.project_id {
+        conditions.push("project_id = ?".to_string());
+        params.push(Box::new(project_id.clone()));
+    }
+    if let Some(search) = query.search.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        conditions.push(
+            "(title LIKE ? ESCAPE '\\' OR COALESCE(description, '') LIKE ? ESCAPE '\\')".to_string(),
+        );
+        let pattern = format!("%{}%", escape_like(search));
+        params.push(Box::new(pattern.clone()));
+        params.push(Box::new(pattern));
+    }
+
+    let from = "(
+        SELECT 'card' AS kind, c.id, c.title, c.description, c.project_id, p.name AS project_name,
+               c.column_id, col.title AS column_title, c.archived_at
+        FROM cards c
+        JOIN projects p ON p.id = c.project_id
+        JOIN columns col ON col.id = c.column_id
+        WHERE c.archived_at IS NOT NULL
+        UNION ALL
+        SELECT 'column', col.id, col.title, NULL, col.project_id, p.name, NULL, NULL, col.archived_at
+        FROM columns col
+        JOIN projects p ON p.id = col.project_id
+        WHERE col.archived_at IS NOT NULL
+        UNION ALL
+        SELECT 'project', p.id, p.name, p.description, p.id, p.name, NULL, NULL, p.archived_at
+        FROM projects p
+        WHERE p.archived_at IS NOT NULL
+    )";
+    let where_clause = conditions.join(" AND ");
+
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM {} WHERE {}", from, where_clause),
+        rusqlite::params_from_iter(params.iter()),
+        |row| row.get(0),
+    )?;
+
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+    let offset = query.offset.unwrap_or(0);
+    params.push(Box::new(limit));
+    params.push(Box::new(offset));
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT kind, id, title, description, project_id, project_name, column_id, column_title, archived_at
+         FROM {} WHERE {} ORDER BY archived_at DESC, id LIMIT ? OFFSET ?",
+        from, where_clause
+    ))?;
+
+    let items_iter = stmt.query_map(rusqlite::params_from_iter(params.iter()), |row| {
+        Ok(ArchivedItem {
+            kind: row.get(0)?,
+            id: row.get(1)?,
+            title: row.get(2)?,
+            description: row.get(3)?,
+            project_id: row.get(4)?,
+            project_name: row.get(5)?,
+            column_id: row.get(6)?,
+            column_title: row.get(7)?,
+            archived_at: row.get(8)?,
+        })
+    })?;
+
+    let items: Vec<ArchivedItem> = items_iter.filter_map(|i| i.ok()).collect();
+    let has_more = i64::from(offset) + (items.len() as i64) < total;
+
+    Ok(ArchivePage { items, total, has_more })
+}
@@ -0,0 +1,337 @@
+// 卡片附件相关数据库操作
+// 文件内容按 SHA-256 哈希存放在 <应用数据目录>/attachments/<前两位>/<哈希> 下,
+// 相同内容只保存一份; attachments 表记录卡片与文件内容的关联。
+// 不再被任何附件、撤销历史、回收站或备份引用的文件由后台任务定期回收。
+use super::backups::backup_blob_hashes;
+use super::history::{record_history, UndoOp};
+use super::kanban::find_card_project;
+use super::{app_data_dir, with_connection, with_transaction, DbError};
+use crate::commands::attachments::Attachment;
+use chrono::Utc;
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+use uuid::Uuid;
+
+const BLOB_DIR_NAME: &str = "attachments";
+
+/// 获取卡片的附件
+pub fn list_attachments(app_handle: &AppHandle, card_id: &str) -> Result<Vec<Attachment>, DbError> {
+    let cid = card_id.to_string();
+    with_connection(app_handle, |conn| get_card_attachments(conn, &cid))
+}
+
+/// 添加附件: 将文件复制到附件库并关联到卡片
+pub fn add_attachment(
+    app_handle: &AppHandle,
+    card_id: &str,
+    source_path: &Path,
+) -> Result<Attachment, DbError> {
+    let cid = card_id.to_string();
+    let file_name = source_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| DbError::Validation(format!("invalid file path {:?}", source_path)))?;
+    let store_dir = blob_store_dir(app_handle)?;
+
+    // 写入文件与插入记录需在同一把锁内完成, 避免文件被并发的回收任务删除
+    with_transaction(app_handle, |conn| {
+        let project_id = find_card_project(conn, &cid)?
+            .ok_or_else(|| DbError::not_found("card", &cid))?;
+
+        let (blob_hash, size) = store_blob(&store_dir, source_path)?;
+
+        let attachment = Attachment {
+            id: Uuid::new_v4().to_string(),
+            card_id: cid.clone(),
+            file_name,
+            size,
+            blob_hash,
+            created_at: Utc::now().to_rfc3339(),
+        };
+        conn.execute(
+            "INSERT INTO attachments (id, card_id, blob_hash, file_name, size, created_at)
+             VALUES (?, ?, ?, ?, ?, ?)",
+            rusqlite::params![
+                attachment.id,
+                attachment.card_id,
+                attachment.blob_hash,
+                attachment.file_name,
+                attachment.size,
+                attachment.created_at,
+            ],
+        )?;
+
+        record_history(
+            conn,
+            &project_id,
+            format!("添加附件「{}」", attachment.file_name),
+            vec![UndoOp::DeleteAttachment { attachment_id: attachment.id.clone() }],
+            vec![UndoOp::RestoreAttachments { attachments: vec![attachment.clone()] }],
+        )?;
+
+        Ok(attachment)
+    })
+}
+
+/// 移除附件
+pub fn remove_attachment(app_handle: &AppHandle, attachment_id: &str) -> Result<(), DbError> {
+    let id = attachment_id.to_string();
+    with_transaction(app_handle, |conn| {
+        let (project_id, previous) = find_attachment(conn, &id)?
+            .ok_or_else(|| DbError::not_found("attachment", &id))?;
+
+        conn.execute("DELETE FROM attachments WHERE id = ?", [&id])?;
+
+        record_history(
+            conn,
+            &project_id,
+            format!("移除附件「{}」", previous.file_name),
+            vec![UndoOp::RestoreAttachments { attachments: vec![previous] }],
+            vec![UndoOp::DeleteAttachment { attachment_id: id.clone() }],
+        )
+    })
+}
+
+/// 将附件以原文件名复制到临时目录, 返回可交给系统打开的路径
+pub fn prepare_open_path(app_handle: &AppHandle, attachment_id: &str) -> Result<PathBuf, DbError> {
+    let id = attachment_id.to_string();
+    let store_dir = blob_store_dir(app_handle)?;
+    with_connection(app_handle, |conn| {
+        let (_, attachment) = find_attachment(conn, &id)?
+            .ok_or_else(|| DbError::not_found("attachment", &id))?;
+
+        // 附件库中的文件没有扩展名, 系统无法据此选择打开方式
+        let open_dir = std::env::temp_dir().join("captaintodo-attachments").join(&attachment.id);
+        fs::create_dir_all(&open_dir)?;
+        let open_path = open_dir.join(&attachment.file_name);
+        fs::copy(blob_path(&store_dir, &attachment.blob_hash), &open_path)?;
+        Ok(open_path)
+    })
+}
+
+/// 删除既不被附件引用、也不被撤销历史、回收站或备份引用的文件, 返回删除数量;
+/// 单个文件出错时记录日志并跳过
+pub fn collect_orphaned_blobs(app_handle: &AppHandle) -> Result<usize, DbError> {
+    let store_dir = blob_store_dir(app_handle)?;
+    let mut referenced = backup_blob_hashes(app_handle)?;
+    // 持锁遍历附件库, 避免删除刚由 add_attachment 写入、尚未计入的文件
+    with_connection(app_handle, |conn| {
+        collect_referenced_hashes(conn, &mut referenced)?;
+
+        let mut removed = 0;
+        for shard in fs::read_dir(&store_dir)? {
+            let shard = match shard {
+                Ok(shard) => shard,
+                Err(e) => {
+                    log::warn!("Failed to read attachment store entry: {}", e);
+                    continue;
+                }
+            };
+            let path = shard.path();
+            if !path.is_dir() {
+                // 写入中断遗留的临时文件一并清理
+                if shard.file_name().to_string_lossy().ends_with(".tmp") && remove_blob_file(&path) {
+                    removed += 1;
+                }
+                continue;
+            }
+            let entries = match fs::read_dir(&path) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    log::warn!("Failed to read attachment directory {:?}: {}", path, e);
+                    continue;
+                }
+            };
+            for entry in entries.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if !referenced.contains(&name) && remove_blob_file(&entry.path()) {
+                    removed += 1;
+                }
+            }
+        }
+        Ok(removed)
+    })
+}
+
+/// 收集附件表以及撤销历史、回收站快照中引用的文件哈希
+fn collect_referenced_hashes(conn: &Connection, hashes: &mut HashSet<String>) -> Result<(), DbError> {
+    let mut stmt = conn.prepare("SELECT DISTINCT blob_hash FROM attachments")?;
+    for hash in stmt.query_map([], |row| row.get::<_, String>(0))? {
+        hashes.insert(hash?);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT undo_ops FROM undo_history
+         UNION ALL SELECT redo_ops FROM undo_history
+         UNION ALL SELECT restore_ops FROM trash",
+    )?;
+    for ops in stmt.query_map([], |row| row.get::<_, String>(0))? {
+        let ops: serde_json::Value = serde_json::from_str(&ops?)?;
+        collect_json_hashes(&ops, hashes);
+    }
+    Ok(())
+}
+
+/// 快照中的附件以 Attachment 的 JSON 形式保存, 取出其中所有 blobHash
+fn collect_json_hashes(value: &serde_json::Value, hashes: &mut HashSet<String>) {
+    match value {
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_json_hashes(item, hashes);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (key, item) in map {
+                match item {
+                    serde_json::Value::String(hash) if key == "blobHash" => {
+                        hashes.insert(hash.clone());
+                    }
+                    _ => collect_json_hashes(item, hashes),
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 删除单个文件, 失败时只记录日志
+fn remove_blob_file(path: &Path) -> bool {
+    match fs::remove_file(path) {
+        Ok(()) => true,
+        Err(e) => {
+            log::warn!("Failed to remove orphaned attachment file {:?}: {}", path, e);
+            false
+        }
+    }
+}
+
+fn blob_store_dir(app_handle: &AppHandle) -> Result<PathBuf, DbError> {
+    let dir = app_data_dir(app_handle)?.join(BLOB_DIR_NAME);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn blob_path(store_dir: &Path, blob_hash: &str) -> PathBuf {
+    store_dir.join(&blob_hash[..2]).join(blob_hash)
+}
+
+/// 计算文件哈希并写入附件库, 已存在相同内容时直接复用
+fn store_blob(store_dir: &Path, source_path: &Path) -> Result<(String, i64), DbError> {
+    let mut source = File::open(source_path)?;
+    let tmp_path = store_dir.join(format!("{}.tmp", Uuid::new_v4()));
+    let mut tmp = File::create(&tmp_path)?;
+
+    // 边读边写边计算哈希, 只读一遍源文件
+    let mut hasher = Sha256::new();
+    let mut size = 0i64;
+    let mut buf = [0u8; 64 * 1024];
+    let copied: io::Result<()> = (|| {
+        loop {
+            let n = source.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            tmp.write_all(&buf[..n])?;
+            size += n as i64;
+        }
+        tmp.sync_all()
+    })();
+    if let Err(e) = copied {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(DbError::Io(e));
+    }
+
+    let blob_hash = format!("{:x}", hasher.finalize());
+    let target = blob_path(store_dir, &blob_hash);
+    if target.exists() {
+        fs::remove_file(&tmp_path)?;
+    } else {
+        fs::create_dir_all(target.parent().unwrap_or(store_dir))?;
+        fs::rename(&tmp_path, &target)?;
+    }
+
+    Ok((blob_hash, size))
+}
+
+/// 按 ID 查询附件及其卡片所属项目
+fn find_attachment(conn: &Connection, attachment_id: &str) -> Result<Option<(String, Attachment)>, DbError> {
+    let result = conn.query_row(
+        "SELECT c.project_id, a.id, a.card_id, a.file_name, a.size, a.blob_hash, a.created_at
+         FROM attachments a JOIN cards c ON c.id = a.card_id
+         WHERE a.id = ?",
+        [attachment_id],
+        |row| Ok((row.get(0)?, map_attachment_at(row, 1)?)),
+    );
+
+    match result {
+        Ok(found) => Ok(Some(found)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
+fn map_attachment_at(row: &rusqlite::Row<'_>, offset: usize) -> rusqlite::Result<Attachment> {
+    Ok(Attachment {
+        id: row.get(offset)?,
+        card_id: row.get(offset + 1)?,
+        file_name: row.get(offset + 2)?,
+        size: row.get(offset + 3)?,
+        blob_hash: row.get(offset + 4)?,
+        created_at: row.get(offset + 5)?,
+    })
+}
+
+/// 获取卡片的全部附件
+pub(crate) fn get_card_attachments(conn: &Connection, card_id: &str) -> Result<Vec<Attachment>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, card_id, file_name, size, blob_hash, created_at
+         FROM attachments WHERE card_id = ? ORDER BY created_at, rowid"
+    )?;
+
+    let attachments: Vec<Attachment> = stmt
+        .query_map([card_id], |row| map_attachment_at(row, 0))?
+        .filter_map(|a| a.ok())
+        .collect();
+    Ok(attachments)
+}
+
+/// 获取项目内所有卡片的附件
+pub(crate) fn get_project_attachments(conn: &Connection, project_id: &str) -> Result<Vec<Attachment>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.card_id, a.file_name, a.size, a.blob_hash, a.created_at
+         FROM attachments a JOIN cards c ON c.id = a.card_id
+         WHERE c.project_id = ? ORDER BY a.created_at, a.rowid"
+    )?;
+
+    let attachments: Vec<Attachment> = stmt
+        .query_map([project_id], |row| map_attachment_at(row, 0))?
+        .filter_map(|a| a.ok())
+        .collect();
+    Ok(attachments)
+}
+
+/// 恢复附件记录, 卡片已不存在的附件会被跳过
+pub(crate) fn restore_attachments(conn: &Connection, attachments: &[Attachment]) -> Result<(), DbError> {
+    for attachment in attachments {
+        conn.execute(
+            "INSERT OR IGNORE INTO attachments (id, card_id, blob_hash, file_name, size, created_at)
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6 WHERE EXISTS (SELECT 1 FROM cards WHERE id = ?2)",
+            rusqlite::params![
+                attachment.id,
+                attachment.card_id,
+                attachment.blob_hash,
+                attachment.file_name,
+                attachment.size,
+                attachment.created_at,
+            ],
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,225 @@
+// 数据库备份相关操作
+// 使用 SQLite 在线备份接口, 备份时无需关闭当前连接
+use super::{app_data_dir, priorities, schema, table_exists, with_connection, DbError, DbState, DATABASE_FILE_NAME};
+use crate::commands::backups::BackupInfo;
+use chrono::{DateTime, Datelike, Local, NaiveDateTime, TimeZone};
+use rusqlite::backup::{Backup, Progress};
+use rusqlite::{Connection, DatabaseName, OpenFlags};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const BACKUP_DIR_NAME: &str = "backups";
+const BACKUP_FILE_PREFIX: &str = "captaintodo-";
+const BACKUP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S%3f";
+/// 旧版本只精确到秒的备份文件名
+const LEGACY_BACKUP_TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+/// 自动备份间隔 (小时)
+const BACKUP_INTERVAL_HOURS: i64 = 24;
+/// 保留最近几天每天的最后一份备份
+const KEEP_DAILY: usize = 7;
+/// 保留最近几周每周的最后一份备份
+const KEEP_WEEKLY: usize = 4;
+
+/// 获取所有备份 (最新的在前)
+pub fn list_backups(app_handle: &AppHandle) -> Result<Vec<BackupInfo>, DbError> {
+    let dir = backup_dir(app_handle)?;
+    Ok(read_backups(&dir)?.into_iter().map(|(_, info)| info).collect())
+}
+
+/// 立即备份数据库, 并按保留策略清理旧备份
+pub fn create_backup(app_handle: &AppHandle) -> Result<BackupInfo, DbError> {
+    let dir = backup_dir(app_handle)?;
+    let info = with_connection(app_handle, |conn| backup_to(conn, &dir))?;
+    prune_backups(&dir)?;
+    Ok(info)
+}
+
+/// Schema 升级前备份数据库 (此时连接尚未放入 DbState)
+pub(crate) fn backup_before_upgrade(app_handle: &AppHandle, conn: &Connection) -> Result<BackupInfo, DbError> {
+    let dir = backup_dir(app_handle)?;
+    let info = backup_to(conn, &dir)?;
+    prune_backups(&dir)?;
+    Ok(info)
+}
+
+/// 距上次备份超过备份间隔时自动备份, 返回新建的备份
+pub fn run_scheduled_backup(app_handle: &AppHandle) -> Result<Option<BackupInfo>, DbError> {
+    let dir = backup_dir(app_handle)?;
+    let latest = read_backups(&dir)?.into_iter().next().map(|(time, _)| time);
+    let due = latest.is_none_or(|time| {
+        Local::now().signed_duration_since(time).num_hours() >= BACKUP_INTERVAL_HOURS
+    });
+    if !due {
+        return Ok(None);
+    }
+    create_backup(app_handle).map(Some)
+}
+
+/// 从备份恢复数据库
+///
+/// 备份先载入内存校验并升级到当前 Schema; 随后备份当前数据, 在数据库文件上打开新连接写入恢复的内容,
+/// 再替换 DbState 中的连接, 无需重启应用。
+pub fn restore_backup(app_handle: &AppHandle, file_name: &str) -> Result<(), DbError> {
+    let dir = backup_dir(app_handle)?;
+    if parse_backup_time(file_name).is_none() {
+        return Err(DbError::Validation(format!("invalid backup file name: {}", file_name)));
+    }
+    let backup_path = dir.join(file_name);
+    if !backup_path.is_file() {
+        return Err(DbError::not_found("backup", file_name));
+    }
+
+    let mut restored = Connection::open_in_memory()?;
+    restored.restore(DatabaseName::Main, &backup_path, None::<fn(Progress)>)?;
+    validate_backup(&restored, &backup_path)?;
+    schema::run_migrations(&restored)?;
+
+    let db_path = app_data_dir(app_handle)?.join(DATABASE_FILE_NAME);
+    let state = app_handle
+        .try_state::<DbState>()
+        .ok_or(DbError::NotInitialized)?;
+    let mut conn = state.conn.lock().map_err(|_| DbError::LockPoisoned)?;
+
+    // 恢复前先备份当前数据, 便于再恢复回来
+    backup_to(&conn, &dir)?;
+
+    let mut live = Connection::open(&db_path)?;
+    Backup::new(&restored, &mut live)?.run_to_completion(100, Duration::ZERO, None)?;
+    schema::run_migrations(&live)?;
+    priorities::load_current_scale(&live)?;
+    *conn = live;
+    drop(conn);
+
+    log::info!("Restored database from backup {:?}", backup_path);
+    prune_backups(&dir)?;
+    Ok(())
+}
+
+/// 备份中仍引用的附件文件哈希, 附件回收时需保留这些文件以便恢复
+pub(crate) fn backup_blob_hashes(app_handle: &AppHandle) -> Result<HashSet<String>, DbError> {
+    let dir = backup_dir(app_handle)?;
+    let mut hashes = HashSet::new();
+    for (_, info) in read_backups(&dir)? {
+        let conn = Connection::open_with_flags(dir.join(&info.file_name), OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        if !table_exists(&conn, "attachments")? {
+            continue;
+        }
+        let mut stmt = conn.prepare("SELECT DISTINCT blob_hash FROM attachments")?;
+        hashes.extend(stmt.query_map([], |row| row.get::<_, String>(0))?.filter_map(|h| h.ok()));
+    }
+    Ok(hashes)
+}
+
+fn backup_dir(app_handle: &AppHandle) -> Result<PathBuf, DbError> {
+    let dir = app_data_dir(app_handle)?.join(BACKUP_DIR_NAME);
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+/// 将当前数据库写入新的备份文件 (先写临时文件再重命名)
+fn backup_to(conn: &Connection, dir: &Path) -> Result<BackupInfo, DbError> {
+    // 文件名精确到毫秒; 同一毫秒内已有备份时顺延, 不覆盖已有文件
+    let mut now = Local::now();
+    let (file_name, path) = loop {
+        let file_name = format!("{}{}.db", BACKUP_FILE_PREFIX, now.format(BACKUP_TIME_FORMAT));
+        let path = dir.join(&file_name);
+        if !path.exists() {
+            break (file_name, path);
+        }
+        now += chrono::Duration::milliseconds(1);
+    };
+    let tmp_path = dir.join(format!("{}.tmp", file_name));
+
+    if let Err(e) = conn.backup(DatabaseName::Main, &tmp_path, None::<fn(Progress)>) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(DbError::from(e));
+    }
+    fs::rename(&tmp_path, &path)?;
+    log::info!("Database backed up to {:?}", path);
+
+    Ok(BackupInfo {
+        file_name,
+        created_at: now.to_rfc3339(),
+        size: fs::metadata(&path)?.len(),
+    })
+}
+
+/// 校验备份是完整的应用数据库
+fn validate_backup(conn: &Connection, path: &Path) -> Result<(), DbError> {
+    let check: String = conn.query_row("PRAGMA quick_check", [], |row| row.get(0))?;
+    if check != "ok" || !table_exists(conn, "projects")? {
+        return Err(DbError::Validation(format!("backup {:?} is not a valid database", path)));
+    }
+    Ok(())
+}
+
+fn parse_backup_time(file_name: &str) -> Option<DateTime<Local>> {
+    let stamp = file_name.strip_prefix(BACKUP_FILE_PREFIX)?.strip_suffix(".db")?;
+    let time = NaiveDateTime::parse_from_str(stamp, BACKUP_TIME_FORMAT)
+        .or_else(|_| NaiveDateTime::parse_from_str(stamp, LEGACY_BACKUP_TIME_FORMAT))
+        .ok()?;
+    Local.from_local_datetime(&time).earliest()
+}
+
+/// 读取备份目录中的备份文件, 最新的在前
+fn read_backups(dir: &Path) -> Result<Vec<(DateTime<Local>, BackupInfo)>, DbError> {
+    let mut backups = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let Some(time) = parse_backup_time(&file_name) else {
+            continue;
+        };
+        backups.push((
+            time,
+            BackupInfo {
+                file_name,
+                created_at: time.to_rfc3339(),
+                size: entry.metadata()?.len(),
+            },
+        ));
+    }
+    backups.sort_by_key(|(time, _)| std::cmp::Reverse(*time));
+    Ok(backups)
+}
+
+/// 按保留策略删除旧备份: 最近 KEEP_DAILY 天每天一份, 最近 KEEP_WEEKLY 周每周一份;
+/// 一个备份间隔内的备份 (含手动备份和恢复前的备份) 全部保留
+fn prune_backups(dir: &Path) -> Result<usize, DbError> {
+    let backups = read_backups(dir)?;
+    let now = Local::now();
+    let mut keep: HashSet<String> = backups
+        .iter()
+        .filter(|(time, _)| now.signed_duration_since(*time).num_hours() < BACKUP_INTERVAL_HOURS)
+        .map(|(_, info)| info.file_name.clone())
+        .collect();
+
+    let mut days = Vec::new();
+    let mut weeks = Vec::new();
+    for (time, info) in &backups {
+        // 备份按时间倒序, 每天/每周遇到的第一份即为最后一份
+        let day = time.date_naive();
+        if !days.contains(&day) && days.len() < KEEP_DAILY {
+            days.push(day);
+            keep.insert(info.file_name.clone());
+        }
+        let week = day.iso_week();
+        if !weeks.contains(&week) && weeks.len() < KEEP_WEEKLY {
+            weeks.push(week);
+            keep.insert(info.file_name.clone());
+        }
+    }
+
+    let mut removed = 0;
+    for (_, info) in backups.iter().filter(|(_, info)| !keep.contains(&info.file_name)) {
+        fs::remove_file(dir.join(&info.file_name))?;
+        removed += 1;
+    }
+    if removed > 0 {
+        log::info!("Removed {} old backups", removed);
+    }
+    Ok(removed)
+}
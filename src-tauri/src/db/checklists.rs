@@ -0,0 +1,307 @@
+// 卡片检查清单相关数据库操作
+use super::history::{record_history, UndoOp};
+use super::kanban::find_card_project;
+use super::{with_connection, with_transaction, DbError};
+use crate::commands::checklists::{ChecklistItem, ChecklistProgress};
+use chrono::Utc;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// 获取卡片的检查清单
+pub fn get_checklist(app_handle: &AppHandle, card_id: &str) -> Result<Vec<ChecklistItem>, DbError> {
+    let cid = card_id.to_string();
+    with_connection(app_handle, |conn| get_card_checklist_items(conn, &cid))
+}
+
+/// 添加检查清单条目
+pub fn add_checklist_item(
+    app_handle: &AppHandle,
+    card_id: &str,
+    text: &str,
+) -> Result<ChecklistItem, DbError> {
+    let cid = card_id.to_string();
+    let text = validate_text(text)?;
+    with_transaction(app_handle, |conn| {
+        let project_id = find_card_project(conn, &cid)?
+            .ok_or_else(|| DbError::not_found("card", &cid))?;
+
+        let now = Utc::now().to_rfc3339();
+        let position = conn.query_row(
+            "SELECT COALESCE(MAX(position), -1) + 1 FROM checklist_items WHERE card_id = ?",
+            [&cid],
+            |row| row.get::<_, i32>(0),
+        )?;
+
+        let item = ChecklistItem {
+            id: Uuid::new_v4().to_string(),
+            card_id: cid.clone(),
+            text,
+            done: false,
+            position,
+            created_at: now.clone(),
+            updated_at: now,
+        };
+        conn.execute(
+            "INSERT INTO checklist_items (id, card_id, text, done, position, created_at, updated_at)
+             VALUES (?, ?, ?, 0, ?, ?, ?)",
+            rusqlite::params![item.id, item.card_id, item.text, item.position, item.created_at, item.updated_at],
+        )?;
+
+        record_history(
+            conn,
+            &project_id,
+            format!("添加检查项「{}」", item.text),
+            vec![UndoOp::DeleteChecklistItem { item_id: item.id.clone() }],
+            vec![UndoOp::RestoreChecklistItems { items: vec![item.clone()] }],
+        )?;
+
+        Ok(item)
+    })
+}
+
+/// 切换检查清单条目的完成状态
+pub fn toggle_checklist_item(app_handle: &AppHandle, item_id: &str) -> Result<ChecklistItem, DbError> {
+    let iid = item_id.to_string();
+    with_transaction(app_handle, |conn| {
+        let (project_id, previous) = find_checklist_item(conn, &iid)?
+            .ok_or_else(|| DbError::not_found("checklist_item", &iid))?;
+
+        let now = Utc::now().to_rfc3339();
+        let done = !previous.done;
+        conn.execute(
+            "UPDATE checklist_items SET done = ?, updated_at = ? WHERE id = ?",
+            rusqlite::params![done, now, iid],
+        )?;
+
+        let toggled = ChecklistItem {
+            done,
+            updated_at: now,
+            ..previous.clone()
+        };
+
+        let label = if done { "完成检查项" } else { "取消完成检查项" };
+        record_history(
+            conn,
+            &project_id,
+            format!("{}「{}」", label, toggled.text),
+            vec![UndoOp::RestoreChecklistItems { items: vec![previous] }],
+            vec![UndoOp::RestoreChecklistItems { items: vec![toggled.clone()] }],
+        )?;
+
+        Ok(toggled)
+    })
+}
+
+/// 按传入 ID 顺序保存检查清单排序
+pub fn reorder_checklist_items(
+    app_handle: &AppHandle,
+    card_id: &str,
+    item_ids: &[String],
+) -> Result<Vec<ChecklistItem>, DbError> {
+    let cid = card_id.to_string();
+    let ids = item_ids.to_vec();
+    with_transaction(app_handle, |conn| {
+        let project_id = find_card_project(conn, &cid)?
+            .ok_or_else(|| DbError::not_found("card", &cid))?;
+        let previous = get_card_checklist_items(conn, &cid)?;
+
+        let now = Utc::now().to_rfc3339();
+        for (position, item_id) in ids.iter().enumerate() {
+            conn.execute(
+                "UPDATE checklist_items SET position = ?, updated_at = ? WHERE id = ? AND card_id = ?",
+                rusqlite::params![position as i32, now, item_id, cid],
+            )?;
+        }
+
+        let reordered = get_card_checklist_items(conn, &cid)?;
+        record_history(
+            conn,
+            &project_id,
+            "调整检查清单顺序",
+            vec![UndoOp::RestoreChecklistItems { items: previous }],
+            vec![UndoOp::RestoreChecklistItems { items: reordered.clone() }],
+        )?;
+
+        Ok(reordered)
+    })
+}
+
+/// 删除检查清单条目
+pub fn delete_checklist_item(app_handle: &AppHandle, item_id: &str) -> Result<(), DbError> {
+    let iid = item_id.to_string();
+    with_transaction(app_handle, |conn| {
+        let Some((project_id, previous)) = find_checklist_item(conn, &iid)? else {
+            return Ok(());
+        };
+
+        conn.execute("DELETE FROM checklist_items WHERE id = ?", [&iid])?;
+
+        record_history(
+            conn,
+            &project_id,
+            format!("删除检查项「{}」", previous.text),
+            vec![UndoOp::RestoreChecklistItems { items: vec![previous] }],
+            vec![UndoOp::DeleteChecklistItem { item_id: iid.clone() }],
+        )
+    })
+}
+
+fn validate_text(text: &str) -> Result<String, DbError> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(DbError::Validation("checklist item text is empty".to_string()));
+    }
+    Ok(text.to_string())
+}
+
+/// 按 ID 查询检查清单条目及其卡片所属项目
+fn find_checklist_item(
+    conn: &Connection,
+    item_id: &str,
+) -> Result<Option<(String, ChecklistItem)>, DbError> {
+    let result = conn.query_row(
+        "SELECT c.project_id, ci.id, ci.card_id, ci.text, ci.done, ci.position, ci.created_at, ci.updated_at
+         FROM checklist_items ci JOIN cards c ON c.id = ci.card_id
+         WHERE ci.id = ?",
+        [item_id],
+        |row| {
+            Ok((
+                row.get(0)?,
+                ChecklistItem {
+                    id: row.get(1)?,
+                    card_id: row.get(2)?,
+                    text: row.get(3)?,
+                    done: row.get(4)?,
+                    position: row.get(5)?,
+                    created_at: row.get(6)?,
+                    updated_at: row.get(7)?,
+                },
+            ))
+        },
+    );
+
+    match result {
+        Ok(found) => Ok(Some(found)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
+fn map_item(row: &rusqlite::Row<'_>) -> rusqlite::Result<ChecklistItem> {
+    Ok(ChecklistItem {
+        id: row.get(0)?,
+        card_id: row.get(1)?,
+        text: row.get(2)?,
+        done: row.get(3)?,
+        position: row.get(4)?,
+        created_at: row.get(5)?,
+        updated_at: row.get(6)?,
+    })
+}
+
+/// 获取卡片的全部检查清单条目
+pub(crate) fn get_card_checklist_items(
+    conn: &Connection,
+    card_id: &str,
+) -> Result<Vec<ChecklistItem>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, card_id, text, done, position, created_at, updated_at
+         FROM checklist_items WHERE card_id = ? ORDER BY position, created_at"
+    )?;
+
+    let items: Vec<ChecklistItem> = stmt
+        .query_map([card_id], map_item)?
+        .filter_map(|i| i.ok())
+        .collect();
+    Ok(items)
+}
+
+/// 获取项目内所有卡片的检查清单条目
+pub(crate) fn get_project_checklist_items(
+    conn: &Connection,
+    project_id: &str,
+) -> Result<Vec<ChecklistItem>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT ci.id, ci.card_id, ci.text, ci.done, ci.position, ci.created_at, ci.updated_at
+         FROM checklist_items ci JOIN cards c ON c.id = ci.card_id
+         WHERE c.project_id = ? ORDER BY ci.card_id, ci.position"
+    )?;
+
+    let items: Vec<ChecklistItem> = stmt
+        .query_map([project_id], map_item)?
+        .filter_map(|i| i.ok())
+        .collect();
+    Ok(items)
+}
+
+/// 恢复检查清单条目, 已存在的条目会被覆盖, 卡片已不存在的条目会被跳过
+pub(crate) fn restore_checklist_items(
+    conn: &Connection,
+    items: &[ChecklistItem],
+) -> Result<(), DbError> {
+    for item in items {
+        conn.execute(
+            "INSERT INTO checklist_items (id, card_id, text, done, position, created_at, updated_at)
+             SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7 WHERE EXISTS (SELECT 1 FROM cards WHERE id = ?2)
+             ON CONFLICT(id) DO UPDATE SET
+                 text = excluded.text,
+                 done = excluded.done,
+                 position = excluded.position,
+                 updated_at = excluded.updated_at",
+            rusqlite::params![
+                item.id,
+                item.card_id,
+                item.text,
+                item.done,
+                item.position,
+                item.created_at,
+                item.updated_at,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// 获取卡片的检查清单进度
+pub(crate) fn get_checklist_progress(
+    conn: &Connection,
+    card_id: &str,
+) -> Result<ChecklistProgress, DbError> {
+    let progress = conn.query_row(
+        "SELECT COALESCE(SUM(done), 0), COUNT(*) FROM checklist_items WHERE card_id = ?",
+        [card_id],
+        |row| {
+            Ok(ChecklistProgress {
+                done: row.get(0)?,
+                total: row.get(1)?,
+            })
+        },
+    )?;
+    Ok(progress)
+}
+
+/// 获取项目内所有卡片的检查清单进度, 按卡片ID分组
+pub(crate) fn get_project_checklist_progress(
+    conn: &Connection,
+    project_id: &str,
+) -> Result<HashMap<String, ChecklistProgress>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT ci.card_id, SUM(ci.done), COUNT(*)
+         FROM checklist_items ci JOIN cards c ON c.id = ci.card_id
+         WHERE c.project_id = ? GROUP BY ci.card_id"
+    )?;
+
+    let rows = stmt.query_map([project_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            ChecklistProgress {
+                done: row.get(1)?,
+                total: row.get(2)?,
+            },
+        ))
+    })?;
+
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
@@ -0,0 +1,225 @@
+// 卡片评论相关数据库操作
+use super::history::{record_history, UndoOp};
+use super::kanban::find_card_project;
+use super::{with_connection, with_transaction, DbError};
+use crate::commands::comments::Comment;
+use chrono::Utc;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// 获取卡片的评论
+pub fn list_comments(app_handle: &AppHandle, card_id: &str) -> Result<Vec<Comment>, DbError> {
+    let cid = card_id.to_string();
+    with_connection(app_handle, |conn| get_card_comments(conn, &cid))
+}
+
+/// 添加评论
+pub fn add_comment(app_handle: &AppHandle, card_id: &str, body: &str) -> Result<Comment, DbError> {
+    let cid = card_id.to_string();
+    let body = validate_body(body)?;
+    with_transaction(app_handle, |conn| {
+        let project_id = find_card_project(conn, &cid)?
+            .ok_or_else(|| DbError::not_found("card", &cid))?;
+
+        let now = Utc::now().to_rfc3339();
+        let comment = Comment {
+            id: Uuid::new_v4().to_string(),
+            card_id: cid.clone(),
+            body,
+            created_at: now.clone(),
+            updated_at: now,
+        };
+        conn.execute(
+            "INSERT INTO comments (id, card_id, body, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+            rusqlite::params![comment.id, comment.card_id, comment.body, comment.created_at, comment.updated_at],
+        )?;
+
+        record_history(
+            conn,
+            &project_id,
+            "添加评论",
+            vec![UndoOp::DeleteComment { comment_id: comment.id.clone() }],
+            vec![UndoOp::RestoreComments { comments: vec![comment.clone()] }],
+        )?;
+
+        Ok(comment)
+    })
+}
+
+/// 编辑评论
+pub fn edit_comment(app_handle: &AppHandle, comment_id: &str, body: &str) -> Result<Comment, DbError> {
+    let id = comment_id.to_string();
+    let body = validate_body(body)?;
+    with_transaction(app_handle, |conn| {
+        let (project_id, previous) = find_comment(conn, &id)?
+            .ok_or_else(|| DbError::not_found("comment", &id))?;
+
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE comments SET body = ?, updated_at = ? WHERE id = ?",
+            rusqlite::params![body, now, id],
+        )?;
+
+        let edited = Comment {
+            body,
+            updated_at: now,
+            ..previous.clone()
+        };
+
+        record_history(
+            conn,
+            &project_id,
+            "编辑评论",
+            vec![UndoOp::RestoreComments { comments: vec![previous] }],
+            vec![UndoOp::RestoreComments { comments: vec![edited.clone()] }],
+        )?;
+
+        Ok(edited)
+    })
+}
+
+/// 删除评论
+pub fn delete_comment(app_handle: &AppHandle, comment_id: &str) -> Result<(), DbError> {
+    let id = comment_id.to_string();
+    with_transaction(app_handle, |conn| {
+        let Some((project_id, previous)) = find_comment(conn, &id)? else {
+            return Ok(());
+        };
+
+        conn.execute("DELETE FROM comments WHERE id = ?", [&id])?;
+
+        record_history(
+            conn,
+            &project_id,
+            "删除评论",
+            vec![UndoOp::RestoreComments { comments: vec![previous] }],
+            vec![UndoOp::DeleteComment { comment_id: id.clone() }],
+        )
+    })
+}
+
+fn validate_body(body: &str) -> Result<String, DbError> {
+    if body.trim().is_empty() {
+        return Err(DbError::Validation("comment body is empty".to_string()));
+    }
+    // 保留 Markdown 正文中的前导缩进, 只去掉末尾空白
+    Ok(body.trim_end().to_string())
+}
+
+/// 按 ID 查询评论及其卡片所属项目
+fn find_comment(conn: &Connection, comment_id: &str) -> Result<Option<(String, Comment)>, DbError> {
+    let result = conn.query_row(
+        "SELECT c.project_id, cm.id, cm.card_id, cm.body, cm.created_at, cm.updated_at
+         FROM comments cm JOIN cards c ON c.id = cm.card_id
+         WHERE cm.id = ?",
+        [comment_id],
+        |row| {
+            Ok((
+                row.get(0)?,
+                Comment {
+                    id: row.get(1)?,
+                    card_id: row.get(2)?,
+                    body: row.get(3)?,
+                    created_at: row.get(4)?,
+                    updated_at: row.get(5)?,
+                },
+            ))
+        },
+    );
+
+    match result {
+        Ok(found) => Ok(Some(found)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
+fn map_comment(row: &rusqlite::Row<'_>) -> rusqlite::Result<Comment> {
+    Ok(Comment {
+        id: row.get(0)?,
+        card_id: row.get(1)?,
+        body: row.get(2)?,
+        created_at: row.get(3)?,
+        updated_at: row.get(4)?,
+    })
+}
+
+/// 获取卡片的全部评论
+pub(crate) fn get_card_comments(conn: &Connection, card_id: &str) -> Result<Vec<Comment>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, card_id, body, created_at, updated_at
+         FROM comments WHERE card_id = ? ORDER BY created_at, rowid"
+    )?;
+
+    let comments: Vec<Comment> = stmt
+        .query_map([card_id], map_comment)?
+        .filter_map(|c| c.ok())
+        .collect();
+    Ok(comments)
+}
+
+/// 获取项目内所有卡片的评论
+pub(crate) fn get_project_comments(conn: &Connection, project_id: &str) -> Result<Vec<Comment>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT cm.id, cm.card_id, cm.body, cm.created_at, cm.updated_at
+         FROM comments cm JOIN cards c ON c.id = cm.card_id
+         WHERE c.project_id = ? ORDER BY cm.created_at, cm.rowid"
+    )?;
+
+    let comments: Vec<Comment> = stmt
+        .query_map([project_id], map_comment)?
+        .filter_map(|c| c.ok())
+        .collect();
+    Ok(comments)
+}
+
+/// 恢复评论, 已存在的评论会被覆盖, 卡片已不存在的评论会被跳过
+pub(crate) fn restore_comments(conn: &Connection, comments: &[Comment]) -> Result<(), DbError> {
+    for comment in comments {
+        conn.execute(
+            "INSERT INTO comments (id, card_id, body, created_at, updated_at)
+             SELECT ?1, ?2, ?3, ?4, ?5 WHERE EXISTS (SELECT 1 FROM cards WHERE id = ?2)
+             ON CONFLICT(id) DO UPDATE SET
+                 body = excluded.body,
+                 updated_at = excluded.updated_at",
+            rusqlite::params![
+                comment.id,
+                comment.card_id,
+                comment.body,
+                comment.created_at,
+                comment.updated_at,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// 获取卡片的评论数
+pub(crate) fn get_comment_count(conn: &Connection, card_id: &str) -> Result<u32, DbError> {
+    let count = conn.query_row(
+        "SELECT COUNT(*) FROM comments WHERE card_id = ?",
+        [card_id],
+        |row| row.get(0),
+    )?;
+    Ok(count)
+}
+
+/// 获取项目内所有卡片的评论数, 按卡片ID分组
+pub(crate) fn get_project_comment_counts(
+    conn: &Connection,
+    project_id: &str,
+) -> Result<HashMap<String, u32>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT cm.card_id, COUNT(*)
+         FROM comments cm JOIN cards c ON c.id = cm.card_id
+         WHERE c.project_id = ? GROUP BY cm.card_id"
+    )?;
+
+    let rows = stmt.query_map([project_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, u32>(1)?))
+    })?;
+
+    Ok(rows.filter_map(|r| r.ok()).collect())
+}
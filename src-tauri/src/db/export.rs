@@ -0,0 +1,199 @@
+// 项目导出相关数据库操作
+use super::checklists::get_project_checklist_items;
+use super::comments::get_project_comments;
+use super::kanban::get_board_impl;
+use super::projects::find_project;
+use super::tags::get_project_tags;
+use super::{with_connection, DbError};
+use crate::commands::checklists::ChecklistItem;
+use crate::commands::export::{ExportFormat, ProjectExport};
+use crate::commands::kanban::{Board, Card};
+use crate::commands::tags::Tag;
+use chrono::Utc;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::AppHandle;
+
+/// JSON 导出文档的格式标识
+pub const EXPORT_FORMAT: &str = "captaintodo-project";
+/// JSON 导出文档的当前版本
+pub const EXPORT_VERSION: u32 = 1;
+
+/// 将项目导出到指定文件
+pub fn export_project(
+    app_handle: &AppHandle,
+    project_id: &str,
+    format: ExportFormat,
+    path: &Path,
+) -> Result<(), DbError> {
+    let pid = project_id.to_string();
+    let content = with_connection(app_handle, |conn| {
+        let document = build_export(conn, &pid)?;
+        match format {
+            ExportFormat::Json => Ok(serde_json::to_string_pretty(&document)?),
+            ExportFormat::Csv => Ok(render_csv(&document)),
+            ExportFormat::Markdown => Ok(render_markdown(&document)),
+        }
+    })?;
+
+    std::fs::write(path, content)?;
+    log::info!("Exported project {} to {:?}", project_id, path);
+    Ok(())
+}
+
+/// 读取项目的完整导出文档
+pub(crate) fn build_export(conn: &Connection, project_id: &str) -> Result<ProjectExport, DbError> {
+    let project = find_project(conn, project_id)?
+        .ok_or_else(|| DbError::not_found("project", project_id))?;
+
+    Ok(ProjectExport {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        exported_at: Utc::now().to_rfc3339(),
+        board: get_board_impl(conn, project_id)?,
+        tags: get_project_tags(conn, project_id)?,
+        checklist_items: get_project_checklist_items(conn, project_id)?,
+        comments: get_project_comments(conn, project_id)?,
+        project,
+    })
+}
+
+/// 按列顺序排列卡片, 不属于任何列的卡片排在最后
+fn ordered_cards(board: &Board) -> Vec<&Card> {
+    let cards: HashMap<&str, &Card> = board.cards.iter().map(|c| (c.id.as_str(), c)).collect();
+    let mut ordered: Vec<&Card> = board
+        .columns
+        .iter()
+        .flat_map(|column| column.card_ids.iter().filter_map(|id| cards.get(id.as_str()).copied()))
+        .collect();
+    let orphans = board
+        .cards
+        .iter()
+        .filter(|card| !board.columns.iter().any(|column| column.id == card.column_id));
+    ordered.extend(orphans);
+    ordered
+}
+
+fn tag_names(tags: &[Tag], card: &Card) -> Vec<String> {
+    let ids = card.tag_ids.as_deref().unwrap_or_default();
+    tags.iter()
+        .filter(|tag| ids.contains(&tag.id))
+        .map(|tag| tag.name.clone())
+        .collect()
+}
+
+fn render_csv(document: &ProjectExport) -> String {
+    let board = &document.board;
+    let headers = [
+        "标题", "描述", "列", "状态", "优先级", "标签", "检查清单",
+        "开始时间", "截止时间", "创建时间", "更新时间",
+    ];
+
+    let mut lines = vec![csv_row(headers.iter().map(|h| h.to_string()))];
+    for card in ordered_cards(board) {
+        let column = board.columns.iter().find(|c| c.id == card.column_id);
+        let progress = card.checklist_progress.unwrap_or_default();
+        lines.push(csv_row([
+            card.title.clone(),
+            card.description.clone().unwrap_or_default(),
+            column.map(|c| c.title.clone()).unwrap_or_default(),
+            if card.completed.unwrap_or(false) { "已完成" } else { "未完成" }.to_string(),
+            card.priority.as_ref().map(|p| p.as_str().to_string()).unwrap_or_default(),
+            tag_names(&document.tags, card).join("; "),
+            if progress.total > 0 { format!("{}/{}", progress.done, progress.total) } else { String::new() },
+            card.start_date.map(|d| d.to_string()).unwrap_or_default(),
+            card.due_date.map(|d| d.to_string()).unwrap_or_default(),
+            card.created_at.clone(),
+            card.updated_at.clone(),
+        ]));
+    }
+
+    // 带 BOM, 便于表格软件识别 UTF-8
+    format!("\u{feff}{}\n", lines.join("\n"))
+}
+
+fn csv_row(fields: impl IntoIterator<Item = String>) -> String {
+    fields
+        .into_iter()
+        .map(|field| format!("\"{}\"", field.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn render_markdown(document: &ProjectExport) -> String {
+    let project = &document.project;
+    let board = &document.board;
+
+    let mut checklists: HashMap<&str, Vec<&ChecklistItem>> = HashMap::new();
+    for item in &document.checklist_items {
+        checklists.entry(item.card_id.as_str()).or_default().push(item);
+    }
+
+    let mut out = format!("# {}\n", single_line(&project.name));
+    if let Some(description) = project.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        out.push_str(&format!("\n{}\n", description.trim()));
+    }
+
+    let cards: HashMap<&str, &Card> = board.cards.iter().map(|c| (c.id.as_str(), c)).collect();
+    for column in &board.columns {
+        out.push_str(&format!("\n## {}\n", single_line(&column.title)));
+        let column_cards: Vec<&Card> = column
+            .card_ids
+            .iter()
+            .filter_map(|id| cards.get(id.as_str()).copied())
+            .collect();
+        if !column_cards.is_empty() {
+            out.push('\n');
+        }
+        for card in column_cards {
+            out.push_str(&markdown_card(document, card, checklists.get(card.id.as_str())));
+        }
+    }
+
+    out
+}
+
+fn markdown_card(document: &ProjectExport, card: &Card, checklist: Option<&Vec<&ChecklistItem>>) -> String {
+    let mut line = format!("- {} {}", task_box(card.completed.unwrap_or(false)), single_line(&card.title));
+
+    let mut meta = Vec::new();
+    if let Some(priority) = &card.priority {
+        meta.push(format!("优先级: {}", priority.as_str()));
+    }
+    if let Some(due) = card.due_date {
+        meta.push(format!("截止: {}", due));
+    }
+    let tags = tag_names(&document.tags, card);
+    if !tags.is_empty() {
+        meta.push(format!("标签: {}", tags.join(", ")));
+    }
+    if !meta.is_empty() {
+        line.push_str(&format!(" ({})", meta.join("; ")));
+    }
+    line.push('\n');
+
+    // 描述和检查清单缩进到任务项下
+    if let Some(description) = card.description.as_deref().filter(|d| !d.trim().is_empty()) {
+        for text in description.trim_end().lines() {
+            if text.trim().is_empty() {
+                line.push('\n');
+            } else {
+                line.push_str(&format!("  {}\n", text));
+            }
+        }
+    }
+    for item in checklist.into_iter().flatten() {
+        line.push_str(&format!("  - {} {}\n", task_box(item.done), single_line(&item.text)));
+    }
+
+    line
+}
+
+fn task_box(done: bool) -> &'static str {
+    if done { "[x]" } else { "[ ]" }
+}
+
+fn single_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,68 @@
+// 卡片字段 (优先级、开始和截止日期) 的存储与解析
+// 数据库中只保存规范格式; 导入和数据迁移时用这里的解析函数整理外部或旧数据。
+use super::priorities::find_current_level;
+use crate::commands::kanban::{CardDate, Priority};
+use chrono::{Local, NaiveDate, NaiveDateTime, TimeZone};
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+impl ToSql for Priority {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for Priority {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let text = value.as_str()?;
+        Priority::parse(text)
+            .ok_or_else(|| FromSqlError::Other(format!("priority {} is not in the priority scale", text).into()))
+    }
+}
+
+impl ToSql for CardDate {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.to_string()))
+    }
+}
+
+impl FromSql for CardDate {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let text = value.as_str()?;
+        CardDate::parse(text)
+            .ok_or_else(|| FromSqlError::Other(format!("invalid date: {}", text).into()))
+    }
+}
+
+/// 优先级可使用当前刻度中的标识 (不区分大小写) 或名称
+pub(crate) fn parse_priority(value: &str) -> Result<Option<Priority>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    find_current_level(value)
+        .and_then(|level| Priority::parse(&level))
+        .map(Some)
+        .ok_or_else(|| format!("无法识别的优先级: {}", value))
+}
+
+/// 日期统一为 RFC 3339 或 YYYY-MM-DD; 不带时区的时间按本地时间处理
+pub(crate) fn normalize_date(value: &str) -> Result<Option<CardDate>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if let Some(date) = CardDate::parse(value) {
+        return Ok(Some(date));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y/%m/%d") {
+        return Ok(Some(CardDate::Date(date)));
+    }
+    for format in ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y/%m/%d %H:%M:%S", "%Y/%m/%d %H:%M"] {
+        if let Ok(datetime) = NaiveDateTime::parse_from_str(value, format) {
+            if let Some(local) = Local.from_local_datetime(&datetime).earliest() {
+                return Ok(Some(CardDate::DateTime(local.fixed_offset())));
+            }
+        }
+    }
+    Err(format!("无法识别的日期: {}", value))
+}
@@ -0,0 +1,373 @@
+// 撤销/重做历史相关数据库操作
+// 每次修改都会在同一事务中记录一组逆操作 (undo) 和正向操作 (redo),
+// 以快照形式持久化到 undo_history 表, 应用重启后仍可撤销。
+use super::rank::{self, RankScope};
+use super::trash::{self, TrashEntry};
+use super::{archive, attachments, checklists, comments, kanban, metrics, projects, tags, with_connection, with_transaction, DbError};
+use crate::commands::archive::ArchiveKind;
+use crate::commands::attachments::Attachment;
+use crate::commands::checklists::ChecklistItem;
+use crate::commands::comments::Comment;
+use crate::commands::history::HistoryEntry;
+use crate::commands::kanban::{Board, Card, Column, ColumnCardOrder};
+use crate::commands::projects::Project;
+use crate::commands::tags::Tag;
+use chrono::Utc;
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+/// 项目列表级操作 (创建/删除/排序项目) 使用的作用域
+pub const PROJECT_LIST_SCOPE: &str = "projects";
+
+/// 每个作用域最多保留的历史条数
+const MAX_HISTORY_PER_SCOPE: i64 = 100;
+
+const DEFAULT_HISTORY_LIMIT: u32 = 50;
+
+/// 项目排序快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectPosition {
+    pub id: String,
+    pub position: i32,
+}
+
+/// 可重放的数据操作, 以 JSON 形式存储
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum UndoOp {
+    RestoreCard { project_id: String, card: Card },
+    DeleteCard { card_id: String },
+    RestoreColumn { project_id: String, column: Column },
+    DeleteColumn { column_id: String },
+    RestoreBoard { project_id: String, board: Board },
+    RestoreCardOrder { columns: Vec<ColumnCardOrder> },
+    RestoreProject { project: Project, board: Option<Board> },
+    DeleteProject { project_id: String },
+    RestoreProjectPositions { positions: Vec<ProjectPosition> },
+    RestoreProjectRecords { project_id: String, records: projects::ProjectRecords },
+    RestoreTag { project_id: String, tag: Tag, card_ids: Vec<String> },
+    DeleteTag { tag_id: String },
+    RestoreChecklistItems { items: Vec<ChecklistItem> },
+    DeleteChecklistItem { item_id: String },
+    RestoreComments { comments: Vec<Comment> },
+    DeleteComment { comment_id: String },
+    RestoreAttachments { attachments: Vec<Attachment> },
+    DeleteAttachment { attachment_id: String },
+    SetArchived { kind: ArchiveKind, id: String, archived_at: Option<String> },
+    PutInTrash { entry: TrashEntry },
+    RemoveFromTrash { trash_id: String },
+}
+
+/// 记录一条历史, 并清空该作用域的重做栈 (应在调用方的事务中执行)
+pub fn record_history(
+    conn: &Connection,
+    scope: &str,
+    label: impl Into<String>,
+    undo_ops: Vec<UndoOp>,
+    redo_ops: Vec<UndoOp>,
+) -> Result<(), DbError> {
+    conn.execute(
+        "DELETE FROM undo_history WHERE scope = ? AND undone = 1",
+        [scope],
+    )?;
+
+    conn.execute(
+        "INSERT INTO undo_history (scope, label, undo_ops, redo_ops, undone, created_at)
+         VALUES (?, ?, ?, ?, 0, ?)",
+        rusqlite::params![
+            scope,
+            label.into(),
+            serde_json::to_string(&undo_ops)?,
+            serde_json::to_string(&redo_ops)?,
+            Utc::now().to_rfc3339(),
+        ],
+    )?;
+
+    conn.execute(
+        "DELETE FROM undo_history WHERE scope = ?1 AND id NOT IN (
+             SELECT id FROM undo_history WHERE scope = ?1 ORDER BY id DESC LIMIT ?2
+         )",
+        rusqlite::params![scope, MAX_HISTORY_PER_SCOPE],
+    )?;
+
+    Ok(())
+}
+
+/// 撤销
+pub fn undo(app_handle: &AppHandle, scope: &str) -> Result<Option<HistoryEntry>, DbError> {
+    let scope = scope.to_string();
+    with_transaction(app_handle, |conn| undo_impl(conn, &scope))
+}
+
+fn undo_impl(conn: &Connection, scope: &str) -> Result<Option<HistoryEntry>, DbError> {
+    let result = conn.query_row(
+        "SELECT id, undo_ops FROM undo_history WHERE scope = ? AND undone = 0 ORDER BY id DESC LIMIT 1",
+        [scope],
+        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+    );
+    let (id, ops) = match result {
+        Ok(found) => found,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(DbError::from(e)),
+    };
+
+    apply_ops(conn, &serde_json::from_str::<Vec<UndoOp>>(&ops)?)?;
+    conn.execute("UPDATE undo_history SET undone = 1 WHERE id = ?", [id])?;
+
+    find_entry(conn, id).map(Some)
+}
+
+/// 重做
+pub fn redo(app_handle: &AppHandle, scope: &str) -> Result<Option<HistoryEntry>, DbError> {
+    let scope = scope.to_string();
+    with_transaction(app_handle, |conn| redo_impl(conn, &scope))
+}
+
+fn redo_impl(conn: &Connection, scope: &str) -> Result<Option<HistoryEntry>, DbError> {
+    let result = conn.query_row(
+        "SELECT id, redo_ops FROM undo_history WHERE scope = ? AND undone = 1 ORDER BY id ASC LIMIT 1",
+        [scope],
+        |row| Ok((row.get::<_, i64>(0)?, row.get::<_, String>(1)?)),
+    );
+    let (id, ops) = match result {
+        Ok(found) => found,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(DbError::from(e)),
+    };
+
+    apply_ops(conn, &serde_json::from_str::<Vec<UndoOp>>(&ops)?)?;
+    conn.execute("UPDATE undo_history SET undone = 0 WHERE id = ?", [id])?;
+
+    find_entry(conn, id).map(Some)
+}
+
+/// 获取撤销历史
+pub fn get_undo_history(
+    app_handle: &AppHandle,
+    scope: &str,
+    limit: Option<u32>,
+) -> Result<Vec<HistoryEntry>, DbError> {
+    let scope = scope.to_string();
+    with_connection(app_handle, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, scope, label, undone, created_at FROM undo_history
+             WHERE scope = ? ORDER BY id DESC LIMIT ?",
+        )?;
+
+        let entries_iter = stmt.query_map(
+            rusqlite::params![scope, limit.unwrap_or(DEFAULT_HISTORY_LIMIT)],
+            map_entry,
+        )?;
+
+        let entries: Vec<HistoryEntry> = entries_iter.filter_map(|e| e.ok()).collect();
+        Ok(entries)
+    })
+}
+
+fn find_entry(conn: &Connection, id: i64) -> Result<HistoryEntry, DbError> {
+    let entry = conn.query_row(
+        "SELECT id, scope, label, undone, created_at FROM undo_history WHERE id = ?",
+        [id],
+        map_entry,
+    )?;
+    Ok(entry)
+}
+
+fn map_entry(row: &rusqlite::Row<'_>) -> rusqlite::Result<HistoryEntry> {
+    let undone: i32 = row.get(3)?;
+    Ok(HistoryEntry {
+        id: row.get(0)?,
+        scope: row.get(1)?,
+        label: row.get(2)?,
+        undone: undone == 1,
+        created_at: row.get(4)?,
+    })
+}
+
+/// 按顺序重放操作
+pub(crate) fn apply_ops(conn: &Connection, ops: &[UndoOp]) -> Result<(), DbError> {
+    for op in ops {
+        match op {
+            UndoOp::RestoreCard { project_id, card } => upsert_card(conn, project_id, card)?,
+            UndoOp::DeleteCard { card_id } => {
+                conn.execute("DELETE FROM cards WHERE id = ?", [card_id])?;
+            }
+            UndoOp::RestoreColumn { project_id, column } => upsert_column(conn, project_id, column)?,
+            UndoOp::DeleteColumn { column_id } => {
+                conn.execute("DELETE FROM cards WHERE column_id = ?", [column_id])?;
+                conn.execute("DELETE FROM columns WHERE id = ?", [column_id])?;
+            }
+            UndoOp::RestoreBoard { project_id, board } => {
+                kanban::save_board_impl(conn, project_id, board)?;
+            }
+            UndoOp::RestoreCardOrder { columns } => {
+                for order in columns {
+                    kanban::set_column_card_order(conn, order)?;
+                }
+            }
+            UndoOp::RestoreProject { project, board } => {
+                upsert_project(conn, project)?;
+                if let Some(board) = board {
+                    kanban::save_board_impl(conn, &project.id, board)?;
+                }
+            }
+            UndoOp::DeleteProject { project_id } => {
+                projects::delete_project_impl(conn, project_id)?;
+            }
+            UndoOp::RestoreProjectPositions { positions } => {
+                let mut positions = positions.clone();
+                positions.sort_by_key(|entry| entry.position);
+                let ids: Vec<String> = positions.into_iter().map(|entry| entry.id).collect();
+                rank::apply_order(conn, RankScope::Projects, &ids)?;
+            }
+            UndoOp::RestoreProjectRecords { project_id, records } => {
+                projects::restore_project_records(conn, project_id, records)?;
+            }
+            UndoOp::RestoreTag { project_id, tag, card_ids } => {
+                tags::restore_tag(conn, project_id, tag, card_ids)?;
+            }
+            UndoOp::DeleteTag { tag_id } => {
+                conn.execute("DELETE FROM tags WHERE id = ?", [tag_id])?;
+            }
+            UndoOp::RestoreChecklistItems { items } => {
+                checklists::restore_checklist_items(conn, items)?;
+            }
+            UndoOp::DeleteChecklistItem { item_id } => {
+                conn.execute("DELETE FROM checklist_items WHERE id = ?", [item_id])?;
+            }
+            UndoOp::RestoreComments { comments } => {
+                comments::restore_comments(conn, comments)?;
+            }
+            UndoOp::DeleteComment { comment_id } => {
+                conn.execute("DELETE FROM comments WHERE id = ?", [comment_id])?;
+            }
+            UndoOp::RestoreAttachments { attachments } => {
+                attachments::restore_attachments(conn, attachments)?;
+            }
+            UndoOp::DeleteAttachment { attachment_id } => {
+                conn.execute("DELETE FROM attachments WHERE id = ?", [attachment_id])?;
+            }
+            UndoOp::SetArchived { kind, id, archived_at } => {
+                archive::set_archived_at(conn, *kind, id, archived_at.as_deref())?;
+            }
+            UndoOp::PutInTrash { entry } => trash::put_entry(conn, entry)?,
+            UndoOp::RemoveFromTrash { trash_id } => trash::remove_entry(conn, trash_id)?,
+        }
+    }
+    Ok(())
+}
+
+fn upsert_card(conn: &Connection, project_id: &str, card: &Card) -> Result<(), DbError> {
+    let previous_column: Option<String> = conn
+        .query_row("SELECT column_id FROM cards WHERE id = ?", [&card.id], |row| row.get(0))
+        .optional()?;
+    let completed = card.completed.map(|c| if c { 1 } else { 0 });
+    let recurrence = card.recurrence.as_ref();
+    let (rank, _) = rank::place(conn, RankScope::Cards(&card.column_id), &card.id, card.position)?;
+    conn.execute(
+        "INSERT INTO cards (id, project_id, column_id, title, description, rank, completed, priority, start_date, due_date, created_at, updated_at,
+                            recurrence_rule, recurrence_column_id, recurrence_next_id, archived_at, version)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET
+             column_id = excluded.column_id,
+             title = excluded.title,
+             description = excluded.description,
+             rank = excluded.rank,
+             completed = excluded.completed,
+             priority = excluded.priority,
+             start_date = excluded.start_date,
+             due_date = excluded.due_date,
+             updated_at = excluded.updated_at,
+             recurrence_rule = excluded.recurrence_rule,
+             recurrence_column_id = excluded.recurrence_column_id,
+             recurrence_next_id = excluded.recurrence_next_id,
+             archived_at = excluded.archived_at,
+             version = cards.version + 1",
+        rusqlite::params![
+            card.id,
+            project_id,
+            card.column_id,
+            card.title,
+            card.description,
+            rank,
+            completed,
+            card.priority,
+            card.start_date,
+            card.due_date,
+            card.created_at,
+            card.updated_at,
+            recurrence.map(|r| &r.rule),
+            recurrence.and_then(|r| r.column_id.as_ref()),
+            recurrence.and_then(|r| r.next_card_id.as_ref()),
+            card.archived_at,
+            card.version + 1,
+        ],
+    )?;
+    if previous_column.as_deref() != Some(card.column_id.as_str()) {
+        metrics::sync_card_transition(conn, &card.id)?;
+    }
+    if let Some(tag_ids) = &card.tag_ids {
+        tags::set_card_tags(conn, project_id, &card.id, tag_ids)?;
+    }
+    Ok(())
+}
+
+fn upsert_column(conn: &Connection, project_id: &str, column: &Column) -> Result<(), DbError> {
+    let (rank, _) = rank::place(conn, RankScope::Columns(project_id), &column.id, column.position)?;
+    conn.execute(
+        "INSERT INTO columns (id, project_id, title, rank, background_color, wip_limit, wip_mode, archived_at, created_at, updated_at, version)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET
+             title = excluded.title,
+             rank = excluded.rank,
+             background_color = excluded.background_color,
+             wip_limit = excluded.wip_limit,
+             wip_mode = excluded.wip_mode,
+             archived_at = excluded.archived_at,
+             updated_at = excluded.updated_at,
+             version = columns.version + 1",
+        rusqlite::params![
+            column.id,
+            project_id,
+            column.title,
+            rank,
+            column.background_color,
+            column.wip_limit,
+            column.wip_mode,
+            column.archived_at,
+            column.created_at,
+            column.updated_at,
+            column.version + 1,
+        ],
+    )?;
+    Ok(())
+}
+
+fn upsert_project(conn: &Connection, project: &Project) -> Result<(), DbError> {
+    // 恢复已删除的项目时放回原来的位置
+    let (rank, _) = rank::place(conn, RankScope::Projects, &project.id, project.position)?;
+
+    conn.execute(
+        "INSERT INTO projects (id, name, description, rank, archived_at, created_at, updated_at, version)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET
+             name = excluded.name,
+             description = excluded.description,
+             rank = excluded.rank,
+             archived_at = excluded.archived_at,
+             updated_at = excluded.updated_at,
+             version = projects.version + 1",
+        rusqlite::params![
+            project.id,
+            project.name,
+            project.description,
+            rank,
+            project.archived_at,
+            project.created_at,
+            project.updated_at,
+            project.version + 1,
+        ],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,739 @@
+// 项目导入相关数据库操作
+use super::checklists::restore_checklist_items;
+use super::comments::restore_comments;
+use super::export::{EXPORT_FORMAT, EXPORT_VERSION};
+use super::fields::{normalize_date, parse_priority};
+use super::history::{record_history, UndoOp, PROJECT_LIST_SCOPE};
+use super::kanban::{create_card_impl, create_column_impl, get_board_impl, save_board_impl};
+use super::projects::{create_project_impl, find_project, restore_project_ops};
+use super::tags::restore_tag;
+use super::{with_transaction, DbError};
+use crate::commands::checklists::ChecklistItem;
+use crate::commands::comments::Comment;
+use crate::commands::export::ProjectExport;
+use crate::commands::import::{CsvFieldMapping, CsvImportReport, CsvRowError, TrelloImportReport};
+use crate::commands::kanban::{Board, Card, CardDate, Column, Recurrence, WipMode};
+use crate::commands::projects::Project;
+use crate::commands::tags::Tag;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// 从 JSON 导出文档创建新项目, 所有ID重新生成, 不会覆盖已有数据
+pub fn import_project(app_handle: &AppHandle, path: &Path) -> Result<Project, DbError> {
+    let content = std::fs::read_to_string(path)?;
+    let document: ProjectExport = serde_json::from_str(&content)?;
+    if document.format != EXPORT_FORMAT {
+        return Err(DbError::Validation(format!("unknown export format: {}", document.format)));
+    }
+    if document.version == 0 || document.version > EXPORT_VERSION {
+        return Err(DbError::Validation(format!(
+            "unsupported export version: {}",
+            document.version
+        )));
+    }
+
+    with_transaction(app_handle, |conn| Ok(import_document(conn, &document)?.project))
+}
+
+/// 写入导出文档并记录历史, 返回写入后的数据
+fn import_document(conn: &Connection, document: &ProjectExport) -> Result<ProjectExport, DbError> {
+    let imported = import_project_impl(conn, document)?;
+    record_history(
+        conn,
+        PROJECT_LIST_SCOPE,
+        format!("导入项目「{}」", imported.project.name),
+        vec![UndoOp::DeleteProject { project_id: imported.project.id.clone() }],
+        restore_project_ops(
+            imported.project.clone(),
+            imported.board.clone(),
+            imported.tags.clone(),
+            imported.checklist_items.clone(),
+            imported.comments.clone(),
+            Vec::new(),
+        ),
+    )?;
+    Ok(imported)
+}
+
+/// 按新ID写入导出文档, 返回写入后的数据
+fn import_project_impl(conn: &Connection, document: &ProjectExport) -> Result<ProjectExport, DbError> {
+    let project = create_project_impl(
+        conn,
+        &Project { id: String::new(), ..document.project.clone() },
+    )?;
+    let pid = project.id.clone();
+
+    let mut ids = IdMap::default();
+    let tags: Vec<Tag> = document
+        .tags
+        .iter()
+        .map(|tag| Tag { id: ids.assign(&tag.id), ..tag.clone() })
+        .collect();
+    let columns: Vec<Column> = document
+        .board
+        .columns
+        .iter()
+        .map(|column| Column { id: ids.assign(&column.id), ..column.clone() })
+        .collect();
+    let cards: Vec<Card> = document
+        .board
+        .cards
+        .iter()
+        .map(|card| Card { id: ids.assign(&card.id), ..card.clone() })
+        .collect();
+
+    // 第二遍映射引用其他对象的字段, 引用不存在的对象时丢弃该引用, 所在列不存在的卡片被跳过
+    let columns: Vec<Column> = columns
+        .into_iter()
+        .map(|column| Column {
+            card_ids: column.card_ids.iter().filter_map(|id| ids.get(id)).collect(),
+            ..column
+        })
+        .collect();
+    let cards: Vec<Card> = cards
+        .into_iter()
+        .filter_map(|card| {
+            Some(Card {
+                column_id: ids.get(&card.column_id)?,
+                tag_ids: Some(
+                    card.tag_ids
+                        .iter()
+                        .flatten()
+                        .filter_map(|id| ids.get(id))
+                        .collect(),
+                ),
+                recurrence: card.recurrence.map(|r| Recurrence {
+                    column_id: r.column_id.and_then(|id| ids.get(&id)),
+                    next_card_id: r.next_card_id.and_then(|id| ids.get(&id)),
+                    ..r
+                }),
+                ..card
+            })
+        })
+        .collect();
+    let checklist_items: Vec<ChecklistItem> = document
+        .checklist_items
+        .iter()
+        .filter_map(|item| {
+            Some(ChecklistItem {
+                id: Uuid::new_v4().to_string(),
+                card_id: ids.get(&item.card_id)?,
+                ..item.clone()
+            })
+        })
+        .collect();
+    let comments: Vec<Comment> = document
+        .comments
+        .iter()
+        .filter_map(|comment| {
+            Some(Comment {
+                id: Uuid::new_v4().to_string(),
+                card_id: ids.get(&comment.card_id)?,
+                ..comment.clone()
+            })
+        })
+        .collect();
+
+    // 标签需先于卡片写入; 看板会替换新项目的默认列
+    for tag in &tags {
+        restore_tag(conn, &pid, tag, &[])?;
+    }
+    let board = Board { id: pid.clone(), columns, cards, ..document.board.clone() };
+    save_board_impl(conn, &pid, &board)?;
+    restore_checklist_items(conn, &checklist_items)?;
+    restore_comments(conn, &comments)?;
+
+    Ok(ProjectExport {
+        project,
+        board,
+        tags,
+        checklist_items,
+        comments,
+        ..document.clone()
+    })
+}
+
+/// 导入时的旧ID到新ID映射
+#[derive(Default)]
+struct IdMap(HashMap<String, String>);
+
+impl IdMap {
+    fn assign(&mut self, old_id: &str) -> String {
+        let new_id = Uuid::new_v4().to_string();
+        self.0.insert(old_id.to_string(), new_id.clone());
+        new_id
+    }
+
+    fn get(&self, old_id: &str) -> Option<String> {
+        self.0.get(old_id).cloned()
+    }
+}
+
+/// 从 Trello 看板导出的 JSON 文件创建新项目
+///
+/// 列表映射为列, 卡片映射为卡片 (含开始/截止时间和完成状态), 标签、检查清单和评论一并导入;
+/// 已归档的列表和卡片、附件及自定义字段不导入, 并在结果中说明。
+pub fn import_trello(app_handle: &AppHandle, path: &Path) -> Result<TrelloImportReport, DbError> {
+    let content = std::fs::read_to_string(path)?;
+    let trello: TrelloBoard = serde_json::from_str(&content)?;
+    let (document, skipped) = trello_to_document(trello);
+
+    let imported = with_transaction(app_handle, |conn| import_document(conn, &document))?;
+    log::info!("Imported Trello board into project {}", imported.project.id);
+
+    Ok(TrelloImportReport {
+        columns: imported.board.columns.len() as u32,
+        cards: imported.board.cards.len() as u32,
+        tags: imported.tags.len() as u32,
+        checklist_items: imported.checklist_items.len() as u32,
+        comments: imported.comments.len() as u32,
+        project: imported.project,
+        skipped,
+    })
+}
+
+#[derive(Deserialize)]
+struct TrelloBoard {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    desc: String,
+    #[serde(default)]
+    lists: Vec<TrelloList>,
+    #[serde(default)]
+    cards: Vec<TrelloCard>,
+    #[serde(default)]
+    labels: Vec<TrelloLabel>,
+    #[serde(default)]
+    checklists: Vec<TrelloChecklist>,
+    #[serde(default)]
+    actions: Vec<TrelloAction>,
+    #[serde(rename = "customFields", default)]
+    custom_fields: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct TrelloList {
+    id: String,
+    name: String,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    pos: f64,
+}
+
+#[derive(Deserialize)]
+struct TrelloCard {
+    id: String,
+    name: String,
+    #[serde(default)]
+    desc: String,
+    #[serde(rename = "idList")]
+    id_list: String,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    pos: f64,
+    due: Option<String>,
+    start: Option<String>,
+    #[serde(rename = "dueComplete", default)]
+    due_complete: bool,
+    #[serde(rename = "idLabels", default)]
+    id_labels: Vec<String>,
+    #[serde(rename = "dateLastActivity")]
+    date_last_activity: Option<String>,
+    #[serde(default)]
+    attachments: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize)]
+struct TrelloLabel {
+    id: String,
+    #[serde(default)]
+    name: String,
+    color: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TrelloChecklist {
+    #[serde(rename = "idCard")]
+    id_card: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    pos: f64,
+    #[serde(rename = "checkItems", default)]
+    check_items: Vec<TrelloCheckItem>,
+}
+
+#[derive(Deserialize)]
+struct TrelloCheckItem {
+    name: String,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    pos: f64,
+}
+
+#[derive(Deserialize)]
+struct TrelloAction {
+    #[serde(rename = "type")]
+    kind: String,
+    date: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+/// 将 Trello 看板转换为导出文档 (沿用 Trello ID, 写入时会重新生成), 同时返回跳过内容的说明
+fn trello_to_document(trello: TrelloBoard) -> (ProjectExport, Vec<String>) {
+    let now = Utc::now().to_rfc3339();
+    let mut skipped = Vec::new();
+
+    // 标签按名称去重, 无名称的标签以颜色命名
+    let mut tags: Vec<Tag> = Vec::new();
+    let mut label_tags: HashMap<String, String> = HashMap::new();
+    for label in &trello.labels {
+        let color = label.color.as_deref().unwrap_or_default();
+        let name = match label.name.trim() {
+            "" if color.is_empty() => "标签".to_string(),
+            "" => color.to_string(),
+            name => name.to_string(),
+        };
+        let tag_id = match tags.iter().find(|t| t.name == name) {
+            Some(existing) => existing.id.clone(),
+            None => {
+                tags.push(Tag {
+                    id: label.id.clone(),
+                    name,
+                    color: trello_color(color).to_string(),
+                    position: tags.len() as i32,
+                    created_at: trello_created_at(&label.id).unwrap_or_else(|| now.clone()),
+                    updated_at: now.clone(),
+                });
+                label.id.clone()
+            }
+        };
+        label_tags.insert(label.id.clone(), tag_id);
+    }
+
+    let mut lists: Vec<&TrelloList> = trello.lists.iter().collect();
+    lists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+    let archived_lists: Vec<&str> = lists.iter().filter(|l| l.closed).map(|l| l.name.as_str()).collect();
+    if !archived_lists.is_empty() {
+        skipped.push(format!("已归档的列表: {}", archived_lists.join(", ")));
+    }
+    lists.retain(|l| !l.closed);
+
+    let mut trello_cards: Vec<&TrelloCard> = trello.cards.iter().collect();
+    trello_cards.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+    let archived_cards = trello_cards.iter().filter(|c| c.closed).count();
+    if archived_cards > 0 {
+        skipped.push(format!("{} 张已归档的卡片", archived_cards));
+    }
+    let orphaned_cards = trello_cards
+        .iter()
+        .filter(|c| !c.closed && !lists.iter().any(|l| l.id == c.id_list))
+        .count();
+    if orphaned_cards > 0 {
+        skipped.push(format!("{} 张位于已归档或不存在列表中的卡片", orphaned_cards));
+    }
+    trello_cards.retain(|c| !c.closed && lists.iter().any(|l| l.id == c.id_list));
+
+    let mut columns = Vec::new();
+    let mut cards = Vec::new();
+    for (position, list) in lists.iter().enumerate() {
+        let list_cards: Vec<&&TrelloCard> = trello_cards.iter().filter(|c| c.id_list == list.id).collect();
+        for (position, card) in list_cards.iter().enumerate() {
+            let created_at = trello_created_at(&card.id).unwrap_or_else(|| now.clone());
+            cards.push(Card {
+                id: card.id.clone(),
+                title: card.name.clone(),
+                description: Some(card.desc.clone()).filter(|d| !d.trim().is_empty()),
+                column_id: list.id.clone(),
+                position: position as i32,
+                completed: Some(card.due_complete),
+                priority: None,
+                tag_ids: Some(card.id_labels.iter().filter_map(|id| label_tags.get(id).cloned()).collect()),
+                checklist_progress: None,
+                comment_count: None,
+                recurrence: None,
+                wip_exceeded: None,
+                start_date: card.start.as_deref().and_then(CardDate::parse),
+                due_date: card.due.as_deref().and_then(CardDate::parse),
+                version: 0,
+                archived_at: None,
+                updated_at: card.date_last_activity.clone().unwrap_or_else(|| created_at.clone()),
+                created_at,
+            });
+        }
+        columns.push(Column {
+            id: list.id.clone(),
+            title: list.name.clone(),
+            position: position as i32,
+            card_ids: list_cards.iter().map(|c| c.id.clone()).collect(),
+            background_color: None,
+            wip_limit: None,
+            wip_mode: WipMode::Soft,
+            version: 0,
+            archived_at: None,
+            created_at: trello_created_at(&list.id).unwrap_or_else(|| now.clone()),
+            updated_at: now.clone(),
+        });
+    }
+
+    let attachments: usize = trello_cards.iter().map(|c| c.attachments.len()).sum();
+    if attachments > 0 {
+        skipped.push(format!("{} 个附件 (请手动重新添加)", attachments));
+    }
+    if !trello.custom_fields.is_empty() {
+        skipped.push(format!("{} 个自定义字段", trello.custom_fields.len()));
+    }
+
+    // 一张卡片有多个检查清单时, 条目前加上清单名称
+    let mut checklists: Vec<&TrelloChecklist> = trello.checklists.iter().collect();
+    checklists.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+    let mut checklist_items = Vec::new();
+    for card in &cards {
+        let card_checklists: Vec<&&TrelloChecklist> =
+            checklists.iter().filter(|c| c.id_card == card.id).collect();
+        let mut position = 0;
+        for checklist in &card_checklists {
+            let mut items: Vec<&TrelloCheckItem> = checklist.check_items.iter().collect();
+            items.sort_by(|a, b| a.pos.total_cmp(&b.pos));
+            for item in items {
+                let text = if card_checklists.len() > 1 && !checklist.name.trim().is_empty() {
+                    format!("{}: {}", checklist.name.trim(), item.name.trim())
+                } else {
+                    item.name.trim().to_string()
+                };
+                if text.is_empty() {
+                    continue;
+                }
+                checklist_items.push(ChecklistItem {
+                    id: String::new(),
+                    card_id: card.id.clone(),
+                    text,
+                    done: item.state == "complete",
+                    position,
+                    created_at: card.created_at.clone(),
+                    updated_at: now.clone(),
+                });
+                position += 1;
+            }
+        }
+    }
+
+    // Trello 按时间倒序导出动作, 评论按时间正序写入
+    let mut comments: Vec<Comment> = trello
+        .actions
+        .iter()
+        .filter(|action| action.kind == "commentCard")
+        .filter_map(|action| {
+            let card_id = action.data["card"]["id"].as_str()?;
+            let body = action.data["text"].as_str()?.trim_end();
+            if body.trim().is_empty() || !cards.iter().any(|c| c.id == card_id) {
+                return None;
+            }
+            Some(Comment {
+                id: String::new(),
+                card_id: card_id.to_string(),
+                body: body.to_string(),
+                created_at: action.date.clone(),
+                updated_at: action.date.clone(),
+            })
+        })
+        .collect();
+    comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+    let name = match trello.name.trim() {
+        "" => "Trello 看板".to_string(),
+        name => name.to_string(),
+    };
+    let document = ProjectExport {
+        format: EXPORT_FORMAT.to_string(),
+        version: EXPORT_VERSION,
+        exported_at: now.clone(),
+        project: Project {
+            id: String::new(),
+            name,
+            description: Some(trello.desc).filter(|d| !d.trim().is_empty()),
+            position: 0,
+            version: 0,
+            archived_at: None,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        },
+        board: Board {
+            id: String::new(),
+            title: "看板".to_string(),
+            columns,
+            cards,
+            created_at: now.clone(),
+            updated_at: now,
+        },
+        tags,
+        checklist_items,
+        comments,
+    };
+
+    (document, skipped)
+}
+
+/// Trello ID 的前 8 位十六进制是创建时间的 Unix 秒数
+fn trello_created_at(id: &str) -> Option<String> {
+    let seconds = i64::from_str_radix(id.get(..8)?, 16).ok()?;
+    DateTime::from_timestamp(seconds, 0).map(|t| t.to_rfc3339())
+}
+
+/// Trello 标签颜色名转换为十六进制颜色
+fn trello_color(color: &str) -> &'static str {
+    let base = color.trim_end_matches("_dark").trim_end_matches("_light");
+    match base {
+        "green" => "#61bd4f",
+        "yellow" => "#f2d600",
+        "orange" => "#ff9f1a",
+        "red" => "#eb5a46",
+        "purple" => "#c377e0",
+        "blue" => "#0079bf",
+        "sky" => "#00c2e0",
+        "lime" => "#51e898",
+        "pink" => "#ff78cb",
+        "black" => "#344563",
+        _ => "#94a3b8",
+    }
+}
+
+/// 按字段映射将 CSV 文件中的行导入为项目卡片
+///
+/// 所有行在同一事务中写入; 任意一行有误时不写入任何数据。dry_run 为 true 时只返回将要创建的列和卡片。
+pub fn import_csv(
+    app_handle: &AppHandle,
+    project_id: &str,
+    path: &Path,
+    mapping: &CsvFieldMapping,
+    dry_run: bool,
+) -> Result<CsvImportReport, DbError> {
+    let content = std::fs::read_to_string(path)?;
+    let rows = read_csv_rows(&content, mapping)?;
+    let pid = project_id.to_string();
+
+    with_transaction(app_handle, |conn| {
+        let mut report = plan_csv_import(conn, &pid, &rows)?;
+        report.dry_run = dry_run;
+        if dry_run {
+            return Ok(report);
+        }
+        if let Some(first) = report.errors.first() {
+            return Err(DbError::Validation(format!(
+                "CSV import aborted, {} invalid rows (line {}: {})",
+                report.errors.len(),
+                first.line,
+                first.message
+            )));
+        }
+
+        let columns = report
+            .created_columns
+            .iter()
+            .map(|column| create_column_impl(conn, &pid, column))
+            .collect::<Result<Vec<_>, _>>()?;
+        let cards = report
+            .cards
+            .iter()
+            .map(|card| create_card_impl(conn, &pid, card))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut undo_ops: Vec<UndoOp> = cards
+            .iter()
+            .map(|card| UndoOp::DeleteCard { card_id: card.id.clone() })
+            .collect();
+        undo_ops.extend(columns.iter().map(|column| UndoOp::DeleteColumn { column_id: column.id.clone() }));
+        let mut redo_ops: Vec<UndoOp> = columns
+            .iter()
+            .map(|column| UndoOp::RestoreColumn { project_id: pid.clone(), column: column.clone() })
+            .collect();
+        redo_ops.extend(cards.iter().map(|card| UndoOp::RestoreCard { project_id: pid.clone(), card: card.clone() }));
+        record_history(conn, &pid, format!("导入 CSV（{} 张卡片）", cards.len()), undo_ops, redo_ops)?;
+
+        Ok(CsvImportReport { dry_run, created_columns: columns, cards, errors: Vec::new() })
+    })
+}
+
+/// 按字段映射取出的一行 CSV 数据
+struct CsvRow {
+    line: u64,
+    title: String,
+    description: String,
+    column: String,
+    priority: String,
+    start_date: String,
+    due_date: String,
+}
+
+fn read_csv_rows(content: &str, mapping: &CsvFieldMapping) -> Result<Vec<CsvRow>, DbError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers = reader.headers().map_err(csv_error)?.clone();
+
+    let index_of = |header: &str| -> Result<usize, DbError> {
+        headers
+            .iter()
+            .position(|h| h.trim() == header.trim())
+            .ok_or_else(|| DbError::Validation(format!("CSV header not found: {}", header)))
+    };
+    let optional_index = |header: &Option<String>| -> Result<Option<usize>, DbError> {
+        match header.as_deref().filter(|h| !h.trim().is_empty()) {
+            Some(header) => index_of(header).map(Some),
+            None => Ok(None),
+        }
+    };
+    let title = index_of(&mapping.title)?;
+    let description = optional_index(&mapping.description)?;
+    let column = optional_index(&mapping.column)?;
+    let priority = optional_index(&mapping.priority)?;
+    let start_date = optional_index(&mapping.start_date)?;
+    let due_date = optional_index(&mapping.due_date)?;
+
+    let mut rows = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(csv_error)?;
+        // 跳过空行
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        let field = |index: Option<usize>| {
+            index
+                .and_then(|i| record.get(i))
+                .map(|value| value.trim().to_string())
+                .unwrap_or_default()
+        };
+        rows.push(CsvRow {
+            line: record.position().map(|p| p.line()).unwrap_or_default(),
+            title: field(Some(title)),
+            description: field(description),
+            column: field(column),
+            priority: field(priority),
+            start_date: field(start_date),
+            due_date: field(due_date),
+        });
+    }
+    Ok(rows)
+}
+
+fn csv_error(error: csv::Error) -> DbError {
+    DbError::Validation(format!("invalid CSV: {}", error))
+}
+
+/// 校验 CSV 行并生成将要创建的列和卡片, 不写入数据
+fn plan_csv_import(conn: &Connection, project_id: &str, rows: &[CsvRow]) -> Result<CsvImportReport, DbError> {
+    find_project(conn, project_id)?
+        .ok_or_else(|| DbError::not_found("project", project_id))?;
+    let board = get_board_impl(conn, project_id)?;
+    let now = Utc::now().to_rfc3339();
+
+    let mut columns: Vec<Column> = board.columns.clone();
+    let mut created_columns: Vec<Column> = Vec::new();
+    let mut cards = Vec::new();
+    let mut errors = Vec::new();
+
+    for row in rows {
+        let mut row_errors = Vec::new();
+        if row.title.is_empty() {
+            row_errors.push("标题为空".to_string());
+        }
+        let priority = match parse_priority(&row.priority) {
+            Ok(priority) => priority,
+            Err(message) => {
+                row_errors.push(message);
+                None
+            }
+        };
+        let mut parse_date = |value: &str| match normalize_date(value) {
+            Ok(date) => date,
+            Err(message) => {
+                row_errors.push(message);
+                None
+            }
+        };
+        let start_date = parse_date(&row.start_date);
+        let due_date = parse_date(&row.due_date);
+        if let (Some(start), Some(due)) = (&start_date, &due_date) {
+            if !start.not_after(due) {
+                row_errors.push("开始日期晚于截止日期".to_string());
+            }
+        }
+        if !row_errors.is_empty() {
+            errors.extend(row_errors.into_iter().map(|message| CsvRowError { line: row.line, message }));
+            continue;
+        }
+
+        // 未指定列时放入第一列, 项目没有列时新建"待办"
+        let column_title = match row.column.as_str() {
+            "" => columns.first().map(|c| c.title.clone()).unwrap_or_else(|| "待办".to_string()),
+            title => title.to_string(),
+        };
+        let column_index = match columns.iter().position(|c| c.title == column_title) {
+            Some(index) => index,
+            None => {
+                let column = Column {
+                    id: Uuid::new_v4().to_string(),
+                    title: column_title,
+                    position: columns.iter().map(|c| c.position + 1).max().unwrap_or(0),
+                    card_ids: Vec::new(),
+                    background_color: None,
+                    wip_limit: None,
+                    wip_mode: WipMode::Soft,
+                    version: 0,
+                    archived_at: None,
+                    created_at: now.clone(),
+                    updated_at: now.clone(),
+                };
+                created_columns.push(column.clone());
+                columns.push(column);
+                columns.len() - 1
+            }
+        };
+
+        // 追加到列末尾
+        let column = &mut columns[column_index];
+        let position = board
+            .cards
+            .iter()
+            .chain(cards.iter())
+            .filter(|c| c.column_id == column.id)
+            .map(|c| c.position + 1)
+            .max()
+            .unwrap_or(0);
+        let card = Card {
+            id: Uuid::new_v4().to_string(),
+            title: row.title.clone(),
+            description: Some(row.description.clone()).filter(|d| !d.is_empty()),
+            column_id: column.id.clone(),
+            position,
+            completed: Some(false),
+            priority,
+            tag_ids: Some(Vec::new()),
+            checklist_progress: None,
+            comment_count: None,
+            recurrence: None,
+            wip_exceeded: None,
+            start_date,
+            due_date,
+            version: 0,
+            archived_at: None,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        };
+        column.card_ids.push(card.id.clone());
+        cards.push(card);
+    }
+
+    Ok(CsvImportReport { dry_run: true, created_columns, cards, errors })
+}
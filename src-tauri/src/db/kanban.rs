@@ -1,64 +1,150 @@
 // 看板相关数据库操作
-use super::{with_connection, DbError};
-use crate::commands::kanban::{Board, Card, Column, MoveCardParams};
+use super::activities::{record_activity, NewActivity};
+use super::attachments::{get_card_attachments, get_project_attachments};
+use super::checklists::{
+    get_card_checklist_items, get_checklist_progress, get_project_checklist_items,
+    get_project_checklist_progress,
+};
+use super::comments::{
+    get_card_comments, get_comment_count, get_project_comment_counts, get_project_comments,
+};
+use super::history::{record_history, UndoOp};
+use super::metrics::{record_transition, sync_card_transition};
+use super::rank::{apply_order, place, reorder, RankScope};
+use super::recurrence::{spawn_next_occurrence, validate_recurrence};
+use super::tags::{get_card_tag_ids, get_project_card_tag_ids, set_card_tags};
+use super::trash::{move_to_trash, TrashEntry};
+use super::{with_connection, with_transaction, ConflictCopy, DbError};
+use crate::commands::activities::ActivityType;
+use crate::commands::archive::ArchiveKind;
+use crate::commands::checklists::ChecklistProgress;
+use crate::commands::kanban::{
+    Board, BoardSaveSummary, Card, Column, ColumnCardOrder, MoveCardParams, MoveCardResult,
+    Recurrence, RemovedCard, WipMode,
+};
 use chrono::Utc;
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
 use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
 use tauri::AppHandle;
 use uuid::Uuid;
 
-/// 获取看板数据
-pub fn get_board(app_handle: &AppHandle, project_id: &str) -> Result<Board, DbError> {
+impl ToSql for WipMode {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for WipMode {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        let text = value.as_str()?;
+        WipMode::parse(text)
+            .ok_or_else(|| FromSqlError::Other(format!("unknown WIP mode: {}", text).into()))
+    }
+}
+
+/// 获取看板数据 (include_archived 为 true 时包含已归档的列和卡片)
+pub fn get_board(app_handle: &AppHandle, project_id: &str, include_archived: bool) -> Result<Board, DbError> {
     let pid = project_id.to_string();
     with_connection(app_handle, |conn| {
-        get_board_impl(conn, &pid)
+        load_board(conn, &pid, include_archived)
     })
 }
 
-fn get_board_impl(conn: &Connection, project_id: &str) -> Result<Board, DbError> {
+/// 读取看板中未归档的列和卡片
+pub(crate) fn get_board_impl(conn: &Connection, project_id: &str) -> Result<Board, DbError> {
+    load_board(conn, project_id, false)
+}
+
+/// 读取看板; 不包含已归档数据时, 已归档列中的卡片也一并排除
+pub(crate) fn load_board(conn: &Connection, project_id: &str, include_archived: bool) -> Result<Board, DbError> {
+    let archived_filter = if include_archived { "" } else { " AND archived_at IS NULL" };
+
     // 获取所有列
-    let mut stmt = conn.prepare(
-        "SELECT id, title, position, background_color, created_at, updated_at
-         FROM columns WHERE project_id = ? ORDER BY position"
-    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, title, background_color, version, created_at, updated_at, wip_limit, wip_mode, archived_at
+         FROM columns WHERE project_id = ?{} ORDER BY rank, id",
+        archived_filter
+    ))?;
 
     let columns_iter = stmt.query_map([project_id], |row| {
         Ok(Column {
             id: row.get(0)?,
             title: row.get(1)?,
-            position: row.get(2)?,
+            position: 0,
             card_ids: Vec::new(),
-            background_color: row.get(3)?,
+            background_color: row.get(2)?,
+            wip_limit: row.get(6)?,
+            wip_mode: row.get(7)?,
+            version: row.get(3)?,
+            archived_at: row.get(8)?,
             created_at: row.get(4)?,
             updated_at: row.get(5)?,
         })
     })?;
 
-    let mut columns: Vec<Column> = columns_iter.filter_map(|c| c.ok()).collect();
+    let mut columns: Vec<Column> = columns_iter.collect::<Result<_, _>>()?;
+    for (position, column) in columns.iter_mut().enumerate() {
+        column.position = position as i32;
+    }
 
     // 获取所有卡片
-    let mut stmt = conn.prepare(
-        "SELECT id, title, description, column_id, position, completed, priority, start_date, due_date, created_at, updated_at
-         FROM cards WHERE project_id = ? ORDER BY position"
-    )?;
+    let column_filter = if include_archived {
+        ""
+    } else {
+        " AND archived_at IS NULL AND column_id IN (SELECT id FROM columns WHERE archived_at IS NULL)"
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, title, description, column_id, completed, priority, start_date, due_date, created_at, updated_at,
+                recurrence_rule, recurrence_column_id, recurrence_next_id, version, archived_at
+         FROM cards WHERE project_id = ?{} ORDER BY rank, id",
+        column_filter
+    ))?;
 
     let cards_iter = stmt.query_map([project_id], |row| {
-        let completed: Option<i32> = row.get(5)?;
+        let completed: Option<i32> = row.get(4)?;
         Ok(Card {
             id: row.get(0)?,
             title: row.get(1)?,
             description: row.get(2)?,
             column_id: row.get(3)?,
-            position: row.get(4)?,
+            position: 0,
             completed: completed.map(|c| c == 1),
-            priority: row.get(6)?,
-            start_date: row.get(7)?,
-            due_date: row.get(8)?,
-            created_at: row.get(9)?,
-            updated_at: row.get(10)?,
+            priority: row.get(5)?,
+            tag_ids: None,
+            checklist_progress: None,
+            comment_count: None,
+            recurrence: Recurrence::from_columns(row.get(10)?, row.get(11)?, row.get(12)?),
+            wip_exceeded: None,
+            start_date: row.get(6)?,
+            due_date: row.get(7)?,
+            version: row.get(13)?,
+            archived_at: row.get(14)?,
+            created_at: row.get(8)?,
+            updated_at: row.get(9)?,
         })
     })?;
 
-    let cards: Vec<Card> = cards_iter.filter_map(|c| c.ok()).collect();
+    // 无法转换的字段 (如不规范的日期) 作为错误返回, 不能让卡片从看板中消失
+    let mut cards: Vec<Card> = cards_iter.collect::<Result<_, _>>()?;
+
+    // 序号为卡片在所在列中的位置
+    let mut column_sizes: HashMap<String, i32> = HashMap::new();
+    for card in &mut cards {
+        let size = column_sizes.entry(card.column_id.clone()).or_default();
+        card.position = *size;
+        *size += 1;
+    }
+
+    // 填充每张卡片的标签ID、检查清单进度和评论数
+    let mut card_tag_ids = get_project_card_tag_ids(conn, project_id)?;
+    let mut checklist_progress = get_project_checklist_progress(conn, project_id)?;
+    let mut comment_counts = get_project_comment_counts(conn, project_id)?;
+    for card in &mut cards {
+        card.tag_ids = Some(card_tag_ids.remove(&card.id).unwrap_or_default());
+        card.checklist_progress = Some(checklist_progress.remove(&card.id).unwrap_or_default());
+        card.comment_count = Some(comment_counts.remove(&card.id).unwrap_or_default());
+    }
 
     // 填充每列的卡片ID
     for column in &mut columns {
@@ -80,110 +166,398 @@ fn get_board_impl(conn: &Connection, project_id: &str) -> Result<Board, DbError>
     })
 }
 
-/// 保存看板数据 (全量保存)
-pub fn save_board(app_handle: &AppHandle, project_id: &str, board: &Board) -> Result<(), DbError> {
+/// 保存看板数据 (按差异写入)
+///
+/// 看板中移除的卡片需在 removed_cards 中给出客户端读取时的版本号; 卡片已被修改,
+/// 或客户端未见过 (如其他窗口新建) 时返回 Conflict, 避免覆盖其他修改。
+pub fn save_board(
+    app_handle: &AppHandle,
+    project_id: &str,
+    board: &Board,
+    removed_cards: &[RemovedCard],
+) -> Result<BoardSaveSummary, DbError> {
     let pid = project_id.to_string();
     let b = board.clone();
-    with_connection(app_handle, |conn| {
-        save_board_impl(conn, &pid, &b)
+    with_transaction(app_handle, |conn| {
+        let visible = get_board_impl(conn, &pid)?;
+        for card in &visible.cards {
+            if b.cards.iter().any(|c| c.id == card.id) {
+                continue;
+            }
+            if !removed_cards.iter().any(|r| r.id == card.id && r.version == card.version) {
+                return Err(card_conflict(conn, card.clone())?);
+            }
+        }
+
+        // 快照包含已归档的行, 以便撤销时恢复随列一起删除的已归档卡片
+        let previous = load_board(conn, &pid, true)?;
+        let removed_columns: HashSet<&str> = previous
+            .columns
+            .iter()
+            .filter(|c| c.archived_at.is_none() && !b.columns.iter().any(|n| n.id == c.id))
+            .map(|c| c.id.as_str())
+            .collect();
+        let removed: HashSet<&str> = previous
+            .cards
+            .iter()
+            .filter(|c| !b.cards.iter().any(|n| n.id == c.id))
+            .filter(|c| c.archived_at.is_none() || removed_columns.contains(c.column_id.as_str()))
+            .map(|c| c.id.as_str())
+            .collect();
+        let checklist_items = get_project_checklist_items(conn, &pid)?
+            .into_iter()
+            .filter(|item| removed.contains(item.card_id.as_str()))
+            .collect();
+        let comments = get_project_comments(conn, &pid)?
+            .into_iter()
+            .filter(|comment| removed.contains(comment.card_id.as_str()))
+            .collect();
+        let attachments = get_project_attachments(conn, &pid)?
+            .into_iter()
+            .filter(|attachment| removed.contains(attachment.card_id.as_str()))
+            .collect();
+
+        let mut summary = write_board(conn, &pid, &b)?;
+
+        // 移除的卡片和列与单独删除时一样放入回收站, 列中剩余的卡片随列一起放入
+        let mut entries = Vec::new();
+        for card in &visible.cards {
+            if b.cards.iter().any(|c| c.id == card.id) {
+                continue;
+            }
+            summary.cards_deleted += 1;
+            if !removed_columns.contains(card.column_id.as_str()) {
+                entries.push(trash_card(conn, &card.id)?);
+            }
+        }
+        for column in &visible.columns {
+            if !b.columns.iter().any(|c| c.id == column.id) {
+                entries.push(trash_column(conn, &column.id)?);
+                summary.columns_deleted += 1;
+            }
+        }
+        if summary.is_empty() {
+            return Ok(summary);
+        }
+
+        // 撤销时一并恢复被删除卡片的检查清单、评论和附件
+        let mut undo_ops = vec![
+            UndoOp::RestoreBoard { project_id: pid.clone(), board: previous },
+            UndoOp::RestoreChecklistItems { items: checklist_items },
+            UndoOp::RestoreComments { comments },
+            UndoOp::RestoreAttachments { attachments },
+        ];
+        undo_ops.extend(entries.iter().map(|entry| UndoOp::RemoveFromTrash { trash_id: entry.item.id.clone() }));
+        let mut redo_ops = vec![UndoOp::RestoreBoard { project_id: pid.clone(), board: b.clone() }];
+        redo_ops.extend(entries.into_iter().map(|entry| UndoOp::PutInTrash { entry }));
+        record_history(conn, &pid, "保存看板", undo_ops, redo_ops)?;
+        Ok(summary)
     })
 }
 
-fn save_board_impl(conn: &Connection, project_id: &str, board: &Board) -> Result<(), DbError> {
-    // 开启事务
-    conn.execute("BEGIN TRANSACTION", [])?;
+/// 将项目的列和卡片更新为看板中的内容
+///
+/// 只插入新增的、更新有变化的、删除已移除的行; 保留的卡片不会被删除重建,
+/// 其创建时间以及检查清单、评论和附件等关联数据保持不变。
+/// 已归档的列和卡片不在看板中时保持不变; 归档状态只在插入时写入。
+pub(crate) fn save_board_impl(conn: &Connection, project_id: &str, board: &Board) -> Result<BoardSaveSummary, DbError> {
+    let previous = get_board_impl(conn, project_id)?;
+    let mut summary = write_board(conn, project_id, board)?;
+
+    // 删除已移除的卡片和列 (移除列时其中剩余的卡片级联删除)
+    for card in &previous.cards {
+        if !board.cards.iter().any(|c| c.id == card.id) {
+            conn.execute("DELETE FROM cards WHERE id = ?", [&card.id])?;
+            summary.cards_deleted += 1;
+        }
+    }
+    for column in &previous.columns {
+        if !board.columns.iter().any(|c| c.id == column.id) {
+            conn.execute("DELETE FROM columns WHERE id = ?", [&column.id])?;
+            summary.columns_deleted += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+/// 插入看板中新增的列和卡片, 更新有变化的列和卡片, 不删除任何行
+fn write_board(conn: &Connection, project_id: &str, board: &Board) -> Result<BoardSaveSummary, DbError> {
+    let stored = load_board(conn, project_id, true)?;
+    let previous_ranks: HashMap<String, String> = conn
+        .prepare(
+            "SELECT id, rank FROM columns WHERE project_id = ?1
+             UNION ALL
+             SELECT id, rank FROM cards WHERE project_id = ?1",
+        )?
+        .query_map([project_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    // 沿用原有排序键, 只为顺序变化的列和卡片生成新键
+    let ranks_by_position = |items: Vec<(&str, i32)>| -> HashMap<String, String> {
+        let mut items = items;
+        items.sort_by_key(|(_, position)| *position);
+        let current: Vec<Option<&str>> =
+            items.iter().map(|(id, _)| previous_ranks.get(*id).map(|r| r.as_str())).collect();
+        items
+            .iter()
+            .map(|(id, _)| id.to_string())
+            .zip(reorder(&current))
+            .collect()
+    };
+    let mut ranks =
+        ranks_by_position(board.columns.iter().map(|c| (c.id.as_str(), c.position)).collect());
+    for column_id in board.cards.iter().map(|c| c.column_id.as_str()).collect::<HashSet<_>>() {
+        ranks.extend(ranks_by_position(
+            board
+                .cards
+                .iter()
+                .filter(|c| c.column_id == column_id)
+                .map(|c| (c.id.as_str(), c.position))
+                .collect(),
+        ));
+    }
 
-    // 删除旧数据
-    conn.execute("DELETE FROM cards WHERE project_id = ?", [project_id])?;
-    conn.execute("DELETE FROM columns WHERE project_id = ?", [project_id])?;
+    let mut summary = BoardSaveSummary::default();
 
-    // 插入新列
+    // 先写入列, 卡片可能被移入新列
     for column in &board.columns {
-        conn.execute(
-            "INSERT INTO columns (id, project_id, title, position, background_color, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
-            rusqlite::params![
-                column.id,
-                project_id,
-                column.title,
-                column.position,
-                column.background_color,
-                column.created_at,
-                column.updated_at,
-            ],
-        )?;
+        let rank = ranks.get(&column.id);
+        match stored.columns.iter().find(|c| c.id == column.id) {
+            None => {
+                conn.execute(
+                    "INSERT INTO columns (id, project_id, title, rank, background_color, wip_limit, wip_mode, archived_at, created_at, updated_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    rusqlite::params![
+                        column.id,
+                        project_id,
+                        column.title,
+                        rank,
+                        column.background_color,
+                        column.wip_limit,
+                        column.wip_mode,
+                        column.archived_at,
+                        column.created_at,
+                        column.updated_at,
+                    ],
+                )?;
+                summary.columns_created += 1;
+            }
+            Some(existing) => {
+                let changed = existing.title != column.title
+                    || existing.background_color != column.background_color
+                    || existing.wip_limit != column.wip_limit
+                    || existing.wip_mode != column.wip_mode
+                    || previous_ranks.get(&column.id) != rank;
+                if changed {
+                    conn.execute(
+                        "UPDATE columns SET title = ?, rank = ?, background_color = ?, wip_limit = ?, wip_mode = ?, updated_at = ?, version = version + 1
+                         WHERE id = ?",
+                        rusqlite::params![
+                            column.title,
+                            rank,
+                            column.background_color,
+                            column.wip_limit,
+                            column.wip_mode,
+                            column.updated_at,
+                            column.id,
+                        ],
+                    )?;
+                    summary.columns_updated += 1;
+                }
+            }
+        }
     }
 
-    // 插入新卡片
     for card in &board.cards {
+        let rank = ranks.get(&card.id);
         let completed = card.completed.map(|c| if c { 1 } else { 0 });
-        conn.execute(
-            "INSERT INTO cards (id, project_id, column_id, title, description, position, completed, priority, start_date, due_date, created_at, updated_at)
-             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
-            rusqlite::params![
-                card.id,
-                project_id,
-                card.column_id,
-                card.title,
-                card.description,
-                card.position,
-                completed,
-                card.priority,
-                card.start_date,
-                card.due_date,
-                card.created_at,
-                card.updated_at,
-            ],
-        )?;
+        let recurrence = card.recurrence.as_ref();
+        let existing = stored.cards.iter().find(|c| c.id == card.id);
+        validate_card_fields(card)?;
+        match existing {
+            None => {
+                conn.execute(
+                    "INSERT INTO cards (id, project_id, column_id, title, description, rank, completed, priority, start_date, due_date, created_at, updated_at,
+                                        recurrence_rule, recurrence_column_id, recurrence_next_id, archived_at)
+                     VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                    rusqlite::params![
+                        card.id,
+                        project_id,
+                        card.column_id,
+                        card.title,
+                        card.description,
+                        rank,
+                        completed,
+                        card.priority,
+                        card.start_date,
+                        card.due_date,
+                        card.created_at,
+                        card.updated_at,
+                        recurrence.map(|r| &r.rule),
+                        recurrence.and_then(|r| r.column_id.as_ref()),
+                        recurrence.and_then(|r| r.next_card_id.as_ref()),
+                        card.archived_at,
+                    ],
+                )?;
+                sync_card_transition(conn, &card.id)?;
+                summary.cards_created += 1;
+            }
+            Some(existing) => {
+                if card_changed(existing, card) || previous_ranks.get(&card.id) != rank {
+                    conn.execute(
+                        "UPDATE cards SET column_id = ?, title = ?, description = ?, rank = ?, completed = ?, priority = ?, start_date = ?, due_date = ?, updated_at = ?,
+                                          recurrence_rule = ?, recurrence_column_id = ?, recurrence_next_id = ?, version = version + 1
+                         WHERE id = ?",
+                        rusqlite::params![
+                            card.column_id,
+                            card.title,
+                            card.description,
+                            rank,
+                            completed,
+                            card.priority,
+                            card.start_date,
+                            card.due_date,
+                            card.updated_at,
+                            recurrence.map(|r| &r.rule),
+                            recurrence.and_then(|r| r.column_id.as_ref()),
+                            recurrence.and_then(|r| r.next_card_id.as_ref()),
+                            card.id,
+                        ],
+                    )?;
+                    if existing.column_id != card.column_id {
+                        sync_card_transition(conn, &card.id)?;
+                    }
+                    summary.cards_updated += 1;
+                }
+            }
+        }
+
+        // 未传入标签时保持原有标签
+        if let Some(tag_ids) = &card.tag_ids {
+            if existing.and_then(|c| c.tag_ids.as_ref()) != Some(tag_ids) {
+                set_card_tags(conn, project_id, &card.id, tag_ids)?;
+            }
+        }
     }
 
-    conn.execute("COMMIT", [])?;
+    Ok(summary)
+}
+
+/// 校验卡片的开始日期不晚于截止日期 (优先级在解析时已按当前刻度校验)
+fn validate_card_fields(card: &Card) -> Result<(), DbError> {
+    if let (Some(start), Some(due)) = (&card.start_date, &card.due_date) {
+        if !start.not_after(due) {
+            return Err(DbError::Validation(format!(
+                "start date {} must not be after due date {}",
+                start, due
+            )));
+        }
+    }
     Ok(())
 }
 
+/// 卡片内容是否有变化 (不含排序和标签)
+fn card_changed(existing: &Card, card: &Card) -> bool {
+    let recurrence = |c: &Card| {
+        c.recurrence
+            .as_ref()
+            .map(|r| (r.rule.clone(), r.column_id.clone(), r.next_card_id.clone()))
+    };
+    existing.column_id != card.column_id
+        || existing.title != card.title
+        || existing.description != card.description
+        || existing.completed.unwrap_or(false) != card.completed.unwrap_or(false)
+        || existing.priority != card.priority
+        || existing.start_date != card.start_date
+        || existing.due_date != card.due_date
+        || recurrence(existing) != recurrence(card)
+}
+
 /// 创建卡片
 pub fn create_card(app_handle: &AppHandle, project_id: &str, card: &Card) -> Result<Card, DbError> {
     let pid = project_id.to_string();
     let c = card.clone();
-    with_connection(app_handle, |conn| {
-        create_card_impl(conn, &pid, &c)
+    with_transaction(app_handle, |conn| {
+        validate_card_fields(&c)?;
+        let wip_exceeded = check_wip_limit(conn, &c.column_id, &c.id)?;
+        let mut created = create_card_impl(conn, &pid, &c)?;
+        created.wip_exceeded = Some(wip_exceeded);
+        record_history(
+            conn,
+            &pid,
+            format!("创建卡片「{}」", created.title),
+            vec![UndoOp::DeleteCard { card_id: created.id.clone() }],
+            vec![UndoOp::RestoreCard { project_id: pid.clone(), card: created.clone() }],
+        )?;
+        Ok(created)
     })
 }
 
-fn create_card_impl(conn: &Connection, project_id: &str, card: &Card) -> Result<Card, DbError> {
+pub(crate) fn create_card_impl(conn: &Connection, project_id: &str, card: &Card) -> Result<Card, DbError> {
     let now = Utc::now().to_rfc3339();
     let id = if card.id.is_empty() { Uuid::new_v4().to_string() } else { card.id.clone() };
 
+    // 新卡片不会有已生成的下一张卡片
+    let recurrence = card.recurrence.clone().map(|r| Recurrence { next_card_id: None, ..r });
+    validate_recurrence(conn, project_id, recurrence.as_ref())?;
+    let (rank, position) = place(conn, RankScope::Cards(&card.column_id), &id, card.position)?;
+
     let completed = card.completed.map(|c| if c { 1 } else { 0 });
     conn.execute(
-        "INSERT INTO cards (id, project_id, column_id, title, description, position, completed, priority, start_date, due_date, created_at, updated_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO cards (id, project_id, column_id, title, description, rank, completed, priority, start_date, due_date, created_at, updated_at,
+                            recurrence_rule, recurrence_column_id)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             id,
             project_id,
             card.column_id,
             card.title,
             card.description,
-            card.position,
+            rank,
             completed,
             card.priority,
             card.start_date,
             card.due_date,
             now,
             now,
+            recurrence.as_ref().map(|r| &r.rule),
+            recurrence.as_ref().and_then(|r| r.column_id.as_ref()),
         ],
     )?;
 
+    if let Some(tag_ids) = &card.tag_ids {
+        set_card_tags(conn, project_id, &id, tag_ids)?;
+    }
+    let tag_ids = get_card_tag_ids(conn, &id)?;
+
+    record_activity(
+        conn,
+        &NewActivity::new(project_id, ActivityType::CardCreated, &card.title)
+            .card(&id)
+            .column(&card.column_id),
+    )?;
+    record_transition(conn, project_id, &id, None, &card.column_id)?;
+
     Ok(Card {
         id,
         title: card.title.clone(),
         description: card.description.clone(),
         column_id: card.column_id.clone(),
-        position: card.position,
+        position,
         completed: card.completed,
         priority: card.priority.clone(),
-        start_date: card.start_date.clone(),
-        due_date: card.due_date.clone(),
+        tag_ids: Some(tag_ids),
+        // 新卡片还没有检查项和评论
+        checklist_progress: Some(ChecklistProgress::default()),
+        comment_count: Some(0),
+        recurrence,
+        wip_exceeded: None,
+        start_date: card.start_date,
+        due_date: card.due_date,
+        version: 1,
+        archived_at: None,
         created_at: now.clone(),
         updated_at: now,
     })
@@ -192,43 +566,149 @@ fn create_card_impl(conn: &Connection, project_id: &str, card: &Card) -> Result<
 /// 更新卡片
 pub fn update_card(app_handle: &AppHandle, _project_id: &str, card: &Card) -> Result<Card, DbError> {
     let c = card.clone();
-    with_connection(app_handle, |conn| {
-        update_card_impl(conn, &c)
+    with_transaction(app_handle, |conn| {
+        let Some((project_id, previous)) = find_card(conn, &c.id)? else {
+            return Err(DbError::not_found("card", &c.id));
+        };
+        let updated = update_card_impl(conn, &c)?;
+        let mut undo_ops = vec![UndoOp::RestoreCard { project_id: project_id.clone(), card: previous.clone() }];
+        let mut redo_ops = vec![UndoOp::RestoreCard { project_id: project_id.clone(), card: updated.clone() }];
+
+        // 完成重复卡片时生成的下一张卡片随本次修改一起撤销/重做
+        let next_card_id = |card: &Card| card.recurrence.as_ref().and_then(|r| r.next_card_id.clone());
+        if let Some(spawned_id) = next_card_id(&updated).filter(|id| Some(id) != next_card_id(&previous).as_ref()) {
+            if let Some((_, spawned)) = find_card(conn, &spawned_id)? {
+                undo_ops.push(UndoOp::DeleteCard { card_id: spawned_id.clone() });
+                redo_ops.push(UndoOp::RestoreCard { project_id: project_id.clone(), card: spawned });
+                redo_ops.push(UndoOp::RestoreChecklistItems {
+                    items: get_card_checklist_items(conn, &spawned_id)?,
+                });
+            }
+        }
+
+        record_history(
+            conn,
+            &project_id,
+            format!("更新卡片「{}」", updated.title),
+            undo_ops,
+            redo_ops,
+        )?;
+        Ok(updated)
     })
 }
 
+/// 版本冲突错误, 带上数据库中卡片的当前内容
+fn card_conflict(conn: &Connection, current: Card) -> Result<DbError, DbError> {
+    let current = Card {
+        checklist_progress: Some(get_checklist_progress(conn, &current.id)?),
+        comment_count: Some(get_comment_count(conn, &current.id)?),
+        ..current
+    };
+    Ok(DbError::Conflict(Box::new(ConflictCopy::Card(current))))
+}
+
 fn update_card_impl(conn: &Connection, card: &Card) -> Result<Card, DbError> {
     let now = Utc::now().to_rfc3339();
+    let Some((project_id, previous)) = find_card(conn, &card.id)? else {
+        return Err(DbError::not_found("card", &card.id));
+    };
+    if previous.version != card.version {
+        return Err(card_conflict(conn, previous)?);
+    }
+    validate_card_fields(card)?;
+    if previous.column_id != card.column_id {
+        ensure_column_in_project(conn, &card.column_id, &project_id)?;
+    }
+    let wip_exceeded = previous.column_id != card.column_id
+        && check_wip_limit(conn, &card.column_id, &card.id)?;
+
+    // 已生成的下一张卡片由后端维护, 忽略前端传入的值
+    let previous_next_id = previous.recurrence.as_ref().and_then(|r| r.next_card_id.clone());
+    let mut recurrence = card
+        .recurrence
+        .clone()
+        .map(|r| Recurrence { next_card_id: previous_next_id, ..r });
+    validate_recurrence(conn, &project_id, recurrence.as_ref())?;
+
+    let (rank, position) = place(conn, RankScope::Cards(&card.column_id), &card.id, card.position)?;
 
     let completed = card.completed.map(|c| if c { 1 } else { 0 });
     conn.execute(
-        "UPDATE cards SET title = ?, description = ?, column_id = ?, position = ?, completed = ?, priority = ?, start_date = ?, due_date = ?, updated_at = ?
+        "UPDATE cards SET title = ?, description = ?, column_id = ?, rank = ?, completed = ?, priority = ?, start_date = ?, due_date = ?, updated_at = ?,
+                          recurrence_rule = ?, recurrence_column_id = ?, recurrence_next_id = ?, version = version + 1
          WHERE id = ?",
         rusqlite::params![
             card.title,
             card.description,
             card.column_id,
-            card.position,
+            rank,
             completed,
             card.priority,
             card.start_date,
             card.due_date,
             now,
+            recurrence.as_ref().map(|r| &r.rule),
+            recurrence.as_ref().and_then(|r| r.column_id.as_ref()),
+            recurrence.as_ref().and_then(|r| r.next_card_id.as_ref()),
             card.id,
         ],
     )?;
 
+    if let Some(tag_ids) = &card.tag_ids {
+        set_card_tags(conn, &project_id, &card.id, tag_ids)?;
+    }
+
+    let was_completed = previous.completed.unwrap_or(false);
+    let is_completed = card.completed.unwrap_or(false);
+    let activity_type = match (was_completed, is_completed) {
+        (false, true) => ActivityType::CardCompleted,
+        (true, false) => ActivityType::CardUncompleted,
+        _ => ActivityType::CardUpdated,
+    };
+    record_activity(
+        conn,
+        &NewActivity::new(&project_id, activity_type, &card.title)
+            .card(&card.id)
+            .column(&card.column_id),
+    )?;
+    if previous.column_id != card.column_id {
+        record_activity(
+            conn,
+            &NewActivity::new(&project_id, ActivityType::CardMoved, &card.title)
+                .card(&card.id)
+                .moved(&previous.column_id, &card.column_id),
+        )?;
+        record_transition(conn, &project_id, &card.id, Some(&previous.column_id), &card.column_id)?;
+    }
+
+    // 完成重复卡片时生成下一张卡片
+    if !was_completed && is_completed {
+        let current = Card { recurrence: recurrence.clone(), ..card.clone() };
+        if let Some(spawned) = spawn_next_occurrence(conn, &project_id, &current, None)? {
+            if let Some(r) = recurrence.as_mut() {
+                r.next_card_id = Some(spawned.id);
+            }
+        }
+    }
+
     Ok(Card {
         id: card.id.clone(),
         title: card.title.clone(),
         description: card.description.clone(),
         column_id: card.column_id.clone(),
-        position: card.position,
+        position,
         completed: card.completed,
         priority: card.priority.clone(),
-        start_date: card.start_date.clone(),
-        due_date: card.due_date.clone(),
-        created_at: card.created_at.clone(),
+        tag_ids: Some(get_card_tag_ids(conn, &card.id)?),
+        checklist_progress: Some(get_checklist_progress(conn, &card.id)?),
+        comment_count: Some(get_comment_count(conn, &card.id)?),
+        recurrence,
+        wip_exceeded: Some(wip_exceeded),
+        start_date: card.start_date,
+        due_date: card.due_date,
+        version: card.version + 1,
+        archived_at: previous.archived_at.clone(),
+        created_at: previous.created_at.clone(),
         updated_at: now,
     })
 }
@@ -236,63 +716,315 @@ fn update_card_impl(conn: &Connection, card: &Card) -> Result<Card, DbError> {
 /// 删除卡片
 pub fn delete_card(app_handle: &AppHandle, _project_id: &str, card_id: &str) -> Result<(), DbError> {
     let cid = card_id.to_string();
-    with_connection(app_handle, |conn| {
-        conn.execute("DELETE FROM cards WHERE id = ?", [&cid])?;
-        Ok(())
+    with_transaction(app_handle, |conn| {
+        let entry = trash_card(conn, &cid)?;
+        let project_id = entry.item.project_id.clone();
+        let label = format!("删除卡片「{}」", entry.item.title);
+        let mut undo_ops = entry.restore_ops.clone();
+        undo_ops.push(UndoOp::RemoveFromTrash { trash_id: entry.item.id.clone() });
+        record_history(
+            conn,
+            &project_id,
+            label,
+            undo_ops,
+            vec![UndoOp::DeleteCard { card_id: cid.clone() }, UndoOp::PutInTrash { entry }],
+        )
     })
 }
 
+/// 将卡片连同检查清单、评论和附件放入回收站后删除, 返回回收站记录
+fn trash_card(conn: &Connection, card_id: &str) -> Result<TrashEntry, DbError> {
+    let Some((project_id, previous)) = find_card(conn, card_id)? else {
+        return Err(DbError::not_found("card", card_id));
+    };
+    let checklist_items = get_card_checklist_items(conn, card_id)?;
+    let comments = get_card_comments(conn, card_id)?;
+    let attachments = get_card_attachments(conn, card_id)?;
+    let title = previous.title.clone();
+    let restore_ops = vec![
+        UndoOp::RestoreCard { project_id: project_id.clone(), card: previous },
+        UndoOp::RestoreChecklistItems { items: checklist_items },
+        UndoOp::RestoreComments { comments },
+        UndoOp::RestoreAttachments { attachments },
+    ];
+    let entry = move_to_trash(conn, ArchiveKind::Card, card_id, &project_id, &title, &restore_ops)?;
+    delete_card_impl(conn, card_id)?;
+    Ok(entry)
+}
+
+fn delete_card_impl(conn: &Connection, card_id: &str) -> Result<(), DbError> {
+    let Some((project_id, card)) = find_card(conn, card_id)? else {
+        return Err(DbError::not_found("card", card_id));
+    };
+
+    conn.execute("DELETE FROM cards WHERE id = ?", [card_id])?;
+
+    record_activity(
+        conn,
+        &NewActivity::new(&project_id, ActivityType::CardDeleted, &card.title)
+            .card(card_id)
+            .column(&card.column_id),
+    )?;
+
+    Ok(())
+}
+
 /// 移动卡片
-pub fn move_card(app_handle: &AppHandle, _project_id: &str, params: &MoveCardParams) -> Result<(), DbError> {
+///
+/// 只为被移动的卡片生成新的排序键, 同列其他卡片无需改动。
+pub fn move_card(app_handle: &AppHandle, _project_id: &str, params: &MoveCardParams) -> Result<MoveCardResult, DbError> {
     let p = params.clone();
-    with_connection(app_handle, |conn| {
-        let now = Utc::now().to_rfc3339();
-        conn.execute(
-            "UPDATE cards SET column_id = ?, position = ?, updated_at = ? WHERE id = ?",
-            rusqlite::params![
-                p.to_column_id,
-                p.new_position,
-                now,
-                p.card_id,
-            ],
+    with_transaction(app_handle, |conn| {
+        let Some((project_id, previous)) = find_card(conn, &p.card_id)? else {
+            return Err(DbError::not_found("card", &p.card_id));
+        };
+        let result = move_card_impl(conn, &p)?;
+        record_history(
+            conn,
+            &project_id,
+            format!("移动卡片「{}」", result.card.title),
+            vec![UndoOp::RestoreCard { project_id: project_id.clone(), card: previous }],
+            vec![UndoOp::RestoreCard { project_id: project_id.clone(), card: result.card.clone() }],
+        )?;
+        Ok(result)
+    })
+}
+
+/// 卡片只能放入同一项目的列中
+fn ensure_column_in_project(conn: &Connection, column_id: &str, project_id: &str) -> Result<(), DbError> {
+    match find_column(conn, column_id)? {
+        Some((column_project_id, _)) if column_project_id == project_id => Ok(()),
+        Some(_) => Err(DbError::Validation(format!(
+            "column {} does not belong to project {}",
+            column_id, project_id
+        ))),
+        None => Err(DbError::not_found("column", column_id)),
+    }
+}
+
+fn move_card_impl(conn: &Connection, params: &MoveCardParams) -> Result<MoveCardResult, DbError> {
+    let Some((project_id, card)) = find_card(conn, &params.card_id)? else {
+        return Err(DbError::not_found("card", &params.card_id));
+    };
+
+    ensure_column_in_project(conn, &params.to_column_id, &project_id)?;
+    let wip_exceeded = card.column_id != params.to_column_id
+        && check_wip_limit(conn, &params.to_column_id, &params.card_id)?;
+
+    let (rank, _) = place(
+        conn,
+        RankScope::Cards(&params.to_column_id),
+        &params.card_id,
+        params.new_position,
+    )?;
+    let now = Utc::now().to_rfc3339();
+    conn.execute(
+        "UPDATE cards SET column_id = ?, rank = ?, updated_at = ?, version = version + 1 WHERE id = ?",
+        rusqlite::params![params.to_column_id, rank, now, params.card_id],
+    )?;
+
+    if card.column_id != params.to_column_id {
+        record_activity(
+            conn,
+            &NewActivity::new(&project_id, ActivityType::CardMoved, &card.title)
+                .card(&params.card_id)
+                .moved(&card.column_id, &params.to_column_id),
         )?;
-        Ok(())
+        record_transition(conn, &project_id, &params.card_id, Some(&card.column_id), &params.to_column_id)?;
+    }
+
+    let (_, moved) = find_card(conn, &params.card_id)?
+        .ok_or_else(|| DbError::not_found("card", &params.card_id))?;
+    Ok(MoveCardResult {
+        card: Card { wip_exceeded: Some(wip_exceeded), ..moved },
+        from_column: get_column_card_order(conn, &card.column_id)?,
+        to_column: get_column_card_order(conn, &params.to_column_id)?,
+    })
+}
+
+/// 按排序键读取列中的卡片顺序
+fn get_column_card_order(conn: &Connection, column_id: &str) -> Result<ColumnCardOrder, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT id FROM cards WHERE column_id = ? AND archived_at IS NULL ORDER BY rank, id",
+    )?;
+    let card_ids = stmt
+        .query_map([column_id], |row| row.get(0))?
+        .filter_map(|id| id.ok())
+        .collect();
+    Ok(ColumnCardOrder {
+        column_id: column_id.to_string(),
+        card_ids,
     })
 }
 
+/// 检查卡片放入列后是否超出在制品上限
+///
+/// 硬限制时返回错误; 软限制时允许放入, 返回 true 表示超出。
+fn check_wip_limit(conn: &Connection, column_id: &str, card_id: &str) -> Result<bool, DbError> {
+    let limit = conn.query_row(
+        "SELECT wip_limit, wip_mode FROM columns WHERE id = ?",
+        [column_id],
+        |row| Ok((row.get::<_, Option<u32>>(0)?, row.get::<_, WipMode>(1)?)),
+    );
+    let (limit, mode) = match limit {
+        Ok((Some(limit), mode)) => (limit, mode),
+        Ok((None, _)) | Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(false),
+        Err(e) => return Err(DbError::from(e)),
+    };
+
+    let count = conn.query_row(
+        "SELECT COUNT(*) FROM cards WHERE column_id = ? AND id != ? AND archived_at IS NULL",
+        [column_id, card_id],
+        |row| row.get::<_, u32>(0),
+    )?;
+    if count < limit {
+        return Ok(false);
+    }
+    match mode {
+        WipMode::Soft => Ok(true),
+        WipMode::Hard => Err(DbError::WipLimitExceeded { column_id: column_id.to_string(), limit }),
+    }
+}
+
+/// 校验在制品上限设置
+fn validate_wip_limit(column: &Column) -> Result<(), DbError> {
+    if column.wip_limit == Some(0) {
+        return Err(DbError::Validation("WIP limit must be greater than 0".to_string()));
+    }
+    Ok(())
+}
+
+/// 将卡片放入列中并按给定顺序排列
+pub(crate) fn set_column_card_order(conn: &Connection, order: &ColumnCardOrder) -> Result<(), DbError> {
+    for card_id in &order.card_ids {
+        let moved = conn.execute(
+            "UPDATE cards SET column_id = ? WHERE id = ? AND column_id != ?",
+            rusqlite::params![order.column_id, card_id, order.column_id],
+        )?;
+        if moved > 0 {
+            sync_card_transition(conn, card_id)?;
+        }
+    }
+    apply_order(conn, RankScope::Cards(&order.column_id), &order.card_ids)
+}
+
+/// 查询卡片所属项目
+pub(crate) fn find_card_project(conn: &Connection, card_id: &str) -> Result<Option<String>, DbError> {
+    let result = conn.query_row(
+        "SELECT project_id FROM cards WHERE id = ?",
+        [card_id],
+        |row| row.get(0),
+    );
+
+    match result {
+        Ok(project_id) => Ok(Some(project_id)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
+/// 按 ID 查询卡片及其所属项目
+pub(crate) fn find_card(conn: &Connection, card_id: &str) -> Result<Option<(String, Card)>, DbError> {
+    let result = conn.query_row(
+        "SELECT project_id, id, title, description, column_id,
+                (SELECT COUNT(*) FROM cards AS prev
+                 WHERE prev.column_id = cards.column_id AND prev.rank < cards.rank AND prev.archived_at IS NULL),
+                completed, priority, start_date, due_date, created_at, updated_at,
+                recurrence_rule, recurrence_column_id, recurrence_next_id, version, archived_at
+         FROM cards WHERE id = ?",
+        [card_id],
+        |row| {
+            let completed: Option<i32> = row.get(6)?;
+            Ok((
+                row.get(0)?,
+                Card {
+                    id: row.get(1)?,
+                    title: row.get(2)?,
+                    description: row.get(3)?,
+                    column_id: row.get(4)?,
+                    position: row.get(5)?,
+                    completed: completed.map(|c| c == 1),
+                    priority: row.get(7)?,
+                    tag_ids: None,
+                    checklist_progress: None,
+                    comment_count: None,
+                    recurrence: Recurrence::from_columns(row.get(12)?, row.get(13)?, row.get(14)?),
+                    wip_exceeded: None,
+                    start_date: row.get(8)?,
+                    due_date: row.get(9)?,
+                    version: row.get(15)?,
+                    archived_at: row.get(16)?,
+                    created_at: row.get(10)?,
+                    updated_at: row.get(11)?,
+                },
+            ))
+        },
+    );
+
+    match result {
+        Ok((project_id, mut card)) => {
+            card.tag_ids = Some(get_card_tag_ids(conn, card_id)?);
+            Ok(Some((project_id, card)))
+        }
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
 /// 创建列
 pub fn create_column(app_handle: &AppHandle, project_id: &str, column: &Column) -> Result<Column, DbError> {
     let pid = project_id.to_string();
     let c = column.clone();
-    with_connection(app_handle, |conn| {
-        create_column_impl(conn, &pid, &c)
+    with_transaction(app_handle, |conn| {
+        let created = create_column_impl(conn, &pid, &c)?;
+        record_history(
+            conn,
+            &pid,
+            format!("创建列「{}」", created.title),
+            vec![UndoOp::DeleteColumn { column_id: created.id.clone() }],
+            vec![UndoOp::RestoreColumn { project_id: pid.clone(), column: created.clone() }],
+        )?;
+        Ok(created)
     })
 }
 
-fn create_column_impl(conn: &Connection, project_id: &str, column: &Column) -> Result<Column, DbError> {
+pub(crate) fn create_column_impl(conn: &Connection, project_id: &str, column: &Column) -> Result<Column, DbError> {
     let now = Utc::now().to_rfc3339();
     let id = if column.id.is_empty() { Uuid::new_v4().to_string() } else { column.id.clone() };
+    validate_wip_limit(column)?;
+    let (rank, position) = place(conn, RankScope::Columns(project_id), &id, column.position)?;
 
     conn.execute(
-        "INSERT INTO columns (id, project_id, title, position, background_color, created_at, updated_at)
-         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        "INSERT INTO columns (id, project_id, title, rank, background_color, wip_limit, wip_mode, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
         rusqlite::params![
             id,
             project_id,
             column.title,
-            column.position,
+            rank,
             column.background_color,
+            column.wip_limit,
+            column.wip_mode,
             now,
             now,
         ],
     )?;
 
+    record_activity(
+        conn,
+        &NewActivity::new(project_id, ActivityType::ColumnCreated, &column.title).column(&id),
+    )?;
+
     Ok(Column {
         id,
         title: column.title.clone(),
-        position: column.position,
+        position,
         card_ids: Vec::new(),
         background_color: column.background_color.clone(),
+        wip_limit: column.wip_limit,
+        wip_mode: column.wip_mode,
+        version: 1,
+        archived_at: None,
         created_at: now.clone(),
         updated_at: now,
     })
@@ -301,32 +1033,68 @@ fn create_column_impl(conn: &Connection, project_id: &str, column: &Column) -> R
 /// 更新列
 pub fn update_column(app_handle: &AppHandle, _project_id: &str, column: &Column) -> Result<Column, DbError> {
     let c = column.clone();
-    with_connection(app_handle, |conn| {
-        update_column_impl(conn, &c)
+    with_transaction(app_handle, |conn| {
+        let Some((project_id, previous)) = find_column(conn, &c.id)? else {
+            return Err(DbError::not_found("column", &c.id));
+        };
+        let updated = update_column_impl(conn, &c)?;
+        record_history(
+            conn,
+            &project_id,
+            format!("更新列「{}」", updated.title),
+            vec![UndoOp::RestoreColumn { project_id: project_id.clone(), column: previous }],
+            vec![UndoOp::RestoreColumn { project_id: project_id.clone(), column: updated.clone() }],
+        )?;
+        Ok(updated)
     })
 }
 
 fn update_column_impl(conn: &Connection, column: &Column) -> Result<Column, DbError> {
     let now = Utc::now().to_rfc3339();
+    let Some((project_id, previous)) = find_column(conn, &column.id)? else {
+        return Err(DbError::not_found("column", &column.id));
+    };
+    if previous.version != column.version {
+        let current = Column {
+            card_ids: get_column_card_order(conn, &column.id)?.card_ids,
+            ..previous
+        };
+        return Err(DbError::Conflict(Box::new(ConflictCopy::Column(current))));
+    }
+    validate_wip_limit(column)?;
+    let (rank, position) = place(conn, RankScope::Columns(&project_id), &column.id, column.position)?;
 
     conn.execute(
-        "UPDATE columns SET title = ?, position = ?, background_color = ?, updated_at = ? WHERE id = ?",
+        "UPDATE columns SET title = ?, rank = ?, background_color = ?, wip_limit = ?, wip_mode = ?, updated_at = ?, version = version + 1
+         WHERE id = ?",
         rusqlite::params![
             column.title,
-            column.position,
+            rank,
             column.background_color,
+            column.wip_limit,
+            column.wip_mode,
             now,
             column.id,
         ],
     )?;
 
+    record_activity(
+        conn,
+        &NewActivity::new(&project_id, ActivityType::ColumnUpdated, &column.title)
+            .column(&column.id),
+    )?;
+
     Ok(Column {
         id: column.id.clone(),
         title: column.title.clone(),
-        position: column.position,
+        position,
         card_ids: column.card_ids.clone(),
         background_color: column.background_color.clone(),
-        created_at: column.created_at.clone(),
+        wip_limit: column.wip_limit,
+        wip_mode: column.wip_mode,
+        version: column.version + 1,
+        archived_at: previous.archived_at.clone(),
+        created_at: previous.created_at.clone(),
         updated_at: now,
     })
 }
@@ -334,11 +1102,114 @@ fn update_column_impl(conn: &Connection, column: &Column) -> Result<Column, DbEr
 /// 删除列
 pub fn delete_column(app_handle: &AppHandle, _project_id: &str, column_id: &str) -> Result<(), DbError> {
     let cid = column_id.to_string();
-    with_connection(app_handle, |conn| {
-        // 先删除列中的卡片
-        conn.execute("DELETE FROM cards WHERE column_id = ?", [&cid])?;
-        // 再删除列
-        conn.execute("DELETE FROM columns WHERE id = ?", [&cid])?;
-        Ok(())
+    with_transaction(app_handle, |conn| {
+        let entry = trash_column(conn, &cid)?;
+        let project_id = entry.item.project_id.clone();
+        let label = format!("删除列「{}」", entry.item.title);
+        let mut undo_ops = entry.restore_ops.clone();
+        undo_ops.push(UndoOp::RemoveFromTrash { trash_id: entry.item.id.clone() });
+        record_history(
+            conn,
+            &project_id,
+            label,
+            undo_ops,
+            vec![UndoOp::DeleteColumn { column_id: cid.clone() }, UndoOp::PutInTrash { entry }],
+        )
     })
 }
+
+/// 将列连同其中的卡片 (含已归档的卡片) 及其检查清单、评论和附件放入回收站后删除, 返回回收站记录
+fn trash_column(conn: &Connection, column_id: &str) -> Result<TrashEntry, DbError> {
+    let Some((project_id, previous)) = find_column(conn, column_id)? else {
+        return Err(DbError::not_found("column", column_id));
+    };
+    let cards: Vec<Card> = load_board(conn, &project_id, true)?
+        .cards
+        .into_iter()
+        .filter(|card| card.column_id == column_id)
+        .collect();
+    let checklist_items = get_project_checklist_items(conn, &project_id)?
+        .into_iter()
+        .filter(|item| cards.iter().any(|card| card.id == item.card_id))
+        .collect();
+    let comments = get_project_comments(conn, &project_id)?
+        .into_iter()
+        .filter(|comment| cards.iter().any(|card| card.id == comment.card_id))
+        .collect();
+    let attachments = get_project_attachments(conn, &project_id)?
+        .into_iter()
+        .filter(|attachment| cards.iter().any(|card| card.id == attachment.card_id))
+        .collect();
+
+    // 恢复时先恢复列, 再恢复列中的卡片及其检查清单、评论和附件
+    let mut restore_ops = vec![UndoOp::RestoreColumn {
+        project_id: project_id.clone(),
+        column: previous.clone(),
+    }];
+    restore_ops.extend(cards.into_iter().map(|card| UndoOp::RestoreCard {
+        project_id: project_id.clone(),
+        card,
+    }));
+    restore_ops.push(UndoOp::RestoreChecklistItems { items: checklist_items });
+    restore_ops.push(UndoOp::RestoreComments { comments });
+    restore_ops.push(UndoOp::RestoreAttachments { attachments });
+    let entry = move_to_trash(conn, ArchiveKind::Column, column_id, &project_id, &previous.title, &restore_ops)?;
+    delete_column_impl(conn, column_id)?;
+    Ok(entry)
+}
+
+fn delete_column_impl(conn: &Connection, column_id: &str) -> Result<(), DbError> {
+    let Some((project_id, column)) = find_column(conn, column_id)? else {
+        return Ok(());
+    };
+
+    // 先删除列中的卡片
+    let removed_cards = conn.execute("DELETE FROM cards WHERE column_id = ?", [column_id])?;
+    // 再删除列
+    conn.execute("DELETE FROM columns WHERE id = ?", [column_id])?;
+
+    record_activity(
+        conn,
+        &NewActivity::new(&project_id, ActivityType::ColumnDeleted, &column.title)
+            .column(column_id)
+            .description(format!("同时删除了 {} 张卡片", removed_cards)),
+    )?;
+
+    Ok(())
+}
+
+/// 按 ID 查询列及其所属项目
+fn find_column(conn: &Connection, column_id: &str) -> Result<Option<(String, Column)>, DbError> {
+    let result = conn.query_row(
+        "SELECT project_id, id, title,
+                (SELECT COUNT(*) FROM columns AS prev
+                 WHERE prev.project_id = columns.project_id AND prev.rank < columns.rank AND prev.archived_at IS NULL),
+                background_color, version, created_at, updated_at, wip_limit, wip_mode, archived_at
+         FROM columns WHERE id = ?",
+        [column_id],
+        |row| {
+            Ok((
+                row.get(0)?,
+                Column {
+                    id: row.get(1)?,
+                    title: row.get(2)?,
+                    position: row.get(3)?,
+                    card_ids: Vec::new(),
+                    background_color: row.get(4)?,
+                    wip_limit: row.get(8)?,
+                    wip_mode: row.get(9)?,
+                    version: row.get(5)?,
+                    archived_at: row.get(10)?,
+                    created_at: row.get(6)?,
+                    updated_at: row.get(7)?,
+                },
+            ))
+        },
+    );
+
+    match result {
+        Ok(found) => Ok(Some(found)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
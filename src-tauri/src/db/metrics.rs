@@ -0,0 +1,458 @@
+// 流动指标相关数据库操作
+// 创建、移动和更新卡片 (含写入看板、导入和撤销重做) 时在 card_transitions 中记录卡片进入的列,
+// 前置时间、周期时间、吞吐量和在制品停留时间都由这份流转记录计算。
+// 累积流图使用后台任务每天记录的各列卡片数 (column_flow_snapshots)。
+use super::kanban::get_board_impl;
+use super::projects::find_project;
+use super::{with_connection, with_transaction, DbError};
+use crate::commands::metrics::{
+    AgingCard, ColumnFlowSeries, CumulativeFlow, CumulativeFlowQuery, DurationStats, MetricsQuery,
+    ProjectMetrics, WeeklyThroughput,
+};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use rusqlite::{Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use tauri::AppHandle;
+
+/// 未指定起点时统计的天数
+const DEFAULT_RANGE_DAYS: i64 = 90;
+
+/// 累积流图未指定起始日期时返回的天数
+const DEFAULT_FLOW_DAYS: i64 = 30;
+
+/// 累积流图最多返回的天数
+const MAX_FLOW_DAYS: i64 = 366;
+
+/// 卡片所在列与最后一条流转记录不同时补记一次流转, 新卡片记为从无到所在列
+/// (批量写入看板、撤销重放等不经过 move_card 的写入使用, 应在调用方的事务中执行)
+pub(crate) fn sync_card_transition(conn: &Connection, card_id: &str) -> Result<(), DbError> {
+    let (project_id, column_id): (String, String) = conn.query_row(
+        "SELECT project_id, column_id FROM cards WHERE id = ?",
+        [card_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let last: Option<String> = conn
+        .query_row(
+            "SELECT to_column_id FROM card_transitions WHERE card_id = ?
+             ORDER BY transitioned_at DESC, id DESC LIMIT 1",
+            [card_id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    if last.as_deref() != Some(column_id.as_str()) {
+        record_transition(conn, &project_id, card_id, last.as_deref(), &column_id)?;
+    }
+    Ok(())
+}
+
+/// 卡片流转记录快照, 删除项目时随回收站保存
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CardTransition {
+    pub card_id: String,
+    pub from_column_id: Option<String>,
+    pub to_column_id: String,
+    pub transitioned_at: String,
+}
+
+/// 累积流每日快照
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnFlowSnapshot {
+    pub column_id: String,
+    pub day: String,
+    pub card_count: u32,
+}
+
+/// 记录卡片进入某列 (应在调用方的事务中执行)
+pub(crate) fn record_transition(
+    conn: &Connection,
+    project_id: &str,
+    card_id: &str,
+    from_column_id: Option<&str>,
+    to_column_id: &str,
+) -> Result<(), DbError> {
+    conn.execute(
+        "INSERT INTO card_transitions (project_id, card_id, from_column_id, to_column_id, transitioned_at)
+         VALUES (?, ?, ?, ?, ?)",
+        rusqlite::params![project_id, card_id, from_column_id, to_column_id, Utc::now().to_rfc3339()],
+    )?;
+    Ok(())
+}
+
+/// 获取项目的全部流转记录
+pub(crate) fn get_project_transitions(conn: &Connection, project_id: &str) -> Result<Vec<CardTransition>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT card_id, from_column_id, to_column_id, transitioned_at FROM card_transitions
+         WHERE project_id = ? ORDER BY id",
+    )?;
+    let transitions = stmt
+        .query_map([project_id], |row| {
+            Ok(CardTransition {
+                card_id: row.get(0)?,
+                from_column_id: row.get(1)?,
+                to_column_id: row.get(2)?,
+                transitioned_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(transitions)
+}
+
+/// 用快照替换项目的流转记录 (恢复项目时, 写回看板产生的新记录以快照为准)
+pub(crate) fn restore_transitions(
+    conn: &Connection,
+    project_id: &str,
+    transitions: &[CardTransition],
+) -> Result<(), DbError> {
+    conn.execute("DELETE FROM card_transitions WHERE project_id = ?", [project_id])?;
+    for transition in transitions {
+        conn.execute(
+            "INSERT INTO card_transitions (project_id, card_id, from_column_id, to_column_id, transitioned_at)
+             VALUES (?, ?, ?, ?, ?)",
+            rusqlite::params![
+                project_id,
+                transition.card_id,
+                transition.from_column_id,
+                transition.to_column_id,
+                transition.transitioned_at,
+            ],
+        )?;
+    }
+    Ok(())
+}
+
+/// 获取项目的全部累积流快照
+pub(crate) fn get_flow_snapshots(conn: &Connection, project_id: &str) -> Result<Vec<ColumnFlowSnapshot>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT column_id, day, card_count FROM column_flow_snapshots WHERE project_id = ? ORDER BY day",
+    )?;
+    let snapshots = stmt
+        .query_map([project_id], |row| {
+            Ok(ColumnFlowSnapshot {
+                column_id: row.get(0)?,
+                day: row.get(1)?,
+                card_count: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(snapshots)
+}
+
+/// 写回累积流快照
+pub(crate) fn restore_flow_snapshots(
+    conn: &Connection,
+    project_id: &str,
+    snapshots: &[ColumnFlowSnapshot],
+) -> Result<(), DbError> {
+    for snapshot in snapshots {
+        conn.execute(
+            "INSERT OR REPLACE INTO column_flow_snapshots (project_id, column_id, day, card_count)
+             VALUES (?, ?, ?, ?)",
+            rusqlite::params![project_id, snapshot.column_id, snapshot.day, snapshot.card_count],
+        )?;
+    }
+    Ok(())
+}
+
+/// 获取项目流动指标
+pub fn get_project_metrics(
+    app_handle: &AppHandle,
+    project_id: &str,
+    query: &MetricsQuery,
+    now: DateTime<Utc>,
+) -> Result<ProjectMetrics, DbError> {
+    let pid = project_id.to_string();
+    with_connection(app_handle, |conn| get_project_metrics_impl(conn, &pid, query, now))
+}
+
+fn get_project_metrics_impl(
+    conn: &Connection,
+    project_id: &str,
+    query: &MetricsQuery,
+    now: DateTime<Utc>,
+) -> Result<ProjectMetrics, DbError> {
+    if find_project(conn, project_id)?.is_none() {
+        return Err(DbError::not_found("project", project_id));
+    }
+
+    let until = match &query.until {
+        Some(value) => parse_timestamp(value)?,
+        None => now,
+    };
+    let since = match &query.since {
+        Some(value) => parse_timestamp(value)?,
+        None => until - Duration::days(DEFAULT_RANGE_DAYS),
+    };
+    if since >= until {
+        return Err(DbError::Validation("metrics range start must be before its end".to_string()));
+    }
+
+    let board = get_board_impl(conn, project_id)?;
+    let positions: HashMap<&str, usize> =
+        board.columns.iter().enumerate().map(|(i, c)| (c.id.as_str(), i)).collect();
+    let Some(last) = board.columns.len().checked_sub(1) else {
+        return Err(DbError::Validation(format!("project {} has no columns", project_id)));
+    };
+    let column_index = |id: &Option<String>, default: usize| match id {
+        Some(id) => positions
+            .get(id.as_str())
+            .copied()
+            .ok_or_else(|| DbError::Validation(format!("column {} does not belong to project {}", id, project_id))),
+        None => Ok(default),
+    };
+    let start = column_index(&query.start_column_id, last.min(1))?;
+    let end = column_index(&query.end_column_id, last)?;
+    if start > end {
+        return Err(DbError::Validation("start column must not come after the end column".to_string()));
+    }
+
+    // 按卡片分组的流转记录 (进入的列, 时间)
+    let mut transitions: BTreeMap<String, Vec<(String, DateTime<Utc>)>> = BTreeMap::new();
+    let mut stmt = conn.prepare(
+        "SELECT card_id, to_column_id, transitioned_at FROM card_transitions
+         WHERE project_id = ? ORDER BY card_id, transitioned_at, id",
+    )?;
+    let rows = stmt.query_map([project_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?))
+    })?;
+    for (card_id, column_id, at) in rows.filter_map(|r| r.ok()) {
+        if let Ok(at) = DateTime::parse_from_rfc3339(&at) {
+            transitions.entry(card_id).or_default().push((column_id, at.with_timezone(&Utc)));
+        }
+    }
+
+    // 首次进入开始列或其后的列的时间
+    let started_at = |history: &[(String, DateTime<Utc>)]| {
+        history
+            .iter()
+            .find(|(column_id, _)| positions.get(column_id.as_str()).is_some_and(|&p| p >= start))
+            .map(|(_, at)| *at)
+    };
+
+    let end_column_id = &board.columns[end].id;
+    let mut lead_times = Vec::new();
+    let mut cycle_times = Vec::new();
+    let mut weekly: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+    for history in transitions.values() {
+        // 最后一次流转进入完成列的卡片视为已完成
+        let (Some((_, created)), Some((column_id, done))) = (history.first(), history.last()) else {
+            continue;
+        };
+        if column_id != end_column_id || *done < since || *done >= until {
+            continue;
+        }
+        lead_times.push(days_between(*created, *done));
+        if let Some(started) = started_at(history) {
+            cycle_times.push(days_between(started, *done));
+        }
+        *weekly.entry(week_start(*done)).or_default() += 1;
+    }
+
+    let mut throughput = Vec::new();
+    let mut week = week_start(since);
+    let last_week = week_start(until - Duration::seconds(1));
+    while week <= last_week {
+        throughput.push(WeeklyThroughput {
+            week_start: week.format("%Y-%m-%d").to_string(),
+            count: weekly.get(&week).copied().unwrap_or(0),
+        });
+        week += Duration::days(7);
+    }
+
+    let mut aging_wip: Vec<AgingCard> = board
+        .cards
+        .iter()
+        .filter(|card| positions.get(card.column_id.as_str()).is_some_and(|&p| p >= start && p < end))
+        .map(|card| {
+            let started = transitions
+                .get(&card.id)
+                .and_then(|history| started_at(history))
+                .or_else(|| DateTime::parse_from_rfc3339(&card.created_at).ok().map(|at| at.with_timezone(&Utc)))
+                .unwrap_or(now);
+            AgingCard {
+                card_id: card.id.clone(),
+                title: card.title.clone(),
+                column_id: card.column_id.clone(),
+                started_at: started.to_rfc3339(),
+                age_days: days_between(started, now),
+            }
+        })
+        .collect();
+    aging_wip.sort_by(|a, b| b.age_days.total_cmp(&a.age_days));
+
+    Ok(ProjectMetrics {
+        since: since.to_rfc3339(),
+        until: until.to_rfc3339(),
+        start_column_id: board.columns[start].id.clone(),
+        end_column_id: end_column_id.clone(),
+        lead_time: duration_stats(lead_times),
+        cycle_time: duration_stats(cycle_times),
+        throughput,
+        aging_wip,
+    })
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, DbError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|_| DbError::Validation(format!("invalid timestamp: {}", value)))
+}
+
+fn days_between(from: DateTime<Utc>, to: DateTime<Utc>) -> f64 {
+    (to - from).num_seconds().max(0) as f64 / 86_400.0
+}
+
+/// 所在周的周一 (本地时区)
+fn week_start(at: DateTime<Utc>) -> NaiveDate {
+    let date = at.with_timezone(&Local).date_naive();
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+/// 计算平均值和百分位数 (最近秩法)
+fn duration_stats(mut values: Vec<f64>) -> DurationStats {
+    if values.is_empty() {
+        return DurationStats::default();
+    }
+    values.sort_by(f64::total_cmp);
+    let percentile = |p: f64| {
+        let rank = (p / 100.0 * values.len() as f64).ceil() as usize;
+        values[rank.clamp(1, values.len()) - 1]
+    };
+    DurationStats {
+        count: values.len(),
+        average: Some(values.iter().sum::<f64>() / values.len() as f64),
+        p50: Some(percentile(50.0)),
+        p85: Some(percentile(85.0)),
+        p95: Some(percentile(95.0)),
+    }
+}
+
+/// 记录当天所有未归档项目各列的卡片数, 同一天多次记录时以最后一次为准; 返回记录的列数
+pub fn snapshot_column_counts(app_handle: &AppHandle, today: NaiveDate) -> Result<usize, DbError> {
+    with_transaction(app_handle, |conn| {
+        let project_ids: Vec<String> = conn
+            .prepare("SELECT id FROM projects WHERE archived_at IS NULL")?
+            .query_map([], |row| row.get(0))?
+            .filter_map(|id| id.ok())
+            .collect();
+
+        let day = today.format("%Y-%m-%d").to_string();
+        let mut recorded = 0;
+        for project_id in project_ids {
+            for (column_id, count) in column_counts(conn, &project_id)? {
+                conn.execute(
+                    "INSERT OR REPLACE INTO column_flow_snapshots (project_id, column_id, day, card_count)
+                     VALUES (?, ?, ?, ?)",
+                    rusqlite::params![project_id, column_id, day, count],
+                )?;
+                recorded += 1;
+            }
+        }
+        Ok(recorded)
+    })
+}
+
+/// 项目中未归档各列当前的卡片数
+fn column_counts(conn: &Connection, project_id: &str) -> Result<HashMap<String, u32>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT col.id, (SELECT COUNT(*) FROM cards c WHERE c.column_id = col.id AND c.archived_at IS NULL)
+         FROM columns col WHERE col.project_id = ? AND col.archived_at IS NULL",
+    )?;
+    let counts = stmt
+        .query_map([project_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .filter_map(|c| c.ok())
+        .collect();
+    Ok(counts)
+}
+
+/// 获取项目每天各列的卡片数
+pub fn get_cumulative_flow(
+    app_handle: &AppHandle,
+    project_id: &str,
+    query: &CumulativeFlowQuery,
+    today: NaiveDate,
+) -> Result<CumulativeFlow, DbError> {
+    let pid = project_id.to_string();
+    with_connection(app_handle, |conn| get_cumulative_flow_impl(conn, &pid, query, today))
+}
+
+/// 当天使用实时数据; 没有记录的日期沿用之前最近一次记录, 更早的日期为 0
+fn get_cumulative_flow_impl(
+    conn: &Connection,
+    project_id: &str,
+    query: &CumulativeFlowQuery,
+    today: NaiveDate,
+) -> Result<CumulativeFlow, DbError> {
+    if find_project(conn, project_id)?.is_none() {
+        return Err(DbError::not_found("project", project_id));
+    }
+
+    // 不返回未来的日期
+    let until = match &query.until {
+        Some(value) => parse_day(value)?.min(today),
+        None => today,
+    };
+    let since = match &query.since {
+        Some(value) => parse_day(value)?,
+        None => until - Duration::days(DEFAULT_FLOW_DAYS - 1),
+    };
+    if since > until {
+        return Err(DbError::Validation("cumulative flow range start must not be after its end".to_string()));
+    }
+    if (until - since).num_days() >= MAX_FLOW_DAYS {
+        return Err(DbError::Validation(format!(
+            "cumulative flow range must not exceed {} days",
+            MAX_FLOW_DAYS
+        )));
+    }
+
+    let mut snapshots: HashMap<String, Vec<(NaiveDate, u32)>> = HashMap::new();
+    let mut stmt = conn.prepare(
+        "SELECT column_id, day, card_count FROM column_flow_snapshots
+         WHERE project_id = ? AND day <= ? ORDER BY day",
+    )?;
+    let rows = stmt.query_map(
+        rusqlite::params![project_id, until.format("%Y-%m-%d").to_string()],
+        |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, u32>(2)?)),
+    )?;
+    for (column_id, day, count) in rows.filter_map(|r| r.ok()) {
+        if let Ok(day) = NaiveDate::parse_from_str(&day, "%Y-%m-%d") {
+            snapshots.entry(column_id).or_default().push((day, count));
+        }
+    }
+
+    let days: Vec<NaiveDate> = since.iter_days().take_while(|day| *day <= until).collect();
+    let live = column_counts(conn, project_id)?;
+    let columns = get_board_impl(conn, project_id)?
+        .columns
+        .into_iter()
+        .map(|column| {
+            let history = snapshots.remove(&column.id).unwrap_or_default();
+            let counts = days
+                .iter()
+                .map(|day| {
+                    if *day == today {
+                        return live.get(&column.id).copied().unwrap_or(0);
+                    }
+                    history
+                        .iter()
+                        .rev()
+                        .find(|(recorded, _)| recorded <= day)
+                        .map(|(_, count)| *count)
+                        .unwrap_or(0)
+                })
+                .collect();
+            ColumnFlowSeries { column_id: column.id, title: column.title, counts }
+        })
+        .collect();
+
+    Ok(CumulativeFlow {
+        days: days.iter().map(|day| day.format("%Y-%m-%d").to_string()).collect(),
+        columns,
+    })
+}
+
+fn parse_day(value: &str) -> Result<NaiveDate, DbError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| DbError::Validation(format!("invalid date: {}", value)))
+}
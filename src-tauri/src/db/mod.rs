@@ -1,11 +1,32 @@
 // 数据库模块
 // 使用 SQLite 进行数据持久化
 
+pub mod activities;
+pub mod archive;
+pub mod attachments;
+pub mod backups;
+pub mod checklists;
+pub mod comments;
+pub mod export;
+mod fields;
+pub mod history;
+pub mod import;
 pub mod kanban;
+pub mod metrics;
+pub mod priorities;
 pub mod projects;
+pub mod rank;
+pub mod recurrence;
+pub mod reminders;
 mod schema;
+pub mod search;
+pub mod tags;
+pub mod trash;
 
+use crate::commands::kanban::{Card, Column};
+use crate::commands::projects::Project;
 use rusqlite::Connection;
+use serde::Serialize;
 use std::{
     path::{Path, PathBuf},
     sync::Mutex,
@@ -27,11 +48,63 @@ pub enum DbError {
     NotInitialized,
     #[error("Lock poisoned")]
     LockPoisoned,
-    #[error("Item not found: {0}")]
-    #[allow(dead_code)]
-    NotFound(String),
+    #[error("Validation error: {0}")]
+    Validation(String),
+    #[error("Database schema version {found} is newer than supported version {supported}")]
+    UnsupportedSchema { found: i32, supported: i32 },
+    #[error("Version conflict: {0} has been modified")]
+    Conflict(Box<ConflictCopy>),
+    #[error("Column {column_id} has reached its WIP limit of {limit}")]
+    WipLimitExceeded { column_id: String, limit: u32 },
+    #[error("{entity} {id} not found")]
+    NotFound { entity: &'static str, id: String },
 }
 
+impl DbError {
+    /// 数据不存在 (entity 为数据类型, 如 "card")
+    pub(crate) fn not_found(entity: &'static str, id: &str) -> Self {
+        DbError::NotFound { entity, id: id.to_string() }
+    }
+}
+
+/// 版本冲突时数据库中的当前数据
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum ConflictCopy {
+    Card(Card),
+    Column(Column),
+    Project(Project),
+}
+
+impl ConflictCopy {
+    /// 数据类型
+    pub fn entity(&self) -> &'static str {
+        match self {
+            ConflictCopy::Card(_) => "card",
+            ConflictCopy::Column(_) => "column",
+            ConflictCopy::Project(_) => "project",
+        }
+    }
+
+    /// 数据ID
+    pub fn id(&self) -> &str {
+        match self {
+            ConflictCopy::Card(card) => &card.id,
+            ConflictCopy::Column(column) => &column.id,
+            ConflictCopy::Project(project) => &project.id,
+        }
+    }
+}
+
+impl std::fmt::Display for ConflictCopy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.entity(), self.id())
+    }
+}
+
+/// 数据库文件名 (位于应用数据目录)
+pub(crate) const DATABASE_FILE_NAME: &str = "captaintodo.db";
+
 /// 数据库连接状态
 pub struct DbState {
     pub conn: Mutex<Connection>,
@@ -40,26 +113,24 @@ pub struct DbState {
 /// 初始化数据库
 pub fn init_database(app_handle: &AppHandle) -> Result<(), DbError> {
     // 获取应用数据目录
-    let app_dir = app_handle
-        .path()
-        .app_data_dir()
-        .map_err(|_| DbError::Io(std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            "Could not get app data directory",
-        )))?;
+    let app_dir = app_data_dir(app_handle)?;
 
-    // 确保目录存在
-    std::fs::create_dir_all(&app_dir)?;
-
-    let db_path = app_dir.join("captaintodo.db");
+    let db_path = app_dir.join(DATABASE_FILE_NAME);
     migrate_legacy_database_if_needed(&app_dir, &db_path)?;
     log::info!("Database path: {:?}", db_path);
 
     // 打开或创建数据库
     let conn = Connection::open(&db_path)?;
 
+    // 升级已有数据库前先自动备份
+    let version = schema::current_version(&conn)?;
+    if version > 0 && version < schema::latest_version() {
+        backups::backup_before_upgrade(app_handle, &conn)?;
+    }
+
     // 执行数据库迁移
     schema::run_migrations(&conn)?;
+    priorities::load_current_scale(&conn)?;
 
     // 将连接存储到应用状态
     app_handle.manage(DbState {
@@ -69,6 +140,21 @@ pub fn init_database(app_handle: &AppHandle) -> Result<(), DbError> {
     Ok(())
 }
 
+/// 获取应用数据目录 (不存在时自动创建)
+pub(crate) fn app_data_dir(app_handle: &AppHandle) -> Result<PathBuf, DbError> {
+    let app_dir = app_handle
+        .path()
+        .app_data_dir()
+        .map_err(|_| DbError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Could not get app data directory",
+        )))?;
+
+    // 确保目录存在
+    std::fs::create_dir_all(&app_dir)?;
+    Ok(app_dir)
+}
+
 fn migrate_legacy_database_if_needed(app_dir: &Path, db_path: &Path) -> Result<(), DbError> {
     let Some(legacy_db_path) = legacy_database_path(app_dir) else {
         return Ok(());
@@ -156,3 +242,16 @@ where
     let conn = state.conn.lock().map_err(|_| DbError::LockPoisoned)?;
     f(&conn)
 }
+
+/// 在单个事务中执行操作, 出错时自动回滚
+pub fn with_transaction<F, T>(app_handle: &AppHandle, f: F) -> Result<T, DbError>
+where
+    F: FnOnce(&Connection) -> Result<T, DbError>,
+{
+    with_connection(app_handle, |conn| {
+        let tx = conn.unchecked_transaction()?;
+        let result = f(&tx)?;
+        tx.commit()?;
+        Ok(result)
+    })
+}
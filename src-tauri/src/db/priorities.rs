@@ -0,0 +1,160 @@
+// 优先级刻度相关数据库操作
+// 刻度 (从低到高的优先级标识和名称) 以 JSON 存放在 settings 表中; 卡片的优先级必须属于当前刻度。
+// 当前刻度另在内存中保存一份, 供 Priority 解析和排序时使用。
+use super::{with_connection, DbError};
+use crate::commands::priorities::{PriorityLevel, PriorityScale};
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::HashSet;
+use std::sync::{LazyLock, PoisonError, RwLock};
+use tauri::AppHandle;
+
+const SETTINGS_KEY: &str = "priority_scale";
+
+/// 当前数据库的优先级刻度; 打开数据库、恢复备份和修改刻度时更新
+static CURRENT_SCALE: LazyLock<RwLock<PriorityScale>> = LazyLock::new(|| RwLock::new(PriorityScale::default()));
+
+/// 最多允许配置的优先级个数
+const MAX_LEVELS: usize = 20;
+
+/// 优先级标识的最大长度
+const MAX_VALUE_LEN: usize = 32;
+
+/// 获取优先级刻度
+pub fn get_priority_scale(app_handle: &AppHandle) -> Result<PriorityScale, DbError> {
+    with_connection(app_handle, get_priority_scale_impl)
+}
+
+pub(crate) fn get_priority_scale_impl(conn: &Connection) -> Result<PriorityScale, DbError> {
+    let result = conn.query_row(
+        "SELECT value FROM settings WHERE key = ?",
+        [SETTINGS_KEY],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(value) => Ok(serde_json::from_str(&value)?),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(PriorityScale::default()),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
+/// 从数据库读取优先级刻度作为当前刻度
+pub(crate) fn load_current_scale(conn: &Connection) -> Result<(), DbError> {
+    let scale = get_priority_scale_impl(conn)?;
+    *CURRENT_SCALE.write().unwrap_or_else(PoisonError::into_inner) = scale;
+    Ok(())
+}
+
+/// 优先级标识在当前刻度中的位次, 最低一级为 0; 不在刻度中时返回 None
+pub(crate) fn current_rank(value: &str) -> Option<usize> {
+    let scale = CURRENT_SCALE.read().unwrap_or_else(PoisonError::into_inner);
+    scale.levels.iter().position(|level| level.value == value)
+}
+
+/// 在当前刻度中按标识 (不区分大小写) 或名称查找, 返回规范的标识
+pub(crate) fn find_current_level(value: &str) -> Option<String> {
+    let scale = CURRENT_SCALE.read().unwrap_or_else(PoisonError::into_inner);
+    scale.find(value).map(|level| level.value.clone())
+}
+
+/// 当前刻度中不低于给定位次的优先级标识
+pub(crate) fn current_levels_from(rank: usize) -> Vec<String> {
+    let scale = CURRENT_SCALE.read().unwrap_or_else(PoisonError::into_inner);
+    scale.levels.iter().skip(rank).map(|level| level.value.clone()).collect()
+}
+
+/// 更新优先级刻度; 仍被卡片、回收站或撤销历史使用的优先级不能移除
+pub fn update_priority_scale(
+    app_handle: &AppHandle,
+    scale: &PriorityScale,
+) -> Result<PriorityScale, DbError> {
+    let scale = PriorityScale {
+        levels: scale
+            .levels
+            .iter()
+            .map(|level| PriorityLevel {
+                value: level.value.trim().to_string(),
+                label: level.label.trim().to_string(),
+            })
+            .collect(),
+    };
+    validate_scale(&scale)?;
+
+    let value = serde_json::to_string(&scale)?;
+    with_connection(app_handle, |conn| {
+        let previous = get_priority_scale_impl(conn)?;
+        for level in &previous.levels {
+            if scale.level(&level.value).is_none() && priority_in_use(conn, &level.value)? {
+                return Err(DbError::Validation(format!(
+                    "priority {} is still in use and cannot be removed",
+                    level.value
+                )));
+            }
+        }
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+            [SETTINGS_KEY, value.as_str()],
+        )?;
+        *CURRENT_SCALE.write().unwrap_or_else(PoisonError::into_inner) = scale.clone();
+        Ok(scale.clone())
+    })
+}
+
+/// 卡片 (含已归档的卡片)、回收站和撤销历史中是否仍有该优先级
+fn priority_in_use(conn: &Connection, value: &str) -> Result<bool, DbError> {
+    let snapshot = format!("\"priority\":\"{}\"", value);
+    let found = conn
+        .query_row(
+            "SELECT 1 FROM cards WHERE priority = ?1
+             UNION ALL SELECT 1 FROM trash WHERE instr(restore_ops, ?2) > 0
+             UNION ALL SELECT 1 FROM undo_history WHERE instr(undo_ops, ?2) > 0 OR instr(redo_ops, ?2) > 0
+             LIMIT 1",
+            [value, snapshot.as_str()],
+            |_| Ok(()),
+        )
+        .optional()?;
+    Ok(found.is_some())
+}
+
+fn validate_scale(scale: &PriorityScale) -> Result<(), DbError> {
+    if scale.levels.is_empty() {
+        return Err(DbError::Validation("priority scale must not be empty".to_string()));
+    }
+    if scale.levels.len() > MAX_LEVELS {
+        return Err(DbError::Validation(format!(
+            "priority scale must not have more than {} levels",
+            MAX_LEVELS
+        )));
+    }
+
+    let mut values = HashSet::new();
+    let mut labels = HashSet::new();
+    for level in &scale.levels {
+        let value = level.value.as_str();
+        let valid = !value.is_empty()
+            && value.len() <= MAX_VALUE_LEN
+            && value.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+        if !valid {
+            return Err(DbError::Validation(format!(
+                "priority value must be 1-{} lowercase letters, digits, '-' or '_': {}",
+                MAX_VALUE_LEN, value
+            )));
+        }
+        if level.label.is_empty() {
+            return Err(DbError::Validation(format!("priority {} must have a label", value)));
+        }
+        if !values.insert(value) {
+            return Err(DbError::Validation(format!(
+                "priority {} appears more than once in the scale",
+                value
+            )));
+        }
+        if !labels.insert(level.label.as_str()) {
+            return Err(DbError::Validation(format!(
+                "priority label {} appears more than once in the scale",
+                level.label
+            )));
+        }
+    }
+    Ok(())
+}
@@ -1,66 +1,90 @@
 // 项目相关数据库操作
-use super::{with_connection, DbError};
+use super::activities::{get_all_project_activities, record_activity, restore_activities, NewActivity};
+use super::attachments::get_project_attachments;
+use super::checklists::get_project_checklist_items;
+use super::comments::get_project_comments;
+use super::history::{record_history, ProjectPosition, UndoOp, PROJECT_LIST_SCOPE};
+use super::kanban::{get_board_impl, load_board};
+use super::metrics::{
+    get_flow_snapshots, get_project_transitions, restore_flow_snapshots, restore_transitions, CardTransition,
+    ColumnFlowSnapshot,
+};
+use super::rank::{apply_order, place, spread, RankScope};
+use super::reminders::{get_project_deliveries, restore_deliveries, ReminderDelivery};
+use super::tags::get_project_tags;
+use super::trash::move_to_trash;
+use super::{with_connection, with_transaction, ConflictCopy, DbError};
+use crate::commands::activities::{Activity, ActivityType};
+use crate::commands::archive::ArchiveKind;
+use crate::commands::attachments::Attachment;
+use crate::commands::checklists::ChecklistItem;
+use crate::commands::comments::Comment;
+use crate::commands::kanban::Board;
 use crate::commands::projects::Project;
+use crate::commands::tags::Tag;
 use chrono::Utc;
 use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
 use tauri::AppHandle;
 use uuid::Uuid;
 
-/// 获取所有项目
-pub fn get_all_projects(app_handle: &AppHandle) -> Result<Vec<Project>, DbError> {
-    with_connection(app_handle, |conn| {
-        let mut stmt = conn.prepare(
-            "SELECT id, name, description, position, created_at, updated_at FROM projects ORDER BY position ASC, created_at DESC"
-        )?;
-
-        let projects_iter = stmt.query_map([], |row| {
-            Ok(Project {
-                id: row.get(0)?,
-                name: row.get(1)?,
-                description: row.get(2)?,
-                position: row.get(3)?,
-                created_at: row.get(4)?,
-                updated_at: row.get(5)?,
-            })
-        })?;
-
-        let projects: Vec<Project> = projects_iter.filter_map(|p| p.ok()).collect();
-        Ok(projects)
-    })
+/// 获取所有项目 (include_archived 为 true 时包含已归档的项目)
+pub fn get_all_projects(app_handle: &AppHandle, include_archived: bool) -> Result<Vec<Project>, DbError> {
+    with_connection(app_handle, |conn| load_projects(conn, include_archived))
 }
 
 /// 创建项目
 pub fn create_project(app_handle: &AppHandle, project: &Project) -> Result<Project, DbError> {
     let p = project.clone();
-    with_connection(app_handle, |conn| create_project_impl(conn, &p))
+    with_transaction(app_handle, |conn| {
+        let created = create_project_impl(conn, &p)?;
+        let board = get_board_impl(conn, &created.id)?;
+        record_history(
+            conn,
+            PROJECT_LIST_SCOPE,
+            format!("创建项目「{}」", created.name),
+            vec![UndoOp::DeleteProject { project_id: created.id.clone() }],
+            vec![UndoOp::RestoreProject { project: created.clone(), board: Some(board) }],
+        )?;
+        Ok(created)
+    })
 }
 
-fn create_project_impl(conn: &Connection, project: &Project) -> Result<Project, DbError> {
+pub(crate) fn create_project_impl(conn: &Connection, project: &Project) -> Result<Project, DbError> {
     let now = Utc::now().to_rfc3339();
     let id = if project.id.is_empty() {
         Uuid::new_v4().to_string()
     } else {
         project.id.clone()
     };
+    // 新项目排在最后
     let position = conn.query_row(
-        "SELECT COALESCE(MAX(position), -1) + 1 FROM projects",
+        "SELECT COUNT(*) FROM projects WHERE archived_at IS NULL",
         [],
         |row| row.get::<_, i32>(0),
     )?;
+    let (rank, position) = place(conn, RankScope::Projects, &id, position)?;
 
     conn.execute(
-        "INSERT INTO projects (id, name, description, position, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
-        rusqlite::params![id, project.name, project.description, position, now, now],
+        "INSERT INTO projects (id, name, description, rank, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+        rusqlite::params![id, project.name, project.description, rank, now, now],
     )?;
 
     // 为新项目创建默认列
     create_default_columns(conn, &id, &now)?;
 
+    record_activity(
+        conn,
+        &NewActivity::new(&id, ActivityType::ProjectCreated, &project.name),
+    )?;
+
     Ok(Project {
         id,
         name: project.name.clone(),
         description: project.description.clone(),
         position,
+        version: 1,
+        archived_at: None,
         created_at: now.clone(),
         updated_at: now,
     })
@@ -69,17 +93,17 @@ fn create_project_impl(conn: &Connection, project: &Project) -> Result<Project,
 /// 创建默认列
 fn create_default_columns(conn: &Connection, project_id: &str, now: &str) -> Result<(), DbError> {
     let default_columns = [
-        ("待办", 0, "#f8fafc"),
-        ("进行中", 1, "#eff6ff"),
-        ("已完成", 2, "#f0fdf4"),
+        ("待办", "#f8fafc"),
+        ("进行中", "#eff6ff"),
+        ("已完成", "#f0fdf4"),
     ];
 
-    for (title, position, color) in default_columns {
+    for ((title, color), rank) in default_columns.into_iter().zip(spread(default_columns.len())) {
         let id = Uuid::new_v4().to_string();
         conn.execute(
-            "INSERT INTO columns (id, project_id, title, position, background_color, created_at, updated_at)
+            "INSERT INTO columns (id, project_id, title, rank, background_color, created_at, updated_at)
              VALUES (?, ?, ?, ?, ?, ?, ?)",
-            rusqlite::params![id, project_id, title, position, color, now, now],
+            rusqlite::params![id, project_id, title, rank, color, now, now],
         )?;
     }
 
@@ -89,69 +113,251 @@ fn create_default_columns(conn: &Connection, project_id: &str, now: &str) -> Res
 /// 更新项目
 pub fn update_project(app_handle: &AppHandle, project: &Project) -> Result<Project, DbError> {
     let p = project.clone();
-    with_connection(app_handle, |conn| {
-        let now = Utc::now().to_rfc3339();
-
-        conn.execute(
-            "UPDATE projects SET name = ?, description = ?, position = ?, updated_at = ? WHERE id = ?",
-            rusqlite::params![p.name, p.description, p.position, now, p.id],
+    with_transaction(app_handle, |conn| {
+        let previous = find_project(conn, &p.id)?
+            .ok_or_else(|| DbError::not_found("project", &p.id))?;
+        let updated = update_project_impl(conn, &p)?;
+        record_history(
+            conn,
+            &updated.id,
+            format!("更新项目「{}」", updated.name),
+            vec![UndoOp::RestoreProject { project: previous, board: None }],
+            vec![UndoOp::RestoreProject { project: updated.clone(), board: None }],
         )?;
+        Ok(updated)
+    })
+}
 
-        Ok(Project {
-            id: p.id.clone(),
-            name: p.name.clone(),
-            description: p.description.clone(),
-            position: p.position,
-            created_at: p.created_at.clone(),
-            updated_at: now,
-        })
+fn update_project_impl(conn: &Connection, project: &Project) -> Result<Project, DbError> {
+    let now = Utc::now().to_rfc3339();
+    let current = find_project(conn, &project.id)?
+        .ok_or_else(|| DbError::not_found("project", &project.id))?;
+    if current.version != project.version {
+        return Err(DbError::Conflict(Box::new(ConflictCopy::Project(current))));
+    }
+    let (rank, position) = place(conn, RankScope::Projects, &project.id, project.position)?;
+
+    conn.execute(
+        "UPDATE projects SET name = ?, description = ?, rank = ?, updated_at = ?, version = version + 1 WHERE id = ?",
+        rusqlite::params![project.name, project.description, rank, now, project.id],
+    )?;
+
+    record_activity(
+        conn,
+        &NewActivity::new(&project.id, ActivityType::ProjectUpdated, &project.name),
+    )?;
+
+    Ok(Project {
+        id: project.id.clone(),
+        name: project.name.clone(),
+        description: project.description.clone(),
+        position,
+        version: project.version + 1,
+        archived_at: current.archived_at.clone(),
+        created_at: current.created_at.clone(),
+        updated_at: now,
     })
 }
 
 /// 删除项目
 pub fn delete_project(app_handle: &AppHandle, project_id: &str) -> Result<(), DbError> {
     let pid = project_id.to_string();
-    with_connection(app_handle, |conn| {
-        // 级联删除会自动删除相关的列和卡片
-        conn.execute("DELETE FROM projects WHERE id = ?", [&pid])?;
+    with_transaction(app_handle, |conn| {
+        let Some(previous) = find_project(conn, &pid)? else {
+            return Err(DbError::not_found("project", &pid));
+        };
+        let board = load_board(conn, &pid, true)?;
+        let checklist_items = get_project_checklist_items(conn, &pid)?;
+        let comments = get_project_comments(conn, &pid)?;
+        let attachments = get_project_attachments(conn, &pid)?;
+        let tags = get_project_tags(conn, &pid)?;
+        let mut undo_ops =
+            restore_project_ops(previous.clone(), board, tags, checklist_items, comments, attachments);
+        undo_ops.push(UndoOp::RestoreProjectRecords {
+            project_id: pid.clone(),
+            records: get_project_records(conn, &pid)?,
+        });
+        let entry = move_to_trash(conn, ArchiveKind::Project, &pid, &pid, &previous.name, &undo_ops)?;
+        undo_ops.push(UndoOp::RemoveFromTrash { trash_id: entry.item.id.clone() });
 
-        // 如果删除的是当前项目，清除设置
-        let current = get_current_project_impl(conn)?;
-        if current.as_deref() == Some(&pid) {
-            conn.execute("DELETE FROM settings WHERE key = 'current_project_id'", [])?;
-        }
+        delete_project_impl(conn, &pid)?;
 
-        normalize_project_positions(conn)?;
+        record_history(
+            conn,
+            PROJECT_LIST_SCOPE,
+            format!("删除项目「{}」", previous.name),
+            undo_ops,
+            vec![UndoOp::DeleteProject { project_id: pid.clone() }, UndoOp::PutInTrash { entry }],
+        )
+    })
+}
 
-        Ok(())
+/// 生成恢复整个项目的操作序列
+pub(crate) fn restore_project_ops(
+    project: Project,
+    mut board: Board,
+    tags: Vec<Tag>,
+    checklist_items: Vec<ChecklistItem>,
+    comments: Vec<Comment>,
+    attachments: Vec<Attachment>,
+) -> Vec<UndoOp> {
+    // 标签随项目一起删除, 先恢复不带标签的看板, 再由标签恢复卡片关联
+    let restore_tags: Vec<UndoOp> = tags
+        .into_iter()
+        .map(|tag| {
+            let card_ids = board
+                .cards
+                .iter()
+                .filter(|c| c.tag_ids.as_ref().is_some_and(|ids| ids.contains(&tag.id)))
+                .map(|c| c.id.clone())
+                .collect();
+            UndoOp::RestoreTag { project_id: project.id.clone(), tag, card_ids }
+        })
+        .collect();
+    for card in &mut board.cards {
+        card.tag_ids = None;
+    }
+
+    let mut ops = vec![UndoOp::RestoreProject { project, board: Some(board) }];
+    ops.extend(restore_tags);
+    ops.extend([
+        UndoOp::RestoreChecklistItems { items: checklist_items },
+        UndoOp::RestoreComments { comments },
+        UndoOp::RestoreAttachments { attachments },
+    ]);
+    ops
+}
+
+/// 删除项目时随级联删除的记录, 保存在回收站快照中以便恢复
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProjectRecords {
+    #[serde(default)]
+    pub activities: Vec<Activity>,
+    #[serde(default)]
+    pub transitions: Vec<CardTransition>,
+    #[serde(default)]
+    pub flow_snapshots: Vec<ColumnFlowSnapshot>,
+    #[serde(default)]
+    pub reminder_deliveries: Vec<ReminderDelivery>,
+}
+
+fn get_project_records(conn: &Connection, project_id: &str) -> Result<ProjectRecords, DbError> {
+    Ok(ProjectRecords {
+        activities: get_all_project_activities(conn, project_id)?,
+        transitions: get_project_transitions(conn, project_id)?,
+        flow_snapshots: get_flow_snapshots(conn, project_id)?,
+        reminder_deliveries: get_project_deliveries(conn, project_id)?,
     })
 }
 
+/// 写回项目记录 (应在项目、看板恢复之后执行)
+pub(crate) fn restore_project_records(
+    conn: &Connection,
+    project_id: &str,
+    records: &ProjectRecords,
+) -> Result<(), DbError> {
+    restore_activities(conn, &records.activities)?;
+    restore_transitions(conn, project_id, &records.transitions)?;
+    restore_flow_snapshots(conn, project_id, &records.flow_snapshots)?;
+    restore_deliveries(conn, &records.reminder_deliveries)
+}
+
+pub(crate) fn delete_project_impl(conn: &Connection, project_id: &str) -> Result<(), DbError> {
+    // 级联删除会自动删除相关的列、卡片和活动记录
+    conn.execute("DELETE FROM projects WHERE id = ?", [project_id])?;
+
+    // 如果删除的是当前项目，清除设置
+    clear_current_project(conn, project_id)
+}
+
+/// 指定项目为当前项目时清除当前项目设置
+pub(crate) fn clear_current_project(conn: &Connection, project_id: &str) -> Result<(), DbError> {
+    let current = get_current_project_impl(conn)?;
+    if current.as_deref() == Some(project_id) {
+        conn.execute("DELETE FROM settings WHERE key = 'current_project_id'", [])?;
+    }
+
+    Ok(())
+}
+
+/// 按 ID 查询项目
+pub(crate) fn find_project(conn: &Connection, project_id: &str) -> Result<Option<Project>, DbError> {
+    let result = conn.query_row(
+        "SELECT id, name, description,
+                (SELECT COUNT(*) FROM projects AS prev WHERE prev.rank < projects.rank AND prev.archived_at IS NULL),
+                version, archived_at, created_at, updated_at
+         FROM projects WHERE id = ?",
+        [project_id],
+        |row| {
+            Ok(Project {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                description: row.get(2)?,
+                position: row.get(3)?,
+                version: row.get(4)?,
+                archived_at: row.get(5)?,
+                created_at: row.get(6)?,
+                updated_at: row.get(7)?,
+            })
+        },
+    );
+
+    match result {
+        Ok(project) => Ok(Some(project)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
 /// 按传入 ID 顺序保存项目排序
 pub fn reorder_projects(
     app_handle: &AppHandle,
     project_ids: &[String],
 ) -> Result<Vec<Project>, DbError> {
     let ids = project_ids.to_vec();
-    with_connection(app_handle, |conn| {
-        let now = Utc::now().to_rfc3339();
-
-        conn.execute("BEGIN TRANSACTION", [])?;
-        for (position, project_id) in ids.iter().enumerate() {
-            conn.execute(
-                "UPDATE projects SET position = ?, updated_at = ? WHERE id = ?",
-                rusqlite::params![position as i32, now, project_id],
-            )?;
+    with_transaction(app_handle, |conn| {
+        let previous = get_all_projects_impl(conn)?;
+        apply_order(conn, RankScope::Projects, &ids)?;
+        let reordered = get_all_projects_impl(conn)?;
+
+        // 只为位置实际发生变化的项目记录活动
+        for project in &reordered {
+            if let Some(before) = previous.iter().find(|p| p.id == project.id) {
+                if before.position != project.position {
+                    record_activity(
+                        conn,
+                        &NewActivity::new(&project.id, ActivityType::ProjectReordered, &project.name)
+                            .description(format!("{} -> {}", before.position, project.position)),
+                    )?;
+                }
+            }
         }
-        conn.execute("COMMIT", [])?;
 
-        get_all_projects_impl(conn)
+        record_history(
+            conn,
+            PROJECT_LIST_SCOPE,
+            "调整项目顺序",
+            vec![UndoOp::RestoreProjectPositions { positions: project_positions(&previous) }],
+            vec![UndoOp::RestoreProjectPositions { positions: project_positions(&reordered) }],
+        )?;
+
+        Ok(reordered)
     })
 }
 
+fn project_positions(projects: &[Project]) -> Vec<ProjectPosition> {
+    projects
+        .iter()
+        .map(|project| ProjectPosition {
+            id: project.id.clone(),
+            position: project.position,
+        })
+        .collect()
+}
+
 /// 获取当前项目ID
 pub fn get_current_project(app_handle: &AppHandle) -> Result<Option<String>, DbError> {
-    with_connection(app_handle, |conn| get_current_project_impl(conn))
+    with_connection(app_handle, get_current_project_impl)
 }
 
 fn get_current_project_impl(conn: &Connection) -> Result<Option<String>, DbError> {
@@ -168,38 +374,36 @@ fn get_current_project_impl(conn: &Connection) -> Result<Option<String>, DbError
     }
 }
 
+/// 读取未归档的项目
 fn get_all_projects_impl(conn: &Connection) -> Result<Vec<Project>, DbError> {
-    let mut stmt = conn.prepare(
-        "SELECT id, name, description, position, created_at, updated_at FROM projects ORDER BY position ASC, created_at DESC"
-    )?;
+    load_projects(conn, false)
+}
+
+fn load_projects(conn: &Connection, include_archived: bool) -> Result<Vec<Project>, DbError> {
+    let archived_filter = if include_archived { "" } else { " WHERE archived_at IS NULL" };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT id, name, description, version, archived_at, created_at, updated_at FROM projects{} ORDER BY rank, id",
+        archived_filter
+    ))?;
 
     let projects_iter = stmt.query_map([], |row| {
         Ok(Project {
             id: row.get(0)?,
             name: row.get(1)?,
             description: row.get(2)?,
-            position: row.get(3)?,
-            created_at: row.get(4)?,
-            updated_at: row.get(5)?,
+            position: 0,
+            version: row.get(3)?,
+            archived_at: row.get(4)?,
+            created_at: row.get(5)?,
+            updated_at: row.get(6)?,
         })
     })?;
 
-    let projects: Vec<Project> = projects_iter.filter_map(|p| p.ok()).collect();
-    Ok(projects)
-}
-
-fn normalize_project_positions(conn: &Connection) -> Result<(), DbError> {
-    let projects = get_all_projects_impl(conn)?;
-    let now = Utc::now().to_rfc3339();
-
-    for (position, project) in projects.iter().enumerate() {
-        conn.execute(
-            "UPDATE projects SET position = ?, updated_at = ? WHERE id = ?",
-            rusqlite::params![position as i32, now, project.id],
-        )?;
+    let mut projects: Vec<Project> = projects_iter.filter_map(|p| p.ok()).collect();
+    for (position, project) in projects.iter_mut().enumerate() {
+        project.position = position as i32;
     }
-
-    Ok(())
+    Ok(projects)
 }
 
 /// 设置当前项目
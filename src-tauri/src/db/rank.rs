@@ -0,0 +1,281 @@
+// 排序键相关操作
+// 项目、列和卡片按字符串排序键 (LexoRank 风格的 36 进制小数) 的字典序排列,
+// 插入或移动时只需在相邻两个键之间生成新键, 只改写被移动的一行。
+// 接口中的 position 为按排序键计算出的序号, 不再存储。
+use super::{with_transaction, DbError};
+use rusqlite::Connection;
+use tauri::AppHandle;
+
+const DIGITS: &[u8; 36] = b"0123456789abcdefghijklmnopqrstuvwxyz";
+const BASE: u32 = 36;
+
+/// 排序键超过该长度时重新均匀分配
+const MAX_RANK_LEN: usize = 12;
+
+/// 排序范围, 排序键只在同一范围内比较
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum RankScope<'a> {
+    /// 项目列表
+    Projects,
+    /// 项目中的列 (项目ID)
+    Columns(&'a str),
+    /// 列中的卡片 (列ID)
+    Cards(&'a str),
+}
+
+impl RankScope<'_> {
+    fn table(&self) -> &'static str {
+        match self {
+            RankScope::Projects => "projects",
+            RankScope::Columns(_) => "columns",
+            RankScope::Cards(_) => "cards",
+        }
+    }
+
+    /// 读取范围内按排序键排列的 (ID, 排序键), 已归档的行不参与排序
+    fn ranks(&self, conn: &Connection) -> Result<Vec<(String, String)>, DbError> {
+        let map = |row: &rusqlite::Row| Ok((row.get(0)?, row.get(1)?));
+        let ranks = match self {
+            RankScope::Projects => conn
+                .prepare("SELECT id, rank FROM projects WHERE archived_at IS NULL ORDER BY rank, id")?
+                .query_map([], map)?
+                .filter_map(|r| r.ok())
+                .collect(),
+            RankScope::Columns(project_id) => conn
+                .prepare("SELECT id, rank FROM columns WHERE project_id = ? AND archived_at IS NULL ORDER BY rank, id")?
+                .query_map([project_id], map)?
+                .filter_map(|r| r.ok())
+                .collect(),
+            RankScope::Cards(column_id) => conn
+                .prepare("SELECT id, rank FROM cards WHERE column_id = ? AND archived_at IS NULL ORDER BY rank, id")?
+                .query_map([column_id], map)?
+                .filter_map(|r| r.ok())
+                .collect(),
+        };
+        Ok(ranks)
+    }
+
+    fn set_rank(&self, conn: &Connection, id: &str, rank: &str) -> Result<(), DbError> {
+        conn.execute(
+            &format!("UPDATE {} SET rank = ? WHERE id = ?", self.table()),
+            [rank, id],
+        )?;
+        Ok(())
+    }
+}
+
+/// 计算把元素放到范围中第 position 位时的排序键, 返回排序键及实际序号
+///
+/// 元素已在该位置时沿用原排序键; 否则在相邻元素之间生成新键。
+pub(crate) fn place(
+    conn: &Connection,
+    scope: RankScope,
+    id: &str,
+    position: i32,
+) -> Result<(String, i32), DbError> {
+    let ranks = scope.ranks(conn)?;
+    if let Some(index) = ranks.iter().position(|(other, _)| other == id) {
+        if index as i32 == position {
+            return Ok((ranks[index].1.clone(), position));
+        }
+    }
+
+    let siblings: Vec<&str> = ranks
+        .iter()
+        .filter(|(other, _)| other != id)
+        .map(|(_, rank)| rank.as_str())
+        .collect();
+    let index = position.clamp(0, siblings.len() as i32) as usize;
+    let before = index.checked_sub(1).map(|i| siblings[i]);
+    let after = siblings.get(index).copied();
+    Ok((between(before, after), index as i32))
+}
+
+/// 按给定 ID 顺序排列范围中的元素
+///
+/// 保留已有排序键中最长的递增序列, 只为其余元素生成新键。未列出的元素不受影响。
+pub(crate) fn apply_order(conn: &Connection, scope: RankScope, ids: &[String]) -> Result<(), DbError> {
+    let ranks = scope.ranks(conn)?;
+    let current: Vec<Option<&str>> = ids
+        .iter()
+        .map(|id| {
+            ranks
+                .iter()
+                .find(|(other, _)| other == id)
+                .map(|(_, rank)| rank.as_str())
+        })
+        .collect();
+
+    for (id, (old, new)) in ids.iter().zip(current.iter().zip(reorder(&current))) {
+        if *old != Some(new.as_str()) {
+            scope.set_rank(conn, id, &new)?;
+        }
+    }
+    Ok(())
+}
+
+/// 为按顺序排列的元素分配排序键, 尽量沿用已有的键 (None 表示没有可用的键)
+pub(crate) fn reorder(current: &[Option<&str>]) -> Vec<String> {
+    let keep = longest_increasing(current);
+
+    let mut result: Vec<String> = Vec::with_capacity(current.len());
+    for (i, rank) in current.iter().enumerate() {
+        if keep[i] {
+            result.push(rank.unwrap_or_default().to_string());
+            continue;
+        }
+        let before = result.last().map(|r| r.as_str());
+        let after = (i + 1..current.len()).find(|&j| keep[j]).and_then(|j| current[j]);
+        result.push(between(before, after));
+    }
+    result
+}
+
+/// 标记构成最长严格递增序列的排序键
+fn longest_increasing(ranks: &[Option<&str>]) -> Vec<bool> {
+    let n = ranks.len();
+    let mut length = vec![0usize; n];
+    let mut previous = vec![None; n];
+    for i in 0..n {
+        let Some(rank) = ranks[i].filter(|r| !r.is_empty()) else {
+            continue;
+        };
+        length[i] = 1;
+        for j in 0..i {
+            if length[j] > 0 && ranks[j].is_some_and(|r| r < rank) && length[j] + 1 > length[i] {
+                length[i] = length[j] + 1;
+                previous[i] = Some(j);
+            }
+        }
+    }
+
+    let mut keep = vec![false; n];
+    let mut cursor = (0..n).filter(|&i| length[i] > 0).max_by_key(|&i| length[i]);
+    while let Some(i) = cursor {
+        keep[i] = true;
+        cursor = previous[i];
+    }
+    keep
+}
+
+/// 生成位于 before 与 after 之间的排序键, None 表示列表首/尾
+pub(crate) fn between(before: Option<&str>, after: Option<&str>) -> String {
+    // 键的顺序已损坏时忽略 after, 保证不会出错
+    let after = after.filter(|b| !b.is_empty() && before.is_none_or(|a| a < *b));
+    let a = digits(before.unwrap_or_default());
+    let b = after.map(digits);
+    midpoint(&a, b.as_deref())
+        .into_iter()
+        .map(|d| DIGITS[d as usize] as char)
+        .collect()
+}
+
+/// 为 count 个元素生成均匀分布的排序键
+pub(crate) fn spread(count: usize) -> Vec<String> {
+    let slots = count as u64 + 1;
+    let mut width = 1;
+    let mut space = BASE as u64;
+    while space < slots {
+        width += 1;
+        space *= BASE as u64;
+    }
+
+    let step = space / slots;
+    (1..=count as u64)
+        .map(|i| {
+            let mut value = i * step;
+            let mut key = vec![0u8; width];
+            for digit in key.iter_mut().rev() {
+                *digit = DIGITS[(value % BASE as u64) as usize];
+                value /= BASE as u64;
+            }
+            // 末尾的 0 不影响大小, 去掉后仍可在其前面插入
+            while key.last() == Some(&b'0') {
+                key.pop();
+            }
+            String::from_utf8(key).unwrap_or_default()
+        })
+        .collect()
+}
+
+fn digits(key: &str) -> Vec<u32> {
+    key.bytes()
+        .map(|c| match c {
+            b'0'..=b'9' => (c - b'0') as u32,
+            b'a'..=b'z' => (c - b'a') as u32 + 10,
+            _ => 0,
+        })
+        .collect()
+}
+
+/// 计算 a 与 b 之间的小数 (b 为 None 时视为 1)
+fn midpoint(a: &[u32], b: Option<&[u32]>) -> Vec<u32> {
+    if let Some(b) = b {
+        // 跳过公共前缀, a 不足的位按 0 补齐
+        let n = b
+            .iter()
+            .enumerate()
+            .take_while(|(i, d)| a.get(*i).copied().unwrap_or(0) == **d)
+            .count();
+        if n > 0 {
+            let mut result = b[..n].to_vec();
+            result.extend(midpoint(a.get(n..).unwrap_or_default(), Some(&b[n..])));
+            return result;
+        }
+    }
+
+    let da = a.first().copied().unwrap_or(0);
+    let db = b.and_then(|b| b.first().copied()).unwrap_or(BASE);
+    if db > da + 1 {
+        return vec![(da + db) / 2];
+    }
+    // 首位相邻: b 更长时取其首位即可, 否则在 a 的首位之后继续细分
+    if let Some(b) = b.filter(|b| b.len() > 1) {
+        return vec![b[0]];
+    }
+    let mut result = vec![da];
+    result.extend(midpoint(a.get(1..).unwrap_or_default(), None));
+    result
+}
+
+/// 重新均匀分配过长、为空或重复的排序键, 返回是否有改动
+fn rebalance_scope(conn: &Connection, scope: RankScope) -> Result<bool, DbError> {
+    let ranks = scope.ranks(conn)?;
+    let needs_rebalance = ranks.iter().any(|(_, rank)| rank.is_empty() || rank.len() > MAX_RANK_LEN)
+        || ranks.windows(2).any(|pair| pair[0].1 == pair[1].1);
+    if !needs_rebalance {
+        return Ok(false);
+    }
+
+    for ((id, _), rank) in ranks.iter().zip(spread(ranks.len())) {
+        scope.set_rank(conn, id, &rank)?;
+    }
+    Ok(true)
+}
+
+/// 检查所有排序范围并重新分配需要整理的排序键, 返回整理的范围数
+pub fn rebalance_ranks(app_handle: &AppHandle) -> Result<usize, DbError> {
+    with_transaction(app_handle, rebalance_all)
+}
+
+fn rebalance_all(conn: &Connection) -> Result<usize, DbError> {
+    let mut rebalanced = 0;
+    if rebalance_scope(conn, RankScope::Projects)? {
+        rebalanced += 1;
+    }
+    for (project_id, _) in RankScope::Projects.ranks(conn)? {
+        if rebalance_scope(conn, RankScope::Columns(&project_id))? {
+            rebalanced += 1;
+        }
+        for (column_id, _) in RankScope::Columns(&project_id).ranks(conn)? {
+            if rebalance_scope(conn, RankScope::Cards(&column_id))? {
+                rebalanced += 1;
+            }
+        }
+    }
+
+    if rebalanced > 0 {
+        log::info!("Rebalanced rank keys in {} scopes", rebalanced);
+    }
+    Ok(rebalanced)
+}
@@ -0,0 +1,389 @@
+// 重复卡片相关数据库操作
+// 重复规则使用 RRULE 的子集: FREQ=DAILY|WEEKLY|MONTHLY|YEARLY, INTERVAL, BYDAY (每周),
+// BYMONTHDAY (每月, -1 表示月末) 和 UNTIL。卡片被完成或截止时间已过时,
+// 按规则生成下一张卡片, 开始/截止日期整体平移到下一个周期。
+use super::activities::{record_activity, NewActivity};
+use super::checklists::get_card_checklist_items;
+use super::kanban::{create_card_impl, find_card};
+use super::tags::get_card_tag_ids;
+use super::{with_transaction, DbError};
+use crate::commands::activities::ActivityType;
+use crate::commands::kanban::{Card, CardDate, Recurrence};
+use chrono::{
+    Datelike, Days, Local, Months, NaiveDate, NaiveDateTime,
+    TimeZone, Utc, Weekday,
+};
+use rusqlite::Connection;
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// 向前查找下一个周期时最多尝试的次数, 防止异常规则导致死循环
+const MAX_SEARCH_STEPS: u32 = 3660;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// 解析后的重复规则
+#[derive(Debug, Clone)]
+pub(crate) struct RecurrenceRule {
+    freq: Frequency,
+    interval: u32,
+    by_day: Vec<Weekday>,
+    by_month_day: Option<i32>,
+    until: Option<NaiveDate>,
+}
+
+impl RecurrenceRule {
+    /// 解析 RRULE 字符串, 如 "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH"
+    pub(crate) fn parse(rule: &str) -> Result<Self, DbError> {
+        let invalid = |reason: &str| DbError::Validation(format!("invalid recurrence rule {:?}: {}", rule, reason));
+
+        let body = rule.trim();
+        let body = body.strip_prefix("RRULE:").unwrap_or(body);
+
+        let mut freq = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = None;
+        let mut until = None;
+
+        for part in body.split(';').filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once('=').ok_or_else(|| invalid("expected KEY=VALUE"))?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return Err(invalid("unsupported FREQ")),
+                    });
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|n| (1..=365).contains(n))
+                        .ok_or_else(|| invalid("INTERVAL must be between 1 and 365"))?;
+                }
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        by_day.push(parse_weekday(day).ok_or_else(|| invalid("unknown BYDAY value"))?);
+                    }
+                }
+                "BYMONTHDAY" => {
+                    let day = value
+                        .parse::<i32>()
+                        .ok()
+                        .filter(|d| (1..=31).contains(d) || *d == -1)
+                        .ok_or_else(|| invalid("BYMONTHDAY must be 1-31 or -1"))?;
+                    by_month_day = Some(day);
+                }
+                "UNTIL" => {
+                    let date = value.get(..8).unwrap_or(value);
+                    until = Some(
+                        NaiveDate::parse_from_str(date, "%Y%m%d")
+                            .map_err(|_| invalid("UNTIL must be YYYYMMDD"))?,
+                    );
+                }
+                _ => return Err(invalid("unsupported rule part")),
+            }
+        }
+
+        let freq = freq.ok_or_else(|| invalid("missing FREQ"))?;
+        if !by_day.is_empty() && freq != Frequency::Weekly {
+            return Err(invalid("BYDAY is only supported with FREQ=WEEKLY"));
+        }
+        if by_month_day.is_some() && freq != Frequency::Monthly {
+            return Err(invalid("BYMONTHDAY is only supported with FREQ=MONTHLY"));
+        }
+
+        Ok(Self { freq, interval, by_day, by_month_day, until })
+    }
+
+    /// 计算 anchor 之后的下一个日期, 超过 UNTIL 时返回 None
+    pub(crate) fn next_after(&self, anchor: NaiveDate) -> Option<NaiveDate> {
+        let next = match self.freq {
+            Frequency::Daily => anchor.checked_add_days(Days::new(self.interval as u64)),
+            Frequency::Weekly if self.by_day.is_empty() => {
+                anchor.checked_add_days(Days::new(7 * self.interval as u64))
+            }
+            Frequency::Weekly => {
+                // 只在与 anchor 所在周间隔 INTERVAL 整数倍的周内取 BYDAY 指定的日子
+                let week_start = anchor.week(Weekday::Mon).first_day();
+                (1..=MAX_SEARCH_STEPS).find_map(|offset| {
+                    let day = anchor.checked_add_days(Days::new(offset as u64))?;
+                    let weeks = (day - week_start).num_days() / 7;
+                    let in_week = weeks % self.interval as i64 == 0;
+                    (in_week && self.by_day.contains(&day.weekday())).then_some(day)
+                })
+            }
+            Frequency::Monthly => (0..=MAX_SEARCH_STEPS / 12).find_map(|step| {
+                let month = anchor
+                    .with_day(1)?
+                    .checked_add_months(Months::new(self.interval * step))?;
+                // 日期超出当月天数时取月末
+                let last = last_day_of_month(month);
+                let day = match self.by_month_day {
+                    Some(-1) => last,
+                    Some(day) => (day as u32).min(last),
+                    None => anchor.day().min(last),
+                };
+                month.with_day(day).filter(|candidate| *candidate > anchor)
+            }),
+            Frequency::Yearly => anchor.checked_add_months(Months::new(12 * self.interval)),
+        }?;
+
+        match self.until {
+            Some(until) if next > until => None,
+            _ => Some(next),
+        }
+    }
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value.trim().to_ascii_uppercase().as_str() {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn last_day_of_month(first_day: NaiveDate) -> u32 {
+    first_day
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(28)
+}
+
+/// 卡片日期平移若干天, 保留原有的本地时刻和存储格式
+fn shift_card_date(date: &CardDate, days: i64) -> Option<CardDate> {
+    match date {
+        CardDate::DateTime(dt) => {
+            let local = dt.with_timezone(&Local);
+            let shifted = shift_date(local.date_naive(), days)?;
+            let local = Local
+                .from_local_datetime(&NaiveDateTime::new(shifted, local.time()))
+                .earliest()?;
+            Some(CardDate::DateTime(local.with_timezone(&Utc).fixed_offset()))
+        }
+        CardDate::Date(date) => Some(CardDate::Date(shift_date(*date, days)?)),
+    }
+}
+
+fn shift_date(date: NaiveDate, days: i64) -> Option<NaiveDate> {
+    if days >= 0 {
+        date.checked_add_days(Days::new(days as u64))
+    } else {
+        date.checked_sub_days(Days::new(days.unsigned_abs()))
+    }
+}
+
+/// 校验重复规则及目标列 (目标列必须属于同一项目)
+pub(crate) fn validate_recurrence(
+    conn: &Connection,
+    project_id: &str,
+    recurrence: Option<&Recurrence>,
+) -> Result<(), DbError> {
+    let Some(recurrence) = recurrence else {
+        return Ok(());
+    };
+    RecurrenceRule::parse(&recurrence.rule)?;
+
+    if let Some(column_id) = &recurrence.column_id {
+        if !column_exists(conn, project_id, column_id)? {
+            return Err(DbError::Validation(format!(
+                "column {} does not belong to project {}",
+                column_id, project_id
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 读取卡片的重复设置
+pub(crate) fn get_card_recurrence(conn: &Connection, card_id: &str) -> Result<Option<Recurrence>, DbError> {
+    let (rule, column_id, next_card_id) = conn.query_row(
+        "SELECT recurrence_rule, recurrence_column_id, recurrence_next_id FROM cards WHERE id = ?",
+        [card_id],
+        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    )?;
+    Ok(Recurrence::from_columns(rule, column_id, next_card_id))
+}
+
+/// 为重复卡片生成下一张卡片; 已生成过或规则已结束时返回 None。
+/// 指定 not_before 时跳过该日期之前错过的周期, 避免长期未打开应用后一次补出大量卡片
+pub(crate) fn spawn_next_occurrence(
+    conn: &Connection,
+    project_id: &str,
+    card: &Card,
+    not_before: Option<NaiveDate>,
+) -> Result<Option<Card>, DbError> {
+    let Some(recurrence) = &card.recurrence else {
+        return Ok(None);
+    };
+    if recurrence.next_card_id.is_some() {
+        return Ok(None);
+    }
+    let rule = RecurrenceRule::parse(&recurrence.rule)?;
+
+    // 以截止日期为基准, 没有则用开始日期, 都没有则从今天算起
+    let anchor = card
+        .due_date
+        .as_ref()
+        .or(card.start_date.as_ref())
+        .map(CardDate::local_date)
+        .unwrap_or_else(|| Local::now().date_naive());
+    let Some(mut next) = rule.next_after(anchor) else {
+        return Ok(None);
+    };
+    if let Some(not_before) = not_before {
+        while next < not_before {
+            match rule.next_after(next) {
+                Some(later) => next = later,
+                None => return Ok(None),
+            }
+        }
+    }
+    let days = (next - anchor).num_days();
+
+    let column_id = match recurrence.column_id.as_deref() {
+        Some(column_id) if column_exists(conn, project_id, column_id)? => column_id.to_string(),
+        _ => match first_column(conn, project_id)? {
+            Some(column_id) => column_id,
+            None => return Ok(None),
+        },
+    };
+    // 放到列的末尾
+    let position = conn.query_row(
+        "SELECT COUNT(*) FROM cards WHERE column_id = ? AND archived_at IS NULL",
+        [&column_id],
+        |row| row.get::<_, i32>(0),
+    )?;
+
+    let template = Card {
+        id: String::new(),
+        title: card.title.clone(),
+        description: card.description.clone(),
+        column_id,
+        position,
+        completed: Some(false),
+        priority: card.priority.clone(),
+        tag_ids: Some(get_card_tag_ids(conn, &card.id)?),
+        checklist_progress: None,
+        comment_count: None,
+        recurrence: Some(Recurrence {
+            rule: recurrence.rule.clone(),
+            column_id: recurrence.column_id.clone(),
+            next_card_id: None,
+        }),
+        wip_exceeded: None,
+        start_date: card.start_date.as_ref().and_then(|d| shift_card_date(d, days)),
+        due_date: card.due_date.as_ref().and_then(|d| shift_card_date(d, days)),
+        version: 0,
+        archived_at: None,
+        created_at: String::new(),
+        updated_at: String::new(),
+    };
+    let spawned = create_card_impl(conn, project_id, &template)?;
+
+    // 检查清单随卡片复制, 并重置为未完成
+    let now = Utc::now().to_rfc3339();
+    for item in get_card_checklist_items(conn, &card.id)? {
+        conn.execute(
+            "INSERT INTO checklist_items (id, card_id, text, done, position, created_at, updated_at)
+             VALUES (?, ?, ?, 0, ?, ?, ?)",
+            rusqlite::params![Uuid::new_v4().to_string(), spawned.id, item.text, item.position, now, now],
+        )?;
+    }
+
+    conn.execute(
+        "UPDATE cards SET recurrence_next_id = ? WHERE id = ?",
+        [&spawned.id, &card.id],
+    )?;
+
+    record_activity(
+        conn,
+        &NewActivity::new(project_id, ActivityType::CardUpdated, &card.title)
+            .card(&card.id)
+            .column(&card.column_id)
+            .description(format!("已生成下一次重复卡片 ({})", next.format("%Y-%m-%d"))),
+    )?;
+
+    Ok(find_card(conn, &spawned.id)?.map(|(_, card)| card))
+}
+
+/// 为截止时间已过的重复卡片生成下一张卡片, 返回受影响的项目ID
+pub fn spawn_due_occurrences(app_handle: &AppHandle) -> Result<Vec<String>, DbError> {
+    let now = Local::now();
+    let today = now.date_naive();
+    with_transaction(app_handle, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, due_date FROM cards
+             WHERE recurrence_rule IS NOT NULL
+               AND recurrence_next_id IS NULL
+               AND archived_at IS NULL
+               AND due_date IS NOT NULL"
+        )?;
+        // 纯日期按本地日期比较, 截止当天结束后才视为已过截止时间
+        let card_ids: Vec<String> = stmt
+            .query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, CardDate>(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter(|(_, due)| match due {
+                CardDate::DateTime(dt) => *dt < now,
+                CardDate::Date(date) => *date < today,
+            })
+            .map(|(id, _)| id)
+            .collect();
+
+        let mut project_ids: Vec<String> = Vec::new();
+        for card_id in card_ids {
+            let Some((project_id, card)) = find_card(conn, &card_id)? else {
+                continue;
+            };
+            // 规则在创建后被手工改坏时跳过, 不影响其他卡片
+            match spawn_next_occurrence(conn, &project_id, &card, Some(today)) {
+                Ok(Some(_)) if !project_ids.contains(&project_id) => project_ids.push(project_id),
+                Ok(_) => {}
+                Err(DbError::Validation(e)) => log::warn!("Skipping recurring card {}: {}", card_id, e),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(project_ids)
+    })
+}
+
+fn column_exists(conn: &Connection, project_id: &str, column_id: &str) -> Result<bool, DbError> {
+    let exists = conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM columns WHERE id = ? AND project_id = ? AND archived_at IS NULL)",
+        [column_id, project_id],
+        |row| row.get(0),
+    )?;
+    Ok(exists)
+}
+
+fn first_column(conn: &Connection, project_id: &str) -> Result<Option<String>, DbError> {
+    let result = conn.query_row(
+        "SELECT id FROM columns WHERE project_id = ? AND archived_at IS NULL ORDER BY rank LIMIT 1",
+        [project_id],
+        |row| row.get(0),
+    );
+
+    match result {
+        Ok(column_id) => Ok(Some(column_id)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
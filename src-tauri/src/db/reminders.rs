@@ -0,0 +1,187 @@
+// 到期提醒相关数据库操作
+// 后台任务定期调用 take_due_reminders, 取出已到提醒时间的卡片并记入 reminder_deliveries,
+// 同一张卡片的同一截止时间、同一提前量只提醒一次, 应用重启后也不会重复。
+use super::{with_connection, with_transaction, DbError};
+use crate::commands::kanban::CardDate;
+use crate::commands::reminders::ReminderSettings;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+
+const SETTINGS_KEY: &str = "reminder_settings";
+
+/// 最多允许配置的提前量个数
+const MAX_LEAD_TIMES: usize = 10;
+
+/// 提前量上限: 30 天
+const MAX_LEAD_MINUTES: u32 = 30 * 24 * 60;
+
+/// 待发送的提醒
+#[derive(Debug, Clone)]
+pub struct DueReminder {
+    pub card_id: String,
+    pub card_title: String,
+    pub project_name: String,
+    pub lead_minutes: u32,
+}
+
+/// 已发送的提醒记录, 删除项目时随回收站保存
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReminderDelivery {
+    pub card_id: String,
+    pub due_date: String,
+    pub lead_minutes: u32,
+    pub delivered_at: String,
+}
+
+/// 获取到期提醒设置
+pub fn get_reminder_settings(app_handle: &AppHandle) -> Result<ReminderSettings, DbError> {
+    with_connection(app_handle, get_reminder_settings_impl)
+}
+
+fn get_reminder_settings_impl(conn: &Connection) -> Result<ReminderSettings, DbError> {
+    let result = conn.query_row(
+        "SELECT value FROM settings WHERE key = ?",
+        [SETTINGS_KEY],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(value) => Ok(serde_json::from_str(&value)?),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(ReminderSettings::default()),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
+/// 更新到期提醒设置
+pub fn update_reminder_settings(
+    app_handle: &AppHandle,
+    settings: &ReminderSettings,
+) -> Result<ReminderSettings, DbError> {
+    if settings.lead_minutes.len() > MAX_LEAD_TIMES {
+        return Err(DbError::Validation(format!(
+            "at most {} reminder lead times are allowed",
+            MAX_LEAD_TIMES
+        )));
+    }
+    if let Some(lead) = settings.lead_minutes.iter().find(|m| **m > MAX_LEAD_MINUTES) {
+        return Err(DbError::Validation(format!("reminder lead time {} is too long", lead)));
+    }
+
+    // 去重并从大到小排列
+    let mut lead_minutes = settings.lead_minutes.clone();
+    lead_minutes.sort_unstable_by(|a, b| b.cmp(a));
+    lead_minutes.dedup();
+    let normalized = ReminderSettings {
+        enabled: settings.enabled,
+        lead_minutes,
+    };
+
+    let value = serde_json::to_string(&normalized)?;
+    with_connection(app_handle, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+            [SETTINGS_KEY, value.as_str()],
+        )?;
+        Ok(normalized)
+    })
+}
+
+/// 取出已到提醒时间且尚未提醒过的卡片, 并记录为已提醒
+pub fn take_due_reminders(app_handle: &AppHandle, now: DateTime<Utc>) -> Result<Vec<DueReminder>, DbError> {
+    with_transaction(app_handle, |conn| take_due_reminders_impl(conn, now))
+}
+
+fn take_due_reminders_impl(conn: &Connection, now: DateTime<Utc>) -> Result<Vec<DueReminder>, DbError> {
+    let settings = get_reminder_settings_impl(conn)?;
+    if !settings.enabled || settings.lead_minutes.is_empty() {
+        return Ok(Vec::new());
+    }
+    let max_lead = settings.lead_minutes.iter().copied().max().unwrap_or(0);
+
+    let mut stmt = conn.prepare(
+        "SELECT c.id, c.title, c.due_date, p.name
+         FROM cards c
+         JOIN projects p ON p.id = c.project_id
+         JOIN columns col ON col.id = c.column_id
+         WHERE c.due_date IS NOT NULL AND COALESCE(c.completed, 0) = 0
+           AND c.archived_at IS NULL AND col.archived_at IS NULL AND p.archived_at IS NULL"
+    )?;
+    let cards: Vec<(String, String, CardDate, String)> = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?
+        .collect::<Result<_, _>>()?;
+
+    let mut reminders = Vec::new();
+    for (card_id, card_title, due_date, project_name) in cards {
+        // 纯日期按当天本地零点计算
+        let Some(due_at) = due_date.start_instant() else {
+            continue;
+        };
+        if due_at - Duration::minutes(max_lead as i64) > now {
+            continue;
+        }
+
+        // 已到时间的提前量中, 只提醒最接近截止时间的一个, 其余一并标记为已提醒,
+        // 避免长时间未打开应用后同一张卡片连续弹出多条通知
+        let reached: Vec<u32> = settings
+            .lead_minutes
+            .iter()
+            .copied()
+            .filter(|lead| due_at - Duration::minutes(*lead as i64) <= now)
+            .collect();
+        let mut pending = Vec::new();
+        for lead in reached {
+            let inserted = conn.execute(
+                "INSERT OR IGNORE INTO reminder_deliveries (card_id, due_date, lead_minutes, delivered_at)
+                 VALUES (?, ?, ?, ?)",
+                rusqlite::params![card_id, due_date, lead, now.to_rfc3339()],
+            )?;
+            if inserted > 0 {
+                pending.push(lead);
+            }
+        }
+
+        if let Some(lead_minutes) = pending.into_iter().min() {
+            reminders.push(DueReminder {
+                card_id,
+                card_title,
+                project_name,
+                lead_minutes,
+            });
+        }
+    }
+
+    Ok(reminders)
+}
+
+/// 获取项目卡片的已发送提醒记录
+pub(crate) fn get_project_deliveries(conn: &Connection, project_id: &str) -> Result<Vec<ReminderDelivery>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT card_id, due_date, lead_minutes, delivered_at FROM reminder_deliveries
+         WHERE card_id IN (SELECT id FROM cards WHERE project_id = ?)",
+    )?;
+    let deliveries = stmt
+        .query_map([project_id], |row| {
+            Ok(ReminderDelivery {
+                card_id: row.get(0)?,
+                due_date: row.get(1)?,
+                lead_minutes: row.get(2)?,
+                delivered_at: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(deliveries)
+}
+
+/// 写回已发送提醒记录, 卡片已不存在的记录会被跳过
+pub(crate) fn restore_deliveries(conn: &Connection, deliveries: &[ReminderDelivery]) -> Result<(), DbError> {
+    for delivery in deliveries {
+        conn.execute(
+            "INSERT OR IGNORE INTO reminder_deliveries (card_id, due_date, lead_minutes, delivered_at)
+             SELECT id, ?, ?, ? FROM cards WHERE id = ?",
+            rusqlite::params![delivery.due_date, delivery.lead_minutes, delivery.delivered_at, delivery.card_id],
+        )?;
+    }
+    Ok(())
+}
@@ -1,194 +1,777 @@
 // 数据库 Schema 定义和迁移
-use rusqlite::Connection;
+use super::fields::normalize_date;
+use super::{table_exists, DbError};
+use crate::commands::priorities::PriorityScale;
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
+
+/// 迁移中的数据整理步骤
+type DataMigration = fn(&Connection) -> Result<(), DbError>;
+
+/// 单个 Schema 迁移
+struct Migration {
+    version: i32,
+    description: &'static str,
+    sql: &'static str,
+    /// SQL 之后在同一事务中执行的数据整理 (无法用 SQL 表达的转换), 不计入校验和
+    data: Option<DataMigration>,
+}
+
+impl Migration {
+    /// 迁移 SQL 的校验和, 用于发现已执行的迁移在之后被修改
+    fn checksum(&self) -> String {
+        format!("{:x}", Sha256::digest(self.sql.as_bytes()))
+    }
+}
+
+/// 全部迁移, 按版本升序排列
+///
+/// 已发布的迁移不能再修改, Schema 变更须追加新版本。
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "创建基础表",
+        sql: MIGRATION_V1,
+        data: None,
+    },
+    Migration {
+        version: 2,
+        description: "添加 priority, start_date, due_date 字段",
+        sql: MIGRATION_V2,
+        data: None,
+    },
+    Migration {
+        version: 3,
+        description: "为项目添加排序字段",
+        sql: MIGRATION_V3,
+        data: None,
+    },
+    Migration {
+        version: 4,
+        description: "为活动日志添加分页查询索引",
+        sql: MIGRATION_V4,
+        data: None,
+    },
+    Migration {
+        version: 5,
+        description: "创建撤销/重做历史表",
+        sql: MIGRATION_V5,
+        data: None,
+    },
+    Migration {
+        version: 6,
+        description: "创建卡片全文索引 (FTS5) 及同步触发器",
+        sql: MIGRATION_V6,
+        data: None,
+    },
+    Migration {
+        version: 7,
+        description: "创建标签表及卡片标签关联表",
+        sql: MIGRATION_V7,
+        data: None,
+    },
+    Migration {
+        version: 8,
+        description: "创建卡片检查清单表",
+        sql: MIGRATION_V8,
+        data: None,
+    },
+    Migration {
+        version: 9,
+        description: "创建卡片评论表",
+        sql: MIGRATION_V9,
+        data: None,
+    },
+    Migration {
+        version: 10,
+        description: "创建卡片附件表 (文件内容存放在附件库中, 按哈希引用)",
+        sql: MIGRATION_V10,
+        data: None,
+    },
+    Migration {
+        version: 11,
+        description: "卡片增加重复规则字段",
+        sql: MIGRATION_V11,
+        data: None,
+    },
+    Migration {
+        version: 12,
+        description: "创建到期提醒发送记录表",
+        sql: MIGRATION_V12,
+        data: None,
+    },
+    Migration {
+        version: 13,
+        description: "项目、列和卡片改用字符串排序键",
+        sql: MIGRATION_V13,
+        data: None,
+    },
+    Migration {
+        version: 14,
+        description: "项目、列和卡片增加版本号",
+        sql: MIGRATION_V14,
+        data: None,
+    },
+    Migration {
+        version: 15,
+        description: "列增加在制品上限",
+        sql: MIGRATION_V15,
+        data: None,
+    },
+    Migration {
+        version: 16,
+        description: "项目、列和卡片支持归档",
+        sql: MIGRATION_V16,
+        data: None,
+    },
+    Migration {
+        version: 17,
+        description: "增加回收站",
+        sql: MIGRATION_V17,
+        data: None,
+    },
+    Migration {
+        version: 18,
+        description: "记录卡片在列之间的流转",
+        sql: MIGRATION_V18,
+        data: None,
+    },
+    Migration {
+        version: 19,
+        description: "记录每日各列卡片数",
+        sql: MIGRATION_V19,
+        data: None,
+    },
+    Migration {
+        version: 20,
+        description: "整理卡片优先级和日期",
+        sql: MIGRATION_V20,
+        data: Some(normalize_card_fields),
+    },
+];
+
+/// 当前应用支持的最新 Schema 版本
+pub fn latest_version() -> i32 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// 读取数据库的 Schema 版本, 新建的数据库为 0
+pub fn current_version(conn: &Connection) -> Result<i32, DbError> {
+    if !table_exists(conn, "schema_version")? {
+        return Ok(0);
+    }
+    let version = conn.query_row(
+        "SELECT COALESCE(MAX(version), 0) FROM schema_version",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(version)
+}
 
 /// 执行数据库迁移
-pub fn run_migrations(conn: &Connection) -> Result<(), rusqlite::Error> {
+///
+/// 每个迁移与其版本记录在同一事务中提交, 失败时整体回滚。
+/// 数据库来自更新版本的应用时拒绝打开, 以免旧代码写坏新 Schema。
+pub fn run_migrations(conn: &Connection) -> Result<(), DbError> {
     // 启用外键约束
     conn.execute_batch("PRAGMA foreign_keys = ON;")?;
 
-    // 创建版本表
-    conn.execute(
+    ensure_version_table(conn)?;
+
+    let current_version = current_version(conn)?;
+    log::info!("Current database version: {}", current_version);
+
+    let latest_version = latest_version();
+    if current_version > latest_version {
+        return Err(DbError::UnsupportedSchema {
+            found: current_version,
+            supported: latest_version,
+        });
+    }
+
+    verify_checksums(conn)?;
+
+    // 按顺序执行迁移
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current_version) {
+        apply_migration(conn, migration)?;
+    }
+
+    Ok(())
+}
+
+/// 创建版本表; 旧版本表只有 version 列, 补充迁移说明, 校验和及执行时间
+fn ensure_version_table(conn: &Connection) -> Result<(), DbError> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
         "CREATE TABLE IF NOT EXISTS schema_version (
-            version INTEGER PRIMARY KEY
+            version INTEGER PRIMARY KEY,
+            description TEXT,
+            checksum TEXT,
+            applied_at TEXT
         )",
         [],
     )?;
 
-    // 获取当前版本
-    let current_version: i32 = conn
-        .query_row(
-            "SELECT COALESCE(MAX(version), 0) FROM schema_version",
-            [],
+    for column in ["description", "checksum", "applied_at"] {
+        let exists: i64 = tx.query_row(
+            "SELECT COUNT(*) FROM pragma_table_info('schema_version') WHERE name = ?",
+            [column],
             |row| row.get(0),
-        )
-        .unwrap_or(0);
+        )?;
+        if exists == 0 {
+            tx.execute(&format!("ALTER TABLE schema_version ADD COLUMN {} TEXT", column), [])?;
+        }
+    }
 
-    log::info!("Current database version: {}", current_version);
+    tx.commit()?;
+    Ok(())
+}
 
-    // 按顺序执行迁移
-    if current_version < 1 {
-        migrate_v1(conn)?;
-    }
-    if current_version < 2 {
-        migrate_v2(conn)?;
-    }
-    if current_version < 3 {
-        migrate_v3(conn)?;
+/// 校验已执行迁移的校验和
+///
+/// 旧版本应用执行的迁移没有校验和, 按当前定义补记; 不一致时只记录警告, 不阻止启动。
+fn verify_checksums(conn: &Connection) -> Result<(), DbError> {
+    let applied: Vec<(i32, Option<String>)> = {
+        let mut stmt = conn.prepare("SELECT version, checksum FROM schema_version ORDER BY version")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
+
+    for (version, checksum) in applied {
+        let Some(migration) = MIGRATIONS.iter().find(|m| m.version == version) else {
+            continue;
+        };
+        match checksum {
+            None => {
+                conn.execute(
+                    "UPDATE schema_version SET description = ?1, checksum = ?2 WHERE version = ?3",
+                    params![migration.description, migration.checksum(), version],
+                )?;
+            }
+            Some(checksum) if checksum != migration.checksum() => {
+                log::warn!(
+                    "Checksum mismatch for migration V{}: recorded {}, expected {}",
+                    version,
+                    checksum,
+                    migration.checksum()
+                );
+            }
+            Some(_) => {}
+        }
     }
 
     Ok(())
 }
 
-/// V3 迁移: 为项目添加排序字段
-fn migrate_v3(conn: &Connection) -> Result<(), rusqlite::Error> {
-    log::info!("Running migration V3...");
+/// 在事务中执行单个迁移并记录版本
+fn apply_migration(conn: &Connection, migration: &Migration) -> Result<(), DbError> {
+    log::info!("Running migration V{}...", migration.version);
 
-    conn.execute(
-        "ALTER TABLE projects ADD COLUMN position INTEGER DEFAULT 0",
-        [],
+    let tx = conn.unchecked_transaction()?;
+    tx.execute_batch(migration.sql)?;
+    if let Some(data) = migration.data {
+        data(&tx)?;
+    }
+    tx.execute(
+        "INSERT INTO schema_version (version, description, checksum, applied_at)
+         VALUES (?1, ?2, ?3, ?4)",
+        params![
+            migration.version,
+            migration.description,
+            migration.checksum(),
+            Utc::now().to_rfc3339(),
+        ],
     )?;
+    tx.commit()?;
 
-    conn.execute(
-        "UPDATE projects
-         SET position = (
-             SELECT COUNT(*)
-             FROM projects AS newer_projects
-             WHERE newer_projects.created_at > projects.created_at
-         )",
-        [],
-    )?;
+    log::info!("Migration V{} completed", migration.version);
+    Ok(())
+}
 
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_projects_position ON projects(position)",
-        [],
-    )?;
+const MIGRATION_V20: &str = "
+    CREATE TABLE migration_issues (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        version INTEGER NOT NULL,
+        table_name TEXT NOT NULL,
+        row_id TEXT NOT NULL,
+        field TEXT NOT NULL,
+        value TEXT NOT NULL,
+        reported_at TEXT NOT NULL
+    );
+";
 
-    conn.execute("INSERT INTO schema_version (version) VALUES (3)", [])?;
+/// V20: 卡片 (含撤销历史和回收站快照中的卡片) 的优先级统一为 low/normal/urgent/critical,
+/// 日期统一为 RFC 3339 或 YYYY-MM-DD; 无法识别的值清空, 原值记录到 migration_issues。
+/// 同时写入默认优先级刻度。
+fn normalize_card_fields(conn: &Connection) -> Result<(), DbError> {
+    type Row = (String, Option<String>, Option<String>, Option<String>);
+    let rows: Vec<Row> = {
+        let mut stmt = conn.prepare("SELECT id, priority, start_date, due_date FROM cards")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))?;
+        rows.filter_map(|r| r.ok()).collect()
+    };
 
-    log::info!("Migration V3 completed");
-    Ok(())
-}
+    let now = Utc::now().to_rfc3339();
+    let report = |card_id: &str, field: &str, value: &str| -> Result<(), DbError> {
+        log::warn!("Cleared unparseable {} {:?} on card {}", field, value, card_id);
+        conn.execute(
+            "INSERT INTO migration_issues (version, table_name, row_id, field, value, reported_at)
+             VALUES (20, 'cards', ?, ?, ?, ?)",
+            params![card_id, field, value, now],
+        )?;
+        Ok(())
+    };
 
-/// V1 迁移: 创建基础表
-fn migrate_v1(conn: &Connection) -> Result<(), rusqlite::Error> {
-    log::info!("Running migration V1...");
+    let mut reported = 0;
+    for (card_id, priority, start_date, due_date) in rows {
+        let mut normalized = Vec::new();
+        for (field, value, result) in [
+            ("priority", &priority, normalize_field(&priority, priority_value)),
+            ("start_date", &start_date, normalize_field(&start_date, normalize_date)),
+            ("due_date", &due_date, normalize_field(&due_date, normalize_date)),
+        ] {
+            match result {
+                Some(value) => normalized.push(value),
+                None => {
+                    report(&card_id, field, value.as_deref().unwrap_or_default())?;
+                    reported += 1;
+                    normalized.push(None);
+                }
+            }
+        }
+        if normalized == [priority, start_date.clone(), due_date.clone()] {
+            continue;
+        }
 
-    // 项目表
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS projects (
-            id TEXT PRIMARY KEY,
-            name TEXT NOT NULL,
-            description TEXT,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL
-        )",
-        [],
-    )?;
+        conn.execute(
+            "UPDATE cards SET priority = ?, start_date = ?, due_date = ? WHERE id = ?",
+            params![normalized[0], normalized[1], normalized[2], card_id],
+        )?;
+        // 已发送的提醒按截止日期字符串去重, 随之更新以免重复提醒
+        if let (Some(old), Some(new)) = (&due_date, &normalized[2]) {
+            conn.execute(
+                "UPDATE OR IGNORE reminder_deliveries SET due_date = ? WHERE card_id = ? AND due_date = ?",
+                params![new, card_id, old],
+            )?;
+        }
+    }
 
-    // 列表
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS columns (
-            id TEXT PRIMARY KEY,
-            project_id TEXT NOT NULL,
-            title TEXT NOT NULL,
-            position INTEGER NOT NULL,
-            background_color TEXT,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL,
-            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
+    reported += normalize_stored_cards(conn, &now)?;
+    if reported > 0 {
+        log::warn!("Cleared {} unparseable card field values, see migration_issues", reported);
+    }
 
-    // 卡片表
     conn.execute(
-        "CREATE TABLE IF NOT EXISTS cards (
-            id TEXT PRIMARY KEY,
-            project_id TEXT NOT NULL,
-            column_id TEXT NOT NULL,
-            title TEXT NOT NULL,
-            description TEXT,
-            position INTEGER NOT NULL,
-            completed INTEGER DEFAULT 0,
-            created_at TEXT NOT NULL,
-            updated_at TEXT NOT NULL,
-            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
-            FOREIGN KEY (column_id) REFERENCES columns(id) ON DELETE CASCADE
-        )",
-        [],
+        "INSERT OR IGNORE INTO settings (key, value) VALUES ('priority_scale', ?)",
+        [serde_json::to_string(&PriorityScale::default())?],
     )?;
+    Ok(())
+}
 
-    // 活动日志表
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS activities (
-            id TEXT PRIMARY KEY,
-            project_id TEXT NOT NULL,
-            type TEXT NOT NULL,
-            card_id TEXT,
-            column_id TEXT,
-            from_column_id TEXT,
-            to_column_id TEXT,
-            title TEXT NOT NULL,
-            description TEXT,
-            timestamp TEXT NOT NULL,
-            FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
-        )",
-        [],
-    )?;
+/// 撤销历史和回收站快照中的卡片按同样的规则整理, 否则旧快照在撤销或恢复时无法解析;
+/// 返回清空的值的个数
+fn normalize_stored_cards(conn: &Connection, now: &str) -> Result<usize, DbError> {
+    let mut reported = 0;
+    for (table, column) in [("undo_history", "undo_ops"), ("undo_history", "redo_ops"), ("trash", "restore_ops")] {
+        let rows: Vec<(String, String)> = {
+            let mut stmt = conn.prepare(&format!("SELECT CAST(id AS TEXT), {} FROM {}", column, table))?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+        for (id, json) in rows {
+            let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&json) else {
+                log::warn!("Skipped unreadable {}.{} row {}", table, column, id);
+                continue;
+            };
+            let mut issues = Vec::new();
+            if !normalize_json_cards(&mut value, &mut issues) {
+                continue;
+            }
+            for (field, raw) in &issues {
+                log::warn!("Cleared unparseable {} {:?} in {}.{} row {}", field, raw, table, column, id);
+                conn.execute(
+                    "INSERT INTO migration_issues (version, table_name, row_id, field, value, reported_at)
+                     VALUES (20, ?, ?, ?, ?, ?)",
+                    params![table, id, format!("{}.{}", column, field), raw, now],
+                )?;
+            }
+            reported += issues.len();
+            conn.execute(
+                &format!("UPDATE {} SET {} = ? WHERE id = ?", table, column),
+                params![value.to_string(), id],
+            )?;
+        }
+    }
 
-    // 设置表
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS settings (
-            key TEXT PRIMARY KEY,
-            value TEXT NOT NULL
-        )",
-        [],
-    )?;
+    Ok(reported)
+}
 
-    // 创建索引
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_cards_project_id ON cards(project_id)",
-        [],
-    )?;
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_cards_column_id ON cards(column_id)",
-        [],
-    )?;
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_columns_project_id ON columns(project_id)",
-        [],
-    )?;
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_activities_project_id ON activities(project_id)",
-        [],
-    )?;
+/// 整理 JSON 中所有卡片对象 (带 columnId 和 priority 的对象) 的字段, 返回是否有修改
+fn normalize_json_cards(value: &mut serde_json::Value, issues: &mut Vec<(&'static str, String)>) -> bool {
+    let mut changed = false;
+    match value {
+        serde_json::Value::Array(items) => {
+            for item in items {
+                changed |= normalize_json_cards(item, issues);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            if map.contains_key("columnId") && map.contains_key("priority") {
+                for (key, parse) in [
+                    ("priority", priority_value as fn(&str) -> Result<Option<String>, String>),
+                    ("startDate", date_value),
+                    ("dueDate", date_value),
+                ] {
+                    let Some(serde_json::Value::String(raw)) = map.get(key) else {
+                        continue;
+                    };
+                    let raw = raw.clone();
+                    let normalized = match normalize_field(&Some(raw.clone()), parse) {
+                        Some(normalized) => normalized,
+                        None => {
+                            issues.push((key, raw.clone()));
+                            None
+                        }
+                    };
+                    if normalized.as_deref() != Some(raw.as_str()) {
+                        map.insert(key.to_string(), normalized.map_or(serde_json::Value::Null, serde_json::Value::String));
+                        changed = true;
+                    }
+                }
+            }
+            for item in map.values_mut() {
+                changed |= normalize_json_cards(item, issues);
+            }
+        }
+        _ => {}
+    }
+    changed
+}
 
-    // 更新版本
-    conn.execute("INSERT INTO schema_version (version) VALUES (1)", [])?;
+/// V20 按默认刻度 (low/normal/urgent/critical) 整理优先级, 与当前刻度无关
+fn priority_value(value: &str) -> Result<Option<String>, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    PriorityScale::default()
+        .find(value)
+        .map(|level| Some(level.value.clone()))
+        .ok_or_else(|| format!("无法识别的优先级: {}", value))
+}
 
-    log::info!("Migration V1 completed");
-    Ok(())
+fn date_value(value: &str) -> Result<Option<String>, String> {
+    normalize_date(value).map(|d| d.map(|d| d.to_string()))
 }
 
-/// V2 迁移: 添加 priority, start_date, due_date 字段
-fn migrate_v2(conn: &Connection) -> Result<(), rusqlite::Error> {
-    log::info!("Running migration V2...");
+/// 按规范格式整理单个字段; 无法识别时返回 None
+fn normalize_field<T: ToString>(
+    value: &Option<String>,
+    parse: fn(&str) -> Result<Option<T>, String>,
+) -> Option<Option<String>> {
+    match value {
+        Some(value) => parse(value).ok().map(|parsed| parsed.map(|p| p.to_string())),
+        None => Some(None),
+    }
+}
 
-    // 添加 priority 字段
-    conn.execute(
-        "ALTER TABLE cards ADD COLUMN priority TEXT DEFAULT 'low'",
-        [],
-    )?;
+const MIGRATION_V19: &str = "
+    CREATE TABLE column_flow_snapshots (
+        project_id TEXT NOT NULL,
+        column_id TEXT NOT NULL,
+        day TEXT NOT NULL,
+        card_count INTEGER NOT NULL,
+        PRIMARY KEY (project_id, column_id, day),
+        FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+    );
+";
 
-    // 添加 start_date 字段
-    conn.execute("ALTER TABLE cards ADD COLUMN start_date TEXT", [])?;
+const MIGRATION_V18: &str = "
+    CREATE TABLE card_transitions (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        project_id TEXT NOT NULL,
+        card_id TEXT NOT NULL,
+        from_column_id TEXT,
+        to_column_id TEXT NOT NULL,
+        transitioned_at TEXT NOT NULL,
+        FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+    );
+    CREATE INDEX idx_card_transitions_project ON card_transitions(project_id, card_id, transitioned_at);
+    -- 已有卡片以创建时间作为进入当前列的时间
+    INSERT INTO card_transitions (project_id, card_id, from_column_id, to_column_id, transitioned_at)
+    SELECT project_id, id, NULL, column_id, created_at FROM cards;
+";
 
-    // 添加 due_date 字段
-    conn.execute("ALTER TABLE cards ADD COLUMN due_date TEXT", [])?;
+const MIGRATION_V17: &str = "
+    CREATE TABLE trash (
+        id TEXT PRIMARY KEY,
+        kind TEXT NOT NULL CHECK (kind IN ('card', 'column', 'project')),
+        entity_id TEXT NOT NULL,
+        project_id TEXT NOT NULL,
+        project_name TEXT NOT NULL,
+        title TEXT NOT NULL,
+        restore_ops TEXT NOT NULL,
+        deleted_at TEXT NOT NULL
+    );
+    CREATE INDEX idx_trash_project_id ON trash(project_id);
+    CREATE INDEX idx_trash_deleted_at ON trash(deleted_at);
+";
 
-    // 更新版本
-    conn.execute("INSERT INTO schema_version (version) VALUES (2)", [])?;
+const MIGRATION_V16: &str = "
+    ALTER TABLE projects ADD COLUMN archived_at TEXT;
+    ALTER TABLE columns ADD COLUMN archived_at TEXT;
+    ALTER TABLE cards ADD COLUMN archived_at TEXT;
+    CREATE INDEX idx_projects_archived_at ON projects(archived_at) WHERE archived_at IS NOT NULL;
+    CREATE INDEX idx_columns_archived_at ON columns(archived_at) WHERE archived_at IS NOT NULL;
+    CREATE INDEX idx_cards_archived_at ON cards(archived_at) WHERE archived_at IS NOT NULL;
+";
 
-    log::info!("Migration V2 completed");
-    Ok(())
-}
+const MIGRATION_V15: &str = "
+    ALTER TABLE columns ADD COLUMN wip_limit INTEGER CHECK (wip_limit IS NULL OR wip_limit > 0);
+    ALTER TABLE columns ADD COLUMN wip_mode TEXT NOT NULL DEFAULT 'soft' CHECK (wip_mode IN ('soft', 'hard'));
+";
+
+const MIGRATION_V14: &str = "
+    ALTER TABLE projects ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+    ALTER TABLE columns ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+    ALTER TABLE cards ADD COLUMN version INTEGER NOT NULL DEFAULT 1;
+";
+
+const MIGRATION_V13: &str = "
+    ALTER TABLE projects ADD COLUMN rank TEXT NOT NULL DEFAULT '';
+    ALTER TABLE columns ADD COLUMN rank TEXT NOT NULL DEFAULT '';
+    ALTER TABLE cards ADD COLUMN rank TEXT NOT NULL DEFAULT '';
+    -- 按原有顺序生成等宽排序键, 以非 0 字符结尾以便在任意两项之间插入
+    UPDATE projects SET rank = (
+        SELECT printf('%06di', ordered.n)
+        FROM (SELECT id, ROW_NUMBER() OVER (ORDER BY position, created_at DESC) AS n FROM projects) AS ordered
+        WHERE ordered.id = projects.id
+    );
+    UPDATE columns SET rank = (
+        SELECT printf('%06di', ordered.n)
+        FROM (SELECT id, ROW_NUMBER() OVER (PARTITION BY project_id ORDER BY position, created_at) AS n FROM columns) AS ordered
+        WHERE ordered.id = columns.id
+    );
+    UPDATE cards SET rank = (
+        SELECT printf('%06di', ordered.n)
+        FROM (SELECT id, ROW_NUMBER() OVER (PARTITION BY column_id ORDER BY position, rowid) AS n FROM cards) AS ordered
+        WHERE ordered.id = cards.id
+    );
+    -- 序号改为按排序键计算, 删除原有的整数位置
+    DROP INDEX IF EXISTS idx_projects_position;
+    ALTER TABLE projects DROP COLUMN position;
+    ALTER TABLE columns DROP COLUMN position;
+    ALTER TABLE cards DROP COLUMN position;
+    CREATE INDEX IF NOT EXISTS idx_projects_rank ON projects(rank);
+    CREATE INDEX IF NOT EXISTS idx_columns_project_rank ON columns(project_id, rank);
+    CREATE INDEX IF NOT EXISTS idx_cards_column_rank ON cards(column_id, rank);
+";
+
+const MIGRATION_V12: &str = "
+    -- 截止日期作为主键的一部分, 修改截止日期后会重新提醒
+    CREATE TABLE IF NOT EXISTS reminder_deliveries (
+        card_id TEXT NOT NULL,
+        due_date TEXT NOT NULL,
+        lead_minutes INTEGER NOT NULL,
+        delivered_at TEXT NOT NULL,
+        PRIMARY KEY (card_id, due_date, lead_minutes),
+        FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+    );
+";
+
+const MIGRATION_V11: &str = "
+    ALTER TABLE cards ADD COLUMN recurrence_rule TEXT;
+    ALTER TABLE cards ADD COLUMN recurrence_column_id TEXT;
+    ALTER TABLE cards ADD COLUMN recurrence_next_id TEXT;
+    CREATE INDEX IF NOT EXISTS idx_cards_recurrence ON cards(recurrence_rule) WHERE recurrence_rule IS NOT NULL;
+";
+
+const MIGRATION_V10: &str = "
+    CREATE TABLE IF NOT EXISTS attachments (
+        id TEXT PRIMARY KEY,
+        card_id TEXT NOT NULL,
+        blob_hash TEXT NOT NULL,
+        file_name TEXT NOT NULL,
+        size INTEGER NOT NULL,
+        created_at TEXT NOT NULL,
+        FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+    );
+    CREATE INDEX IF NOT EXISTS idx_attachments_card_id ON attachments(card_id);
+    CREATE INDEX IF NOT EXISTS idx_attachments_blob_hash ON attachments(blob_hash);
+";
+
+const MIGRATION_V9: &str = "
+    CREATE TABLE IF NOT EXISTS comments (
+        id TEXT PRIMARY KEY,
+        card_id TEXT NOT NULL,
+        body TEXT NOT NULL,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+    );
+    CREATE INDEX IF NOT EXISTS idx_comments_card_id ON comments(card_id, created_at);
+";
+
+const MIGRATION_V8: &str = "
+    CREATE TABLE IF NOT EXISTS checklist_items (
+        id TEXT PRIMARY KEY,
+        card_id TEXT NOT NULL,
+        text TEXT NOT NULL,
+        done INTEGER NOT NULL DEFAULT 0,
+        position INTEGER NOT NULL DEFAULT 0,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE
+    );
+    CREATE INDEX IF NOT EXISTS idx_checklist_items_card_id ON checklist_items(card_id, position);
+";
+
+const MIGRATION_V7: &str = "
+    CREATE TABLE IF NOT EXISTS tags (
+        id TEXT PRIMARY KEY,
+        project_id TEXT NOT NULL,
+        name TEXT NOT NULL,
+        color TEXT NOT NULL,
+        position INTEGER NOT NULL DEFAULT 0,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        UNIQUE (project_id, name),
+        FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+    );
+    CREATE TABLE IF NOT EXISTS card_tags (
+        card_id TEXT NOT NULL,
+        tag_id TEXT NOT NULL,
+        PRIMARY KEY (card_id, tag_id),
+        FOREIGN KEY (card_id) REFERENCES cards(id) ON DELETE CASCADE,
+        FOREIGN KEY (tag_id) REFERENCES tags(id) ON DELETE CASCADE
+    );
+    CREATE INDEX IF NOT EXISTS idx_tags_project_id ON tags(project_id);
+    CREATE INDEX IF NOT EXISTS idx_card_tags_tag_id ON card_tags(tag_id);
+";
+
+const MIGRATION_V6: &str = "
+    -- trigram 分词器支持中文等无空格文本的子串匹配
+    CREATE VIRTUAL TABLE IF NOT EXISTS cards_fts USING fts5(
+        title,
+        description,
+        content = 'cards',
+        content_rowid = 'rowid',
+        tokenize = 'trigram'
+    );
+    CREATE TRIGGER IF NOT EXISTS cards_fts_after_insert AFTER INSERT ON cards BEGIN
+        INSERT INTO cards_fts (rowid, title, description)
+        VALUES (new.rowid, new.title, new.description);
+    END;
+    CREATE TRIGGER IF NOT EXISTS cards_fts_after_delete AFTER DELETE ON cards BEGIN
+        INSERT INTO cards_fts (cards_fts, rowid, title, description)
+        VALUES ('delete', old.rowid, old.title, old.description);
+    END;
+    CREATE TRIGGER IF NOT EXISTS cards_fts_after_update AFTER UPDATE OF title, description ON cards BEGIN
+        INSERT INTO cards_fts (cards_fts, rowid, title, description)
+        VALUES ('delete', old.rowid, old.title, old.description);
+        INSERT INTO cards_fts (rowid, title, description)
+        VALUES (new.rowid, new.title, new.description);
+    END;
+    -- 为已有卡片建立索引
+    INSERT INTO cards_fts (cards_fts) VALUES ('rebuild');
+";
+
+const MIGRATION_V5: &str = "
+    -- scope 为项目ID或项目列表作用域, 不设外键以便撤销项目删除
+    CREATE TABLE IF NOT EXISTS undo_history (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        scope TEXT NOT NULL,
+        label TEXT NOT NULL,
+        undo_ops TEXT NOT NULL,
+        redo_ops TEXT NOT NULL,
+        undone INTEGER NOT NULL DEFAULT 0,
+        created_at TEXT NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS idx_undo_history_scope ON undo_history(scope, id);
+";
+
+const MIGRATION_V4: &str = "
+    CREATE INDEX IF NOT EXISTS idx_activities_project_timestamp ON activities(project_id, timestamp);
+    CREATE INDEX IF NOT EXISTS idx_activities_card_timestamp ON activities(card_id, timestamp);
+";
+
+const MIGRATION_V3: &str = "
+    ALTER TABLE projects ADD COLUMN position INTEGER DEFAULT 0;
+    UPDATE projects
+    SET position = (
+        SELECT COUNT(*)
+        FROM projects AS newer_projects
+        WHERE newer_projects.created_at > projects.created_at
+    );
+    CREATE INDEX IF NOT EXISTS idx_projects_position ON projects(position);
+";
+
+const MIGRATION_V2: &str = "
+    -- 添加 priority 字段
+    ALTER TABLE cards ADD COLUMN priority TEXT DEFAULT 'low';
+    -- 添加 start_date 字段
+    ALTER TABLE cards ADD COLUMN start_date TEXT;
+    -- 添加 due_date 字段
+    ALTER TABLE cards ADD COLUMN due_date TEXT;
+";
+
+const MIGRATION_V1: &str = "
+    -- 项目表
+    CREATE TABLE IF NOT EXISTS projects (
+        id TEXT PRIMARY KEY,
+        name TEXT NOT NULL,
+        description TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL
+    );
+    -- 列表
+    CREATE TABLE IF NOT EXISTS columns (
+        id TEXT PRIMARY KEY,
+        project_id TEXT NOT NULL,
+        title TEXT NOT NULL,
+        position INTEGER NOT NULL,
+        background_color TEXT,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+    );
+    -- 卡片表
+    CREATE TABLE IF NOT EXISTS cards (
+        id TEXT PRIMARY KEY,
+        project_id TEXT NOT NULL,
+        column_id TEXT NOT NULL,
+        title TEXT NOT NULL,
+        description TEXT,
+        position INTEGER NOT NULL,
+        completed INTEGER DEFAULT 0,
+        created_at TEXT NOT NULL,
+        updated_at TEXT NOT NULL,
+        FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE,
+        FOREIGN KEY (column_id) REFERENCES columns(id) ON DELETE CASCADE
+    );
+    -- 活动日志表
+    CREATE TABLE IF NOT EXISTS activities (
+        id TEXT PRIMARY KEY,
+        project_id TEXT NOT NULL,
+        type TEXT NOT NULL,
+        card_id TEXT,
+        column_id TEXT,
+        from_column_id TEXT,
+        to_column_id TEXT,
+        title TEXT NOT NULL,
+        description TEXT,
+        timestamp TEXT NOT NULL,
+        FOREIGN KEY (project_id) REFERENCES projects(id) ON DELETE CASCADE
+    );
+    -- 设置表
+    CREATE TABLE IF NOT EXISTS settings (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );
+    -- 创建索引
+    CREATE INDEX IF NOT EXISTS idx_cards_project_id ON cards(project_id);
+    CREATE INDEX IF NOT EXISTS idx_cards_column_id ON cards(column_id);
+    CREATE INDEX IF NOT EXISTS idx_columns_project_id ON columns(project_id);
+    CREATE INDEX IF NOT EXISTS idx_activities_project_id ON activities(project_id);
+";
@@ -0,0 +1,312 @@
+// 卡片全文搜索相关数据库操作
+use super::checklists::get_checklist_progress;
+use super::comments::get_comment_count;
+use super::priorities::current_levels_from;
+use super::recurrence::get_card_recurrence;
+use super::tags::get_card_tag_ids;
+use super::{with_connection, DbError};
+use crate::commands::kanban::{Card, CardDate};
+use crate::commands::search::{CardSearchResult, SearchCardsParams};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rusqlite::types::ToSql;
+use rusqlite::Connection;
+use tauri::AppHandle;
+
+const HIGHLIGHT_OPEN: &str = "<mark>";
+const HIGHLIGHT_CLOSE: &str = "</mark>";
+/// 命中部分先用控制字符标记, 转义 HTML 后再替换为 `<mark>`, 避免卡片内容被当作 HTML
+const MATCH_OPEN: &str = "\u{1}";
+const MATCH_CLOSE: &str = "\u{2}";
+const SNIPPET_ELLIPSIS: &str = "…";
+
+/// trigram 分词器只能匹配至少 3 个字符的词, 更短的词退回 LIKE 匹配
+const MIN_FTS_TERM_CHARS: usize = 3;
+
+const DEFAULT_SEARCH_LIMIT: u32 = 50;
+const MAX_SEARCH_LIMIT: u32 = 200;
+
+/// 跨项目搜索卡片
+pub fn search_cards(
+    app_handle: &AppHandle,
+    params: &SearchCardsParams,
+) -> Result<Vec<CardSearchResult>, DbError> {
+    with_connection(app_handle, |conn| search_cards_impl(conn, params))
+}
+
+fn search_cards_impl(
+    conn: &Connection,
+    params: &SearchCardsParams,
+) -> Result<Vec<CardSearchResult>, DbError> {
+    let terms: Vec<&str> = params.query.split_whitespace().collect();
+    if terms.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let (fts_terms, like_terms): (Vec<&str>, Vec<&str>) = terms
+        .iter()
+        .partition(|term| term.chars().count() >= MIN_FTS_TERM_CHARS);
+    let use_fts = !fts_terms.is_empty();
+
+    // 已归档的卡片, 以及已归档列或项目中的卡片不参与搜索
+    let mut conditions = vec![
+        "c.archived_at IS NULL AND col.archived_at IS NULL AND p.archived_at IS NULL".to_string(),
+    ];
+    let mut sql_params: Vec<Box<dyn ToSql>> = Vec::new();
+
+    let (select_match, from) = if use_fts {
+        conditions.push("cards_fts MATCH ?".to_string());
+        sql_params.push(Box::new(fts_match_expression(&fts_terms)));
+        (
+            format!(
+                "highlight(cards_fts, 0, '{open}', '{close}'),
+                 snippet(cards_fts, 1, '{open}', '{close}', '{ellipsis}', 32),
+                 -bm25(cards_fts, 10.0, 1.0)",
+                open = MATCH_OPEN,
+                close = MATCH_CLOSE,
+                ellipsis = SNIPPET_ELLIPSIS,
+            ),
+            "cards_fts JOIN cards c ON c.rowid = cards_fts.rowid",
+        )
+    } else {
+        ("c.title, c.description, 0.0".to_string(), "cards c")
+    };
+
+    for term in &like_terms {
+        conditions.push(
+            "(c.title LIKE ? ESCAPE '\\' OR c.description LIKE ? ESCAPE '\\')".to_string(),
+        );
+        let pattern = format!("%{}%", escape_like(term));
+        sql_params.push(Box::new(pattern.clone()));
+        sql_params.push(Box::new(pattern));
+    }
+
+    if let Some(project_id) = &params.project_id {
+        conditions.push("c.project_id = ?".to_string());
+        sql_params.push(Box::new(project_id.clone()));
+    }
+    if !params.priorities.is_empty() {
+        let placeholders = vec!["?"; params.priorities.len()].join(", ");
+        conditions.push(format!("c.priority IN ({})", placeholders));
+        for priority in &params.priorities {
+            sql_params.push(Box::new(priority.clone()));
+        }
+    }
+    if let Some(min_priority) = &params.min_priority {
+        let levels = current_levels_from(min_priority.rank());
+        let placeholders = vec!["?"; levels.len()].join(", ");
+        conditions.push(format!("c.priority IN ({})", placeholders));
+        for level in levels {
+            sql_params.push(Box::new(level));
+        }
+    }
+    if let Some(completed) = params.completed {
+        conditions.push("COALESCE(c.completed, 0) = ?".to_string());
+        sql_params.push(Box::new(i32::from(completed)));
+    }
+    // 纯日期的卡片按本地日期比较, 带时间的卡片按时刻比较; 纯日期的上下限表示本地一整天
+    let due_from = params.due_from.as_deref().map(parse_date_bound).transpose()?;
+    let due_to = params.due_to.as_deref().map(parse_date_bound).transpose()?;
+    if let (Some(from), Some(to)) = (&due_from, &due_to) {
+        if !from.not_after(to) {
+            return Err(DbError::Validation(format!(
+                "due_from {} must not be after due_to {}",
+                from, to
+            )));
+        }
+    }
+    if let Some(from) = &due_from {
+        conditions.push(
+            "(CASE WHEN length(c.due_date) = 10 THEN c.due_date >= ? ELSE julianday(c.due_date) >= julianday(?) END)"
+                .to_string(),
+        );
+        sql_params.push(Box::new(from.local_date().format("%Y-%m-%d").to_string()));
+        sql_params.push(Box::new(bound_instant(from, from.start_instant())?));
+    }
+    if let Some(to) = &due_to {
+        let (compare, end) = match to {
+            CardDate::DateTime(_) => ("<=", to.start_instant()),
+            CardDate::Date(date) => ("<", date.succ_opt().and_then(|next| CardDate::Date(next).start_instant())),
+        };
+        conditions.push(format!(
+            "(CASE WHEN length(c.due_date) = 10 THEN c.due_date <= ? ELSE julianday(c.due_date) {} julianday(?) END)",
+            compare
+        ));
+        sql_params.push(Box::new(to.local_date().format("%Y-%m-%d").to_string()));
+        sql_params.push(Box::new(bound_instant(to, end)?));
+    }
+
+    let limit = params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_SEARCH_LIMIT);
+    sql_params.push(Box::new(limit));
+
+    let order_by = if use_fts { "bm25(cards_fts, 10.0, 1.0)" } else { "c.updated_at DESC" };
+    let sql = format!(
+        "SELECT c.id, c.title, c.description, c.column_id,
+                (SELECT COUNT(*) FROM cards AS prev WHERE prev.column_id = c.column_id AND prev.rank < c.rank AND prev.archived_at IS NULL),
+                c.completed, c.priority, c.start_date, c.due_date, c.created_at, c.updated_at,
+                c.project_id, p.name, col.title, {select_match}, c.version
+         FROM {from}
+         JOIN projects p ON p.id = c.project_id
+         JOIN columns col ON col.id = c.column_id
+         WHERE {conditions}
+         ORDER BY {order_by}
+         LIMIT ?",
+        select_match = select_match,
+        from = from,
+        conditions = conditions.join(" AND "),
+        order_by = order_by,
+    );
+
+    let mut stmt = conn.prepare(&sql)?;
+    let results_iter = stmt.query_map(rusqlite::params_from_iter(sql_params.iter()), |row| {
+        let completed: Option<i32> = row.get(5)?;
+        Ok(CardSearchResult {
+            card: Card {
+                id: row.get(0)?,
+                title: row.get(1)?,
+                description: row.get(2)?,
+                column_id: row.get(3)?,
+                position: row.get(4)?,
+                completed: completed.map(|c| c == 1),
+                priority: row.get(6)?,
+                tag_ids: None,
+                checklist_progress: None,
+                comment_count: None,
+                recurrence: None,
+                wip_exceeded: None,
+                start_date: row.get(7)?,
+                due_date: row.get(8)?,
+                version: row.get(17)?,
+                archived_at: None,
+                created_at: row.get(9)?,
+                updated_at: row.get(10)?,
+            },
+            project_id: row.get(11)?,
+            project_name: row.get(12)?,
+            column_title: row.get(13)?,
+            title_highlight: row.get(14)?,
+            description_snippet: row.get(15)?,
+            score: row.get(16)?,
+        })
+    })?;
+
+    let mut results: Vec<CardSearchResult> = results_iter.collect::<Result<_, _>>()?;
+
+    for result in &mut results {
+        result.card.tag_ids = Some(get_card_tag_ids(conn, &result.card.id)?);
+        result.card.checklist_progress = Some(get_checklist_progress(conn, &result.card.id)?);
+        result.card.comment_count = Some(get_comment_count(conn, &result.card.id)?);
+        result.card.recurrence = get_card_recurrence(conn, &result.card.id)?;
+    }
+
+    // 只有短词时没有 FTS 高亮, 在这里补上
+    if !use_fts {
+        for result in &mut results {
+            result.title_highlight = highlight_terms(&result.card.title, &like_terms);
+            result.description_snippet = result
+                .card
+                .description
+                .as_deref()
+                .map(|description| like_snippet(description, &like_terms));
+        }
+    }
+    for result in &mut results {
+        result.title_highlight = render_highlight(&result.title_highlight);
+        result.description_snippet = result.description_snippet.as_deref().map(render_highlight);
+    }
+
+    Ok(results)
+}
+
+/// 将每个词作为短语加引号, 避免用户输入被解析为 FTS5 语法
+fn fts_match_expression(terms: &[&str]) -> String {
+    terms
+        .iter()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub(crate) fn escape_like(term: &str) -> String {
+    term.replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// 截止日期筛选条件与卡片日期格式相同: RFC 3339 时间或 YYYY-MM-DD 日期
+fn parse_date_bound(value: &str) -> Result<CardDate, DbError> {
+    CardDate::parse(value).ok_or_else(|| DbError::Validation(format!("invalid date: {}", value)))
+}
+
+/// 筛选条件对应的 UTC 时刻, 用于和带时间的卡片比较
+fn bound_instant(bound: &CardDate, instant: Option<DateTime<Utc>>) -> Result<String, DbError> {
+    instant
+        .map(|instant| instant.to_rfc3339_opts(SecondsFormat::Millis, true))
+        .ok_or_else(|| DbError::Validation(format!("invalid date: {}", bound)))
+}
+
+/// 高亮所有命中的词 (与 LIKE 一致, 仅对 ASCII 忽略大小写)
+fn highlight_terms(text: &str, terms: &[&str]) -> String {
+    let lowered = text.to_ascii_lowercase();
+    let lowered_terms: Vec<String> = terms.iter().map(|t| t.to_ascii_lowercase()).collect();
+
+    let mut result = String::with_capacity(text.len());
+    let mut index = 0;
+    'outer: while index < text.len() {
+        for term in &lowered_terms {
+            if lowered[index..].starts_with(term.as_str()) {
+                result.push_str(MATCH_OPEN);
+                result.push_str(&text[index..index + term.len()]);
+                result.push_str(MATCH_CLOSE);
+                index += term.len();
+                continue 'outer;
+            }
+        }
+        let ch = text[index..].chars().next().unwrap_or_default();
+        result.push(ch);
+        index += ch.len_utf8();
+    }
+    result
+}
+
+/// 转义 HTML 并把命中标记替换为 `<mark></mark>`
+fn render_highlight(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(ch),
+        }
+    }
+    result.replace(MATCH_OPEN, HIGHLIGHT_OPEN).replace(MATCH_CLOSE, HIGHLIGHT_CLOSE)
+}
+
+/// 截取第一个命中词附近的文本作为摘要
+fn like_snippet(text: &str, terms: &[&str]) -> String {
+    const BEFORE_CHARS: usize = 8;
+    const TOTAL_CHARS: usize = 32;
+
+    let lowered = text.to_ascii_lowercase();
+    let first_match = terms
+        .iter()
+        .filter_map(|term| lowered.find(&term.to_ascii_lowercase()))
+        .min()
+        .unwrap_or(0);
+    let match_char = text[..first_match].chars().count();
+    let start = match_char.saturating_sub(BEFORE_CHARS);
+    let total = text.chars().count();
+
+    let window: String = text.chars().skip(start).take(TOTAL_CHARS).collect();
+    let mut snippet = String::new();
+    if start > 0 {
+        snippet.push_str(SNIPPET_ELLIPSIS);
+    }
+    snippet.push_str(&highlight_terms(&window, terms));
+    if start + TOTAL_CHARS < total {
+        snippet.push_str(SNIPPET_ELLIPSIS);
+    }
+    snippet
+}
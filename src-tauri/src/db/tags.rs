@@ -0,0 +1,273 @@
+// 标签相关数据库操作
+use super::history::{record_history, UndoOp};
+use super::{with_connection, with_transaction, DbError};
+use crate::commands::tags::Tag;
+use chrono::Utc;
+use rusqlite::Connection;
+use std::collections::{HashMap, HashSet};
+use tauri::AppHandle;
+use uuid::Uuid;
+
+/// 获取项目的所有标签
+pub fn get_tags(app_handle: &AppHandle, project_id: &str) -> Result<Vec<Tag>, DbError> {
+    let pid = project_id.to_string();
+    with_connection(app_handle, |conn| get_project_tags(conn, &pid))
+}
+
+pub(crate) fn get_project_tags(conn: &Connection, project_id: &str) -> Result<Vec<Tag>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT id, name, color, position, created_at, updated_at
+         FROM tags WHERE project_id = ? ORDER BY position, created_at"
+    )?;
+
+    let tags_iter = stmt.query_map([project_id], |row| {
+        Ok(Tag {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            color: row.get(2)?,
+            position: row.get(3)?,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    })?;
+
+    let tags: Vec<Tag> = tags_iter.filter_map(|t| t.ok()).collect();
+    Ok(tags)
+}
+
+/// 创建标签
+pub fn create_tag(app_handle: &AppHandle, project_id: &str, tag: &Tag) -> Result<Tag, DbError> {
+    let pid = project_id.to_string();
+    let t = tag.clone();
+    with_transaction(app_handle, |conn| {
+        let created = create_tag_impl(conn, &pid, &t)?;
+        record_history(
+            conn,
+            &pid,
+            format!("创建标签「{}」", created.name),
+            vec![UndoOp::DeleteTag { tag_id: created.id.clone() }],
+            vec![UndoOp::RestoreTag {
+                project_id: pid.clone(),
+                tag: created.clone(),
+                card_ids: Vec::new(),
+            }],
+        )?;
+        Ok(created)
+    })
+}
+
+fn create_tag_impl(conn: &Connection, project_id: &str, tag: &Tag) -> Result<Tag, DbError> {
+    let now = Utc::now().to_rfc3339();
+    let id = if tag.id.is_empty() { Uuid::new_v4().to_string() } else { tag.id.clone() };
+    let position = conn.query_row(
+        "SELECT COALESCE(MAX(position), -1) + 1 FROM tags WHERE project_id = ?",
+        [project_id],
+        |row| row.get::<_, i32>(0),
+    )?;
+
+    conn.execute(
+        "INSERT INTO tags (id, project_id, name, color, position, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![id, project_id, tag.name, tag.color, position, now, now],
+    )?;
+
+    Ok(Tag {
+        id,
+        name: tag.name.clone(),
+        color: tag.color.clone(),
+        position,
+        created_at: now.clone(),
+        updated_at: now,
+    })
+}
+
+/// 更新标签
+pub fn update_tag(app_handle: &AppHandle, project_id: &str, tag: &Tag) -> Result<Tag, DbError> {
+    let pid = project_id.to_string();
+    let t = tag.clone();
+    with_transaction(app_handle, |conn| {
+        let Some(previous) = find_tag(conn, &pid, &t.id)? else {
+            return Err(DbError::not_found("tag", &t.id));
+        };
+
+        let now = Utc::now().to_rfc3339();
+        conn.execute(
+            "UPDATE tags SET name = ?, color = ?, position = ?, updated_at = ? WHERE id = ?",
+            rusqlite::params![t.name, t.color, t.position, now, t.id],
+        )?;
+
+        let updated = Tag {
+            id: t.id.clone(),
+            name: t.name.clone(),
+            color: t.color.clone(),
+            position: t.position,
+            created_at: previous.created_at.clone(),
+            updated_at: now,
+        };
+
+        record_history(
+            conn,
+            &pid,
+            format!("更新标签「{}」", updated.name),
+            vec![UndoOp::RestoreTag { project_id: pid.clone(), tag: previous, card_ids: Vec::new() }],
+            vec![UndoOp::RestoreTag { project_id: pid.clone(), tag: updated.clone(), card_ids: Vec::new() }],
+        )?;
+
+        Ok(updated)
+    })
+}
+
+/// 删除标签
+pub fn delete_tag(app_handle: &AppHandle, project_id: &str, tag_id: &str) -> Result<(), DbError> {
+    let pid = project_id.to_string();
+    let tid = tag_id.to_string();
+    with_transaction(app_handle, |conn| {
+        let Some(previous) = find_tag(conn, &pid, &tid)? else {
+            return Ok(());
+        };
+
+        // 记录使用该标签的卡片, 撤销时一并恢复关联
+        let mut stmt = conn.prepare("SELECT card_id FROM card_tags WHERE tag_id = ?")?;
+        let card_ids: Vec<String> = stmt
+            .query_map([&tid], |row| row.get(0))?
+            .filter_map(|c| c.ok())
+            .collect();
+
+        // card_tags 通过外键级联删除
+        conn.execute("DELETE FROM tags WHERE id = ?", [&tid])?;
+
+        record_history(
+            conn,
+            &pid,
+            format!("删除标签「{}」", previous.name),
+            vec![UndoOp::RestoreTag { project_id: pid.clone(), tag: previous, card_ids }],
+            vec![UndoOp::DeleteTag { tag_id: tid.clone() }],
+        )
+    })
+}
+
+fn find_tag(conn: &Connection, project_id: &str, tag_id: &str) -> Result<Option<Tag>, DbError> {
+    let result = conn.query_row(
+        "SELECT id, name, color, position, created_at, updated_at
+         FROM tags WHERE id = ? AND project_id = ?",
+        [tag_id, project_id],
+        |row| {
+            Ok(Tag {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                color: row.get(2)?,
+                position: row.get(3)?,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
+            })
+        },
+    );
+
+    match result {
+        Ok(tag) => Ok(Some(tag)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
+/// 获取卡片的标签ID列表
+pub(crate) fn get_card_tag_ids(conn: &Connection, card_id: &str) -> Result<Vec<String>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT ct.tag_id FROM card_tags ct
+         JOIN tags t ON t.id = ct.tag_id
+         WHERE ct.card_id = ? ORDER BY t.position"
+    )?;
+
+    let tag_ids: Vec<String> = stmt
+        .query_map([card_id], |row| row.get(0))?
+        .filter_map(|t| t.ok())
+        .collect();
+    Ok(tag_ids)
+}
+
+/// 获取项目内所有卡片的标签ID, 按卡片ID分组
+pub(crate) fn get_project_card_tag_ids(
+    conn: &Connection,
+    project_id: &str,
+) -> Result<HashMap<String, Vec<String>>, DbError> {
+    let mut stmt = conn.prepare(
+        "SELECT ct.card_id, ct.tag_id FROM card_tags ct
+         JOIN tags t ON t.id = ct.tag_id
+         WHERE t.project_id = ? ORDER BY t.position"
+    )?;
+
+    let rows = stmt.query_map([project_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+
+    let mut tag_ids: HashMap<String, Vec<String>> = HashMap::new();
+    for (card_id, tag_id) in rows.filter_map(|r| r.ok()) {
+        tag_ids.entry(card_id).or_default().push(tag_id);
+    }
+    Ok(tag_ids)
+}
+
+/// 用给定的标签替换卡片的全部标签, 标签必须属于同一项目
+pub(crate) fn set_card_tags(
+    conn: &Connection,
+    project_id: &str,
+    card_id: &str,
+    tag_ids: &[String],
+) -> Result<(), DbError> {
+    conn.execute("DELETE FROM card_tags WHERE card_id = ?", [card_id])?;
+
+    // 忽略重复传入的标签ID
+    let mut seen = HashSet::new();
+    for tag_id in tag_ids.iter().filter(|id| seen.insert(id.as_str())) {
+        let inserted = conn.execute(
+            "INSERT INTO card_tags (card_id, tag_id)
+             SELECT ?, id FROM tags WHERE id = ? AND project_id = ?",
+            [card_id, tag_id, project_id],
+        )?;
+        if inserted == 0 {
+            return Err(DbError::Validation(format!(
+                "tag {} does not belong to project {}",
+                tag_id, project_id
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// 恢复标签及其卡片关联 (撤销/重做使用)
+pub(crate) fn restore_tag(
+    conn: &Connection,
+    project_id: &str,
+    tag: &Tag,
+    card_ids: &[String],
+) -> Result<(), DbError> {
+    conn.execute(
+        "INSERT INTO tags (id, project_id, name, color, position, created_at, updated_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?)
+         ON CONFLICT(id) DO UPDATE SET
+             name = excluded.name,
+             color = excluded.color,
+             position = excluded.position,
+             updated_at = excluded.updated_at",
+        rusqlite::params![
+            tag.id,
+            project_id,
+            tag.name,
+            tag.color,
+            tag.position,
+            tag.created_at,
+            tag.updated_at,
+        ],
+    )?;
+
+    for card_id in card_ids {
+        conn.execute(
+            "INSERT OR IGNORE INTO card_tags (card_id, tag_id)
+             SELECT id, ? FROM cards WHERE id = ?",
+            [&tag.id, card_id],
+        )?;
+    }
+
+    Ok(())
+}
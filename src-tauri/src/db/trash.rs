@@ -0,0 +1,249 @@
+// 回收站相关数据库操作
+// 删除卡片、列或项目时, 将恢复所需的快照 (与撤销历史相同的操作序列) 连同删除时间写入 trash 表,
+// 恢复时重放快照; 超过保留天数的记录由后台任务定期清除。
+use super::history::{apply_ops, record_history, UndoOp, PROJECT_LIST_SCOPE};
+use super::{with_connection, with_transaction, DbError};
+use crate::commands::archive::ArchiveKind;
+use crate::commands::trash::{TrashItem, TrashSettings};
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use tauri::AppHandle;
+use uuid::Uuid;
+
+const SETTINGS_KEY: &str = "trash_settings";
+
+/// 保留天数上限
+const MAX_RETENTION_DAYS: u32 = 365;
+
+/// 回收站记录及恢复所需的操作序列
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashEntry {
+    pub item: TrashItem,
+    pub restore_ops: Vec<UndoOp>,
+}
+
+/// 把即将删除的数据放入回收站 (应在删除前、调用方的事务中执行)
+pub(crate) fn move_to_trash(
+    conn: &Connection,
+    kind: ArchiveKind,
+    entity_id: &str,
+    project_id: &str,
+    title: &str,
+    restore_ops: &[UndoOp],
+) -> Result<TrashEntry, DbError> {
+    let project_name: String = conn.query_row(
+        "SELECT name FROM projects WHERE id = ?",
+        [project_id],
+        |row| row.get(0),
+    )?;
+    let entry = TrashEntry {
+        item: TrashItem {
+            id: Uuid::new_v4().to_string(),
+            kind,
+            entity_id: entity_id.to_string(),
+            project_id: project_id.to_string(),
+            project_name,
+            title: title.to_string(),
+            deleted_at: Utc::now().to_rfc3339(),
+        },
+        restore_ops: restore_ops.to_vec(),
+    };
+    put_entry(conn, &entry)?;
+    Ok(entry)
+}
+
+/// 写入回收站记录 (撤销/重做时也会调用)
+pub(crate) fn put_entry(conn: &Connection, entry: &TrashEntry) -> Result<(), DbError> {
+    let item = &entry.item;
+    conn.execute(
+        "INSERT OR REPLACE INTO trash (id, kind, entity_id, project_id, project_name, title, restore_ops, deleted_at)
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+        rusqlite::params![
+            item.id,
+            item.kind,
+            item.entity_id,
+            item.project_id,
+            item.project_name,
+            item.title,
+            serde_json::to_string(&entry.restore_ops)?,
+            item.deleted_at,
+        ],
+    )?;
+    Ok(())
+}
+
+/// 删除回收站记录
+pub(crate) fn remove_entry(conn: &Connection, trash_id: &str) -> Result<(), DbError> {
+    conn.execute("DELETE FROM trash WHERE id = ?", [trash_id])?;
+    Ok(())
+}
+
+/// 获取回收站内容
+pub fn list_trash(app_handle: &AppHandle, project_id: Option<&str>) -> Result<Vec<TrashItem>, DbError> {
+    let pid = project_id.map(str::to_string);
+    with_connection(app_handle, |conn| {
+        let mut stmt = conn.prepare(
+            "SELECT id, kind, entity_id, project_id, project_name, title, deleted_at FROM trash
+             WHERE ?1 IS NULL OR project_id = ?1
+             ORDER BY deleted_at DESC, id",
+        )?;
+
+        let items_iter = stmt.query_map([&pid], |row| {
+            Ok(TrashItem {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                entity_id: row.get(2)?,
+                project_id: row.get(3)?,
+                project_name: row.get(4)?,
+                title: row.get(5)?,
+                deleted_at: row.get(6)?,
+            })
+        })?;
+
+        let items: Vec<TrashItem> = items_iter.filter_map(|i| i.ok()).collect();
+        Ok(items)
+    })
+}
+
+fn find_entry(conn: &Connection, trash_id: &str) -> Result<Option<TrashEntry>, DbError> {
+    let result = conn.query_row(
+        "SELECT id, kind, entity_id, project_id, project_name, title, deleted_at, restore_ops
+         FROM trash WHERE id = ?",
+        [trash_id],
+        |row| {
+            let item = TrashItem {
+                id: row.get(0)?,
+                kind: row.get(1)?,
+                entity_id: row.get(2)?,
+                project_id: row.get(3)?,
+                project_name: row.get(4)?,
+                title: row.get(5)?,
+                deleted_at: row.get(6)?,
+            };
+            Ok((item, row.get::<_, String>(7)?))
+        },
+    );
+
+    match result {
+        Ok((item, ops)) => Ok(Some(TrashEntry { item, restore_ops: serde_json::from_str(&ops)? })),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
+/// 从回收站恢复; 卡片所在的列或列所在的项目已不存在时需先恢复它们
+pub fn restore_from_trash(app_handle: &AppHandle, trash_id: &str) -> Result<TrashItem, DbError> {
+    let tid = trash_id.to_string();
+    with_transaction(app_handle, |conn| {
+        let Some(entry) = find_entry(conn, &tid)? else {
+            return Err(DbError::not_found("trash", &tid));
+        };
+        let item = entry.item.clone();
+
+        if item.kind != ArchiveKind::Project && !exists(conn, "projects", &item.project_id)? {
+            return Err(DbError::Validation(format!(
+                "project {} no longer exists; restore the project first",
+                item.project_id
+            )));
+        }
+        let column_id = entry.restore_ops.iter().find_map(|op| match op {
+            UndoOp::RestoreCard { card, .. } if item.kind == ArchiveKind::Card => Some(card.column_id.as_str()),
+            _ => None,
+        });
+        if let Some(column_id) = column_id {
+            if !exists(conn, "columns", column_id)? {
+                return Err(DbError::Validation(format!(
+                    "column {} no longer exists; restore the column first",
+                    column_id
+                )));
+            }
+        }
+
+        apply_ops(conn, &entry.restore_ops)?;
+        remove_entry(conn, &tid)?;
+
+        let delete_op = match item.kind {
+            ArchiveKind::Card => UndoOp::DeleteCard { card_id: item.entity_id.clone() },
+            ArchiveKind::Column => UndoOp::DeleteColumn { column_id: item.entity_id.clone() },
+            ArchiveKind::Project => UndoOp::DeleteProject { project_id: item.entity_id.clone() },
+        };
+        let scope = if item.kind == ArchiveKind::Project { PROJECT_LIST_SCOPE } else { &item.project_id };
+        let mut redo_ops = entry.restore_ops.clone();
+        redo_ops.push(UndoOp::RemoveFromTrash { trash_id: tid.clone() });
+        record_history(
+            conn,
+            scope,
+            format!("从回收站恢复「{}」", item.title),
+            vec![delete_op, UndoOp::PutInTrash { entry }],
+            redo_ops,
+        )?;
+        Ok(item)
+    })
+}
+
+fn exists(conn: &Connection, table: &'static str, id: &str) -> Result<bool, DbError> {
+    let exists = conn.query_row(
+        &format!("SELECT EXISTS (SELECT 1 FROM {} WHERE id = ?)", table),
+        [id],
+        |row| row.get(0),
+    )?;
+    Ok(exists)
+}
+
+/// 获取回收站设置
+pub fn get_trash_settings(app_handle: &AppHandle) -> Result<TrashSettings, DbError> {
+    with_connection(app_handle, get_trash_settings_impl)
+}
+
+fn get_trash_settings_impl(conn: &Connection) -> Result<TrashSettings, DbError> {
+    let result = conn.query_row(
+        "SELECT value FROM settings WHERE key = ?",
+        [SETTINGS_KEY],
+        |row| row.get::<_, String>(0),
+    );
+
+    match result {
+        Ok(value) => Ok(serde_json::from_str(&value)?),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(TrashSettings::default()),
+        Err(e) => Err(DbError::from(e)),
+    }
+}
+
+/// 更新回收站设置
+pub fn update_trash_settings(
+    app_handle: &AppHandle,
+    settings: &TrashSettings,
+) -> Result<TrashSettings, DbError> {
+    if !(1..=MAX_RETENTION_DAYS).contains(&settings.retention_days) {
+        return Err(DbError::Validation(format!(
+            "trash retention must be between 1 and {} days",
+            MAX_RETENTION_DAYS
+        )));
+    }
+
+    let value = serde_json::to_string(settings)?;
+    with_connection(app_handle, |conn| {
+        conn.execute(
+            "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+            [SETTINGS_KEY, value.as_str()],
+        )?;
+        Ok(settings.clone())
+    })
+}
+
+/// 清除超过保留天数的回收站记录, 返回清除数量
+pub fn purge_expired_trash(app_handle: &AppHandle, now: DateTime<Utc>) -> Result<usize, DbError> {
+    let purged = with_connection(app_handle, |conn| purge_expired_trash_impl(conn, now))?;
+    if purged > 0 {
+        log::info!("Purged {} expired trash entries", purged);
+    }
+    Ok(purged)
+}
+
+fn purge_expired_trash_impl(conn: &Connection, now: DateTime<Utc>) -> Result<usize, DbError> {
+    let settings = get_trash_settings_impl(conn)?;
+    let cutoff = now - Duration::days(i64::from(settings.retention_days));
+    let purged = conn.execute("DELETE FROM trash WHERE deleted_at < ?", [cutoff.to_rfc3339()])?;
+    Ok(purged)
+}
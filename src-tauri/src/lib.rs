@@ -4,6 +4,10 @@
 mod commands;
 mod db;
 
+use std::time::Duration;
+use tauri::Emitter;
+use tauri_plugin_notification::NotificationExt;
+
 /// 获取应用版本信息
 #[tauri::command]
 fn get_app_info() -> serde_json::Value {
@@ -14,9 +18,130 @@ fn get_app_info() -> serde_json::Value {
     })
 }
 
+/// 重复卡片到期检查间隔
+const RECURRENCE_CHECK_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// 定期为截止时间已过的重复卡片生成下一张卡片, 并通知前端刷新看板
+async fn run_recurrence_scheduler(app_handle: tauri::AppHandle) {
+    loop {
+        match db::recurrence::spawn_due_occurrences(&app_handle) {
+            Ok(project_ids) => {
+                for project_id in project_ids {
+                    if let Err(e) = app_handle.emit("board-changed", project_id) {
+                        log::warn!("Failed to emit board-changed event: {}", e);
+                    }
+                }
+            }
+            Err(e) => log::error!("Failed to spawn recurring cards: {}", e),
+        }
+        tokio::time::sleep(RECURRENCE_CHECK_INTERVAL).await;
+    }
+}
+
+/// 到期提醒检查间隔
+const REMINDER_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 定期检查即将到期和已过期的卡片, 发送桌面通知
+async fn run_reminder_scheduler(app_handle: tauri::AppHandle) {
+    loop {
+        match db::reminders::take_due_reminders(&app_handle, chrono::Utc::now()) {
+            Ok(reminders) => {
+                for reminder in reminders {
+                    let body = match reminder.lead_minutes {
+                        0 => format!("「{}」已到期", reminder.card_title),
+                        m if m % (24 * 60) == 0 => format!("「{}」将在 {} 天后到期", reminder.card_title, m / (24 * 60)),
+                        m if m % 60 == 0 => format!("「{}」将在 {} 小时后到期", reminder.card_title, m / 60),
+                        m => format!("「{}」将在 {} 分钟后到期", reminder.card_title, m),
+                    };
+                    let shown = app_handle
+                        .notification()
+                        .builder()
+                        .title(format!("船长待办 · {}", reminder.project_name))
+                        .body(body)
+                        .show();
+                    if let Err(e) = shown {
+                        log::warn!("Failed to show reminder for card {}: {}", reminder.card_id, e);
+                    }
+                }
+            }
+            Err(e) => log::error!("Failed to check due reminders: {}", e),
+        }
+        tokio::time::sleep(REMINDER_CHECK_INTERVAL).await;
+    }
+}
+
+/// 自动备份检查间隔
+const BACKUP_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 定期检查并创建数据库备份
+async fn run_backup_scheduler(app_handle: tauri::AppHandle) {
+    loop {
+        if let Err(e) = db::backups::run_scheduled_backup(&app_handle) {
+            log::error!("Failed to back up database: {}", e);
+        }
+        tokio::time::sleep(BACKUP_CHECK_INTERVAL).await;
+    }
+}
+
+/// 排序键整理间隔
+const RANK_REBALANCE_INTERVAL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 定期重新分配过长的排序键
+async fn run_rank_rebalancer(app_handle: tauri::AppHandle) {
+    loop {
+        if let Err(e) = db::rank::rebalance_ranks(&app_handle) {
+            log::error!("Failed to rebalance rank keys: {}", e);
+        }
+        tokio::time::sleep(RANK_REBALANCE_INTERVAL).await;
+    }
+}
+
+/// 各列卡片数记录间隔
+const FLOW_SNAPSHOT_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 定期记录当天各列的卡片数, 供累积流图使用
+async fn run_flow_snapshotter(app_handle: tauri::AppHandle) {
+    loop {
+        if let Err(e) = db::metrics::snapshot_column_counts(&app_handle, chrono::Local::now().date_naive()) {
+            log::error!("Failed to record column card counts: {}", e);
+        }
+        tokio::time::sleep(FLOW_SNAPSHOT_INTERVAL).await;
+    }
+}
+
+/// 回收站清理间隔
+const TRASH_PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 定期清除超过保留天数的回收站记录
+async fn run_trash_purger(app_handle: tauri::AppHandle) {
+    loop {
+        if let Err(e) = db::trash::purge_expired_trash(&app_handle, chrono::Utc::now()) {
+            log::error!("Failed to purge trash: {}", e);
+        }
+        tokio::time::sleep(TRASH_PURGE_INTERVAL).await;
+    }
+}
+
+/// 附件回收间隔
+const ATTACHMENT_GC_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 定期删除不再被引用的附件文件; 启动时先执行一次, 清理上次运行遗留的文件
+async fn run_attachment_collector(app_handle: tauri::AppHandle) {
+    loop {
+        match db::attachments::collect_orphaned_blobs(&app_handle) {
+            Ok(0) => {}
+            Ok(removed) => log::info!("Removed {} orphaned attachment blobs", removed),
+            Err(e) => log::error!("Failed to collect orphaned attachment blobs: {}", e),
+        }
+        tokio::time::sleep(ATTACHMENT_GC_INTERVAL).await;
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_opener::init())
         .setup(|app| {
             // 初始化日志
             if cfg!(debug_assertions) {
@@ -33,6 +158,13 @@ pub fn run() {
                 log::error!("Failed to initialize database: {}", e);
             } else {
                 log::info!("Database initialized successfully");
+                tauri::async_runtime::spawn(run_backup_scheduler(app_handle.clone()));
+                tauri::async_runtime::spawn(run_reminder_scheduler(app_handle.clone()));
+                tauri::async_runtime::spawn(run_rank_rebalancer(app_handle.clone()));
+                tauri::async_runtime::spawn(run_trash_purger(app_handle.clone()));
+                tauri::async_runtime::spawn(run_flow_snapshotter(app_handle.clone()));
+                tauri::async_runtime::spawn(run_attachment_collector(app_handle.clone()));
+                tauri::async_runtime::spawn(run_recurrence_scheduler(app_handle));
             }
 
             Ok(())
@@ -55,6 +187,49 @@ pub fn run() {
             commands::projects::reorder_projects,
             commands::projects::get_current_project,
             commands::projects::set_current_project,
+            commands::archive::archive_item,
+            commands::archive::unarchive_item,
+            commands::archive::list_archived,
+            commands::trash::list_trash,
+            commands::trash::restore_from_trash,
+            commands::trash::get_trash_settings,
+            commands::trash::update_trash_settings,
+            commands::metrics::get_project_metrics,
+            commands::metrics::get_cumulative_flow,
+            commands::priorities::get_priority_scale,
+            commands::priorities::update_priority_scale,
+            commands::activities::get_project_activities,
+            commands::activities::get_card_activities,
+            commands::history::undo,
+            commands::history::redo,
+            commands::history::get_undo_history,
+            commands::search::search_cards,
+            commands::tags::get_tags,
+            commands::tags::create_tag,
+            commands::tags::update_tag,
+            commands::tags::delete_tag,
+            commands::checklists::get_checklist,
+            commands::checklists::add_checklist_item,
+            commands::checklists::toggle_checklist_item,
+            commands::checklists::reorder_checklist_items,
+            commands::checklists::delete_checklist_item,
+            commands::comments::list_comments,
+            commands::comments::add_comment,
+            commands::comments::edit_comment,
+            commands::comments::delete_comment,
+            commands::attachments::list_attachments,
+            commands::attachments::add_attachment,
+            commands::attachments::open_attachment,
+            commands::attachments::remove_attachment,
+            commands::backups::list_backups,
+            commands::backups::create_backup,
+            commands::backups::restore_backup,
+            commands::reminders::get_reminder_settings,
+            commands::reminders::update_reminder_settings,
+            commands::export::export_project,
+            commands::import::import_project,
+            commands::import::import_trello,
+            commands::import::import_csv,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -16,9 +16,18 @@ const activityTypeLabels = {
   card_updated: '更新卡片',
   card_moved: '移动卡片',
   card_deleted: '删除卡片',
+  card_archived: '归档卡片',
+  card_unarchived: '取消归档卡片',
   column_created: '创建列',
   column_updated: '更新列',
   column_deleted: '删除列',
+  column_archived: '归档列',
+  column_unarchived: '取消归档列',
+  project_created: '创建项目',
+  project_updated: '更新项目',
+  project_reordered: '调整项目顺序',
+  project_archived: '归档项目',
+  project_unarchived: '取消归档项目',
 };
 
 const activityTypeColors = {
@@ -26,9 +35,18 @@ const activityTypeColors = {
   card_updated: 'bg-blue-500',
   card_moved: 'bg-yellow-500',
   card_deleted: 'bg-red-500',
+  card_archived: 'bg-stone-500',
+  card_unarchived: 'bg-lime-500',
   column_created: 'bg-purple-500',
   column_updated: 'bg-indigo-500',
   column_deleted: 'bg-pink-500',
+  column_archived: 'bg-stone-500',
+  column_unarchived: 'bg-lime-500',
+  project_created: 'bg-teal-500',
+  project_updated: 'bg-cyan-500',
+  project_reordered: 'bg-slate-500',
+  project_archived: 'bg-stone-500',
+  project_unarchived: 'bg-lime-500',
 };
 
 export function ActivityPanel({ activities }: ActivityPanelProps) {
@@ -3,7 +3,7 @@
  * 简洁设计
  */
 import { useState, useEffect } from 'react';
-import { Card as CardType, PRIORITY_CONFIG, PRIORITY_ORDER } from '../types/kanban';
+import { Card as CardType, getPriorityConfig, PRIORITY_ORDER } from '../types/kanban';
 import { Dialog, DialogContent } from './ui/dialog';
 import { Button } from './ui/button';
 import { Input } from './ui/input';
@@ -179,18 +179,18 @@ export function CardDetailsDialog({
                 <button
                   className="inline-flex items-center gap-1.5 h-7 px-2.5 rounded text-xs font-medium border focus:outline-none"
                   style={{
-                    color: PRIORITY_CONFIG[editPriority || 'low'].textColor,
-                    backgroundColor: PRIORITY_CONFIG[editPriority || 'low'].bgColor,
-                    borderColor: PRIORITY_CONFIG[editPriority || 'low'].borderColor,
+                    color: getPriorityConfig(editPriority).textColor,
+                    backgroundColor: getPriorityConfig(editPriority).bgColor,
+                    borderColor: getPriorityConfig(editPriority).borderColor,
                   }}
                 >
                   <Flag className="w-3 h-3" />
-                  {PRIORITY_CONFIG[editPriority || 'low'].label}
+                  {getPriorityConfig(editPriority).label}
                 </button>
               </DropdownMenuTrigger>
               <DropdownMenuContent align="start" className="w-32">
                 {PRIORITY_ORDER.map((priority) => {
-                  const config = PRIORITY_CONFIG[priority];
+                  const config = getPriorityConfig(priority);
                   const isSelected = (editPriority || 'low') === priority;
                   return (
                     <DropdownMenuItem
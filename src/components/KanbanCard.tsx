@@ -10,7 +10,7 @@
 import { useState, memo, useCallback } from 'react';
 import { useSortable } from '@dnd-kit/sortable';
 import { CSS } from '@dnd-kit/utilities';
-import { Card as CardType, PRIORITY_CONFIG, PRIORITY_ORDER, Priority } from '../types/kanban';
+import { Card as CardType, getPriorityConfig, PRIORITY_ORDER, Priority } from '../types/kanban';
 import { Button } from './ui/button';
 import { Textarea } from './ui/textarea';
 import { Input } from './ui/input';
@@ -282,19 +282,19 @@ export const KanbanCard = memo(function KanbanCard({
                   <button
                     className="inline-flex items-center gap-1 px-2 py-0.5 rounded border text-xs transition-all duration-150 hover:shadow-sm"
                     style={{
-                      color: PRIORITY_CONFIG[card.priority || 'low'].textColor,
-                      backgroundColor: PRIORITY_CONFIG[card.priority || 'low'].bgColor,
-                      borderColor: PRIORITY_CONFIG[card.priority || 'low'].borderColor,
+                      color: getPriorityConfig(card.priority).textColor,
+                      backgroundColor: getPriorityConfig(card.priority).bgColor,
+                      borderColor: getPriorityConfig(card.priority).borderColor,
                     }}
                     onClick={(e) => e.stopPropagation()}
                   >
                     <Flag className="w-3 h-3" />
-                    {PRIORITY_CONFIG[card.priority || 'low'].label}
+                    {getPriorityConfig(card.priority).label}
                   </button>
                 </DropdownMenuTrigger>
                 <DropdownMenuContent align="start" className="w-36">
                   {PRIORITY_ORDER.map((priority) => {
-                    const config = PRIORITY_CONFIG[priority];
+                    const config = getPriorityConfig(priority);
                     const isSelected = (card.priority || 'low') === priority;
                     return (
                       <DropdownMenuItem
@@ -1,6 +1,8 @@
 import { useState, useCallback, useEffect, useRef } from 'react';
 import { Board, Card, Column, Activity, SearchFilters, Priority } from '../types/kanban';
-import { kanbanApi } from '../lib/api/kanban';
+import { listen } from '@tauri-apps/api/event';
+import { kanbanApi, fromBackendCard, fromBackendColumn } from '../lib/api/kanban';
+import { parseVersionConflict } from '../lib/api/errors';
 
 // 生成空白看板
 const generateEmptyBoard = (projectId: string): Board => {
@@ -48,6 +50,24 @@ export function useKanbanStore(projectId: string | null) {
     loadBoard();
   }, [projectId]);
 
+  // 后端自动生成重复卡片后刷新看板
+  useEffect(() => {
+    if (!projectId) return;
+
+    const unlisten = listen<string>('board-changed', async (event) => {
+      if (event.payload !== projectId) return;
+      try {
+        setBoard(await kanbanApi.getBoard(projectId));
+      } catch (error) {
+        console.error('Failed to refresh board:', error);
+      }
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [projectId]);
+
   const [searchFilters, setSearchFilters] = useState<SearchFilters>({
     keyword: '',
     columnIds: [],
@@ -119,13 +139,18 @@ export function useKanbanStore(projectId: string | null) {
     };
 
     try {
-      await kanbanApi.updateCard(projectId, updatedCard);
+      const savedCard = await kanbanApi.updateCard(projectId, updatedCard);
 
-      setBoard(prev => ({
-        ...prev,
-        cards: prev.cards.map(c => c.id === cardId ? updatedCard : c),
-        updatedAt: new Date(),
-      }));
+      // 完成重复卡片时后端会生成下一张卡片, 重新加载看板
+      if (updates.completed && !card.completed && card.recurrence) {
+        setBoard(await kanbanApi.getBoard(projectId));
+      } else {
+        setBoard(prev => ({
+          ...prev,
+          cards: prev.cards.map(c => c.id === cardId ? savedCard : c),
+          updatedAt: new Date(),
+        }));
+      }
 
       // 添加不同类型的活动记录
       if ('completed' in updates) {
@@ -144,6 +169,15 @@ export function useKanbanStore(projectId: string | null) {
         });
       }
     } catch (error) {
+      // 卡片已被其他窗口修改时以数据库中的当前数据为准
+      const conflict = parseVersionConflict(error);
+      if (conflict) {
+        const current = fromBackendCard(conflict.current);
+        setBoard(prev => ({
+          ...prev,
+          cards: prev.cards.map(c => c.id === cardId ? current : c),
+        }));
+      }
       console.error('Failed to update card:', error);
     }
   }, [projectId, board, addActivity]);
@@ -305,13 +339,28 @@ export function useKanbanStore(projectId: string | null) {
     }
 
     try {
-      // 后台调用 API 持久化
-      await kanbanApi.moveCard(projectId, {
+      // 后台调用 API 持久化, 以后端返回的顺序为准
+      const result = await kanbanApi.moveCard(projectId, {
         cardId,
         fromColumnId,
         toColumnId,
         newPosition: finalPosition,
       });
+      const orders = [result.fromColumn, result.toColumn];
+      setBoard(prevBoard => ({
+        ...prevBoard,
+        columns: prevBoard.columns.map(col => {
+          const order = orders.find(o => o.columnId === col.id);
+          return order ? { ...col, cardIds: order.cardIds } : col;
+        }),
+        cards: prevBoard.cards.map(c => {
+          if (c.id === cardId) {
+            return { ...c, columnId: result.card.columnId, position: result.card.position, version: result.card.version, updatedAt: result.card.updatedAt };
+          }
+          const order = orders.find(o => o.cardIds.includes(c.id));
+          return order ? { ...c, columnId: order.columnId, position: order.cardIds.indexOf(c.id) } : c;
+        }),
+      }));
     } catch (error) {
       console.error('Failed to move card:', error);
       // API 失败时回滚到之前的状态
@@ -357,11 +406,11 @@ export function useKanbanStore(projectId: string | null) {
     };
 
     try {
-      await kanbanApi.updateColumn(projectId, updatedColumn);
+      const savedColumn = await kanbanApi.updateColumn(projectId, updatedColumn);
 
       setBoard(prev => ({
         ...prev,
-        columns: prev.columns.map(col => col.id === columnId ? updatedColumn : col),
+        columns: prev.columns.map(col => col.id === columnId ? savedColumn : col),
         updatedAt: new Date(),
       }));
 
@@ -373,6 +422,15 @@ export function useKanbanStore(projectId: string | null) {
         });
       }
     } catch (error) {
+      // 列已被其他窗口修改时以数据库中的当前数据为准
+      const conflict = parseVersionConflict(error);
+      if (conflict) {
+        const current = fromBackendColumn(conflict.current);
+        setBoard(prev => ({
+          ...prev,
+          columns: prev.columns.map(col => col.id === columnId ? current : col),
+        }));
+      }
       console.error('Failed to update column:', error);
     }
   }, [projectId, board, addActivity]);
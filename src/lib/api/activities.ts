@@ -0,0 +1,70 @@
+// Tauri API 封装层 - 活动记录相关
+import { invoke } from '@tauri-apps/api/core';
+import type { Activity } from '@/types/kanban';
+
+export interface ActivityQuery {
+  types?: Activity['type'][];
+  since?: Date;
+  until?: Date;
+  limit?: number;
+  offset?: number;
+}
+
+export interface ActivityPage {
+  activities: Activity[];
+  total: number;
+  hasMore: boolean;
+}
+
+// 将查询参数转换为后端格式 (参数结构体使用 snake_case)
+function toBackendQuery(query: ActivityQuery): Record<string, unknown> {
+  return {
+    types: query.types ?? [],
+    since: query.since?.toISOString() ?? null,
+    until: query.until?.toISOString() ?? null,
+    limit: query.limit ?? null,
+    offset: query.offset ?? null,
+  };
+}
+
+function fromBackendActivity(data: Record<string, unknown>): Activity {
+  return {
+    id: data.id as string,
+    type: data.type as Activity['type'],
+    cardId: (data.cardId as string | null) ?? undefined,
+    columnId: (data.columnId as string | null) ?? undefined,
+    fromColumnId: (data.fromColumnId as string | null) ?? undefined,
+    toColumnId: (data.toColumnId as string | null) ?? undefined,
+    title: data.title as string,
+    description: (data.description as string | null) ?? undefined,
+    timestamp: new Date(data.timestamp as string),
+  };
+}
+
+function fromBackendPage(data: Record<string, unknown>): ActivityPage {
+  return {
+    activities: (data.activities as Record<string, unknown>[]).map(fromBackendActivity),
+    total: data.total as number,
+    hasMore: data.hasMore as boolean,
+  };
+}
+
+export const activitiesApi = {
+  /** 分页获取项目活动记录 */
+  async getProjectActivities(projectId: string, query: ActivityQuery = {}): Promise<ActivityPage> {
+    const result = await invoke<Record<string, unknown>>('get_project_activities', {
+      projectId,
+      query: toBackendQuery(query),
+    });
+    return fromBackendPage(result);
+  },
+
+  /** 分页获取卡片活动记录 */
+  async getCardActivities(cardId: string, query: ActivityQuery = {}): Promise<ActivityPage> {
+    const result = await invoke<Record<string, unknown>>('get_card_activities', {
+      cardId,
+      query: toBackendQuery(query),
+    });
+    return fromBackendPage(result);
+  },
+};
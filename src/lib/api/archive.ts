@@ -0,0 +1,76 @@
+// Tauri API 封装层 - 归档相关
+import { invoke } from '@tauri-apps/api/core';
+
+export type ArchiveKind = 'card' | 'column' | 'project';
+
+export interface ArchiveQuery {
+  projectId?: string;
+  kind?: ArchiveKind;
+  /** 按标题 (卡片和项目还包括描述) 模糊匹配 */
+  search?: string;
+  limit?: number;
+  offset?: number;
+}
+
+export interface ArchivedItem {
+  kind: ArchiveKind;
+  id: string;
+  /** 卡片或列的标题, 项目名称 */
+  title: string;
+  description?: string;
+  projectId: string;
+  projectName: string;
+  columnId?: string;
+  columnTitle?: string;
+  archivedAt: Date;
+}
+
+export interface ArchivePage {
+  items: ArchivedItem[];
+  total: number;
+  hasMore: boolean;
+}
+
+function fromBackendItem(data: Record<string, unknown>): ArchivedItem {
+  return {
+    kind: data.kind as ArchiveKind,
+    id: data.id as string,
+    title: data.title as string,
+    description: (data.description as string | null) ?? undefined,
+    projectId: data.projectId as string,
+    projectName: data.projectName as string,
+    columnId: (data.columnId as string | null) ?? undefined,
+    columnTitle: (data.columnTitle as string | null) ?? undefined,
+    archivedAt: new Date(data.archivedAt as string),
+  };
+}
+
+export const archiveApi = {
+  /** 归档卡片、列或项目 */
+  async archive(kind: ArchiveKind, id: string): Promise<void> {
+    await invoke('archive_item', { kind, id });
+  },
+
+  /** 取消归档, 恢复到原来的位置 */
+  async unarchive(kind: ArchiveKind, id: string): Promise<void> {
+    await invoke('unarchive_item', { kind, id });
+  },
+
+  /** 分页查询已归档的数据, 按归档时间倒序 */
+  async list(query: ArchiveQuery = {}): Promise<ArchivePage> {
+    const result = await invoke<Record<string, unknown>>('list_archived', {
+      query: {
+        projectId: query.projectId ?? null,
+        kind: query.kind ?? null,
+        search: query.search ?? null,
+        limit: query.limit ?? null,
+        offset: query.offset ?? null,
+      },
+    });
+    return {
+      items: (result.items as Record<string, unknown>[]).map(fromBackendItem),
+      total: result.total as number,
+      hasMore: result.hasMore as boolean,
+    };
+  },
+};
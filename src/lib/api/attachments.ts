@@ -0,0 +1,38 @@
+// Tauri API 封装层 - 卡片附件相关
+import { invoke } from '@tauri-apps/api/core';
+import type { Attachment } from '@/types/kanban';
+
+function fromBackendAttachment(data: Record<string, unknown>): Attachment {
+  return {
+    id: data.id as string,
+    cardId: data.cardId as string,
+    fileName: data.fileName as string,
+    size: data.size as number,
+    blobHash: data.blobHash as string,
+    createdAt: new Date(data.createdAt as string),
+  };
+}
+
+export const attachmentsApi = {
+  /** 获取卡片的附件 */
+  async list(cardId: string): Promise<Attachment[]> {
+    const result = await invoke<Record<string, unknown>[]>('list_attachments', { cardId });
+    return result.map(fromBackendAttachment);
+  },
+
+  /** 添加附件 (复制本地文件到附件库) */
+  async add(cardId: string, filePath: string): Promise<Attachment> {
+    const result = await invoke<Record<string, unknown>>('add_attachment', { cardId, filePath });
+    return fromBackendAttachment(result);
+  },
+
+  /** 使用系统默认程序打开附件 */
+  async open(attachmentId: string): Promise<void> {
+    await invoke('open_attachment', { attachmentId });
+  },
+
+  /** 移除附件 */
+  async remove(attachmentId: string): Promise<void> {
+    await invoke('remove_attachment', { attachmentId });
+  },
+};
@@ -0,0 +1,36 @@
+// Tauri API 封装层 - 数据库备份相关
+import { invoke } from '@tauri-apps/api/core';
+
+/** 数据库备份文件信息 */
+export interface BackupInfo {
+  fileName: string;
+  createdAt: Date;
+  size: number;
+}
+
+function fromBackendBackup(data: Record<string, unknown>): BackupInfo {
+  return {
+    fileName: data.fileName as string,
+    createdAt: new Date(data.createdAt as string),
+    size: data.size as number,
+  };
+}
+
+export const backupsApi = {
+  /** 获取所有备份 (最新的在前) */
+  async list(): Promise<BackupInfo[]> {
+    const result = await invoke<Record<string, unknown>[]>('list_backups');
+    return result.map(fromBackendBackup);
+  },
+
+  /** 立即备份数据库 */
+  async create(): Promise<BackupInfo> {
+    const result = await invoke<Record<string, unknown>>('create_backup');
+    return fromBackendBackup(result);
+  },
+
+  /** 从备份恢复数据库 (恢复前会先备份当前数据), 完成后需重新加载项目和看板 */
+  async restore(fileName: string): Promise<void> {
+    await invoke('restore_backup', { fileName });
+  },
+};
@@ -0,0 +1,46 @@
+// Tauri API 封装层 - 卡片检查清单相关
+import { invoke } from '@tauri-apps/api/core';
+import type { ChecklistItem } from '@/types/kanban';
+
+function fromBackendChecklistItem(data: Record<string, unknown>): ChecklistItem {
+  return {
+    id: data.id as string,
+    cardId: data.cardId as string,
+    text: data.text as string,
+    done: data.done as boolean,
+    position: data.position as number,
+    createdAt: new Date(data.createdAt as string),
+    updatedAt: new Date(data.updatedAt as string),
+  };
+}
+
+export const checklistsApi = {
+  /** 获取卡片的检查清单 */
+  async getAll(cardId: string): Promise<ChecklistItem[]> {
+    const result = await invoke<Record<string, unknown>[]>('get_checklist', { cardId });
+    return result.map(fromBackendChecklistItem);
+  },
+
+  /** 添加检查清单条目 (追加到末尾) */
+  async add(cardId: string, text: string): Promise<ChecklistItem> {
+    const result = await invoke<Record<string, unknown>>('add_checklist_item', { cardId, text });
+    return fromBackendChecklistItem(result);
+  },
+
+  /** 切换条目的完成状态 */
+  async toggle(itemId: string): Promise<ChecklistItem> {
+    const result = await invoke<Record<string, unknown>>('toggle_checklist_item', { itemId });
+    return fromBackendChecklistItem(result);
+  },
+
+  /** 按给定顺序重排条目 */
+  async reorder(cardId: string, itemIds: string[]): Promise<ChecklistItem[]> {
+    const result = await invoke<Record<string, unknown>[]>('reorder_checklist_items', { cardId, itemIds });
+    return result.map(fromBackendChecklistItem);
+  },
+
+  /** 删除条目 */
+  async delete(itemId: string): Promise<void> {
+    await invoke('delete_checklist_item', { itemId });
+  },
+};
@@ -0,0 +1,38 @@
+// Tauri API 封装层 - 卡片评论相关
+import { invoke } from '@tauri-apps/api/core';
+import type { Comment } from '@/types/kanban';
+
+function fromBackendComment(data: Record<string, unknown>): Comment {
+  return {
+    id: data.id as string,
+    cardId: data.cardId as string,
+    body: data.body as string,
+    createdAt: new Date(data.createdAt as string),
+    updatedAt: new Date(data.updatedAt as string),
+  };
+}
+
+export const commentsApi = {
+  /** 获取卡片的评论 (按时间正序) */
+  async list(cardId: string): Promise<Comment[]> {
+    const result = await invoke<Record<string, unknown>[]>('list_comments', { cardId });
+    return result.map(fromBackendComment);
+  },
+
+  /** 添加评论 */
+  async add(cardId: string, body: string): Promise<Comment> {
+    const result = await invoke<Record<string, unknown>>('add_comment', { cardId, body });
+    return fromBackendComment(result);
+  },
+
+  /** 编辑评论 */
+  async edit(commentId: string, body: string): Promise<Comment> {
+    const result = await invoke<Record<string, unknown>>('edit_comment', { commentId, body });
+    return fromBackendComment(result);
+  },
+
+  /** 删除评论 */
+  async delete(commentId: string): Promise<void> {
+    await invoke('delete_comment', { commentId });
+  },
+};
@@ -0,0 +1,41 @@
+// 命令错误解析
+
+/** 命令错误码, 与后端 ErrorCode 一致 */
+export type ErrorCode =
+  | 'NotFound'
+  | 'Conflict'
+  | 'Validation'
+  | 'ForeignKey'
+  | 'WipLimit'
+  | 'Busy'
+  | 'NotInitialized'
+  | 'Io'
+  | 'Internal';
+
+/** 所有命令返回的结构化错误 */
+export interface CommandError {
+  code: ErrorCode;
+  message: string;
+  entity?: string; // 相关数据类型, 如 'card'
+  id?: string; // 相关数据ID
+  current?: Record<string, unknown>; // 版本冲突时数据库中的当前数据
+}
+
+/** 版本冲突: 数据已被其他窗口修改, current 为数据库中的当前数据 */
+export interface VersionConflict extends CommandError {
+  code: 'Conflict';
+  entity: 'card' | 'column' | 'project';
+  current: Record<string, unknown>;
+}
+
+/** 判断 invoke 抛出的错误是否为结构化命令错误 */
+export function isCommandError(error: unknown): error is CommandError {
+  return typeof error === 'object' && error !== null && 'code' in error && 'message' in error;
+}
+
+/** 解析更新命令返回的版本冲突错误, 其他错误返回 null */
+export function parseVersionConflict(error: unknown): VersionConflict | null {
+  return isCommandError(error) && error.code === 'Conflict' && error.current
+    ? (error as VersionConflict)
+    : null;
+}
@@ -0,0 +1,83 @@
+// Tauri API 封装层 - 项目导出/导入相关
+import { invoke } from '@tauri-apps/api/core';
+import type { Card, Column, Project } from '@/types/kanban';
+import { fromBackendCard, fromBackendColumn } from './kanban';
+import { fromBackendProject } from './projects';
+
+/** 导出格式 */
+export type ExportFormat = 'json' | 'csv' | 'markdown';
+
+/** Trello 导入结果 */
+export interface TrelloImportReport {
+  project: Project;
+  columns: number;
+  cards: number;
+  tags: number;
+  checklistItems: number;
+  comments: number;
+  /** 未导入内容的说明 */
+  skipped: string[];
+}
+
+/** CSV 导入的字段映射, 值为 CSV 表头名称 */
+export interface CsvFieldMapping {
+  title: string;
+  description?: string;
+  /** 列名称, 项目中不存在的列会自动创建 */
+  column?: string;
+  priority?: string;
+  startDate?: string;
+  dueDate?: string;
+}
+
+/** CSV 导入结果; 试运行时为将要创建的内容 */
+export interface CsvImportReport {
+  dryRun: boolean;
+  createdColumns: Column[];
+  cards: Card[];
+  /** 存在错误时不会写入任何数据 */
+  errors: { line: number; message: string }[];
+}
+
+export const exportApi = {
+  /** 将项目导出到指定文件 */
+  async exportProject(projectId: string, format: ExportFormat, filePath: string): Promise<void> {
+    await invoke('export_project', { projectId, format, filePath });
+  },
+
+  /** 从 JSON 导出文件创建新项目 */
+  async importProject(filePath: string): Promise<Project> {
+    const result = await invoke<Record<string, unknown>>('import_project', { filePath });
+    return fromBackendProject(result);
+  },
+
+  /** 从 Trello 看板导出的 JSON 文件创建新项目 */
+  async importTrello(filePath: string): Promise<TrelloImportReport> {
+    const result = await invoke<Record<string, unknown>>('import_trello', { filePath });
+    return {
+      ...(result as unknown as TrelloImportReport),
+      project: fromBackendProject(result.project as Record<string, unknown>),
+    };
+  },
+
+  /** 按字段映射将 CSV 文件导入为项目卡片, dryRun 为 true 时只返回将要创建的内容 */
+  async importCsv(
+    projectId: string,
+    filePath: string,
+    mapping: CsvFieldMapping,
+    dryRun: boolean,
+  ): Promise<CsvImportReport> {
+    const result = await invoke<Record<string, unknown>>('import_csv', {
+      projectId,
+      filePath,
+      mapping,
+      dryRun,
+    });
+    return {
+      dryRun: result.dryRun as boolean,
+      createdColumns: (result.createdColumns as Record<string, unknown>[]).map(fromBackendColumn),
+      cards: (result.cards as Record<string, unknown>[]).map(fromBackendCard),
+      errors: result.errors as CsvImportReport['errors'],
+    };
+  },
+};
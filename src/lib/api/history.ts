@@ -0,0 +1,46 @@
+// Tauri API 封装层 - 撤销/重做相关
+import { invoke } from '@tauri-apps/api/core';
+
+/** 项目列表级操作 (创建/删除/排序项目) 的撤销作用域, 与后端 PROJECT_LIST_SCOPE 保持一致 */
+export const PROJECT_LIST_SCOPE = 'projects';
+
+export interface HistoryEntry {
+  id: number;
+  scope: string;
+  label: string;
+  undone: boolean;
+  createdAt: Date;
+}
+
+function fromBackendEntry(data: Record<string, unknown>): HistoryEntry {
+  return {
+    id: data.id as number,
+    scope: data.scope as string,
+    label: data.label as string,
+    undone: data.undone as boolean,
+    createdAt: new Date(data.createdAt as string),
+  };
+}
+
+export const historyApi = {
+  /** 撤销作用域内最近一次操作 */
+  async undo(projectId: string): Promise<HistoryEntry | null> {
+    const result = await invoke<Record<string, unknown> | null>('undo', { projectId });
+    return result ? fromBackendEntry(result) : null;
+  },
+
+  /** 重做作用域内最近一次被撤销的操作 */
+  async redo(projectId: string): Promise<HistoryEntry | null> {
+    const result = await invoke<Record<string, unknown> | null>('redo', { projectId });
+    return result ? fromBackendEntry(result) : null;
+  },
+
+  /** 获取撤销历史 (最新的在前) */
+  async getHistory(projectId: string, limit?: number): Promise<HistoryEntry[]> {
+    const result = await invoke<Record<string, unknown>[]>('get_undo_history', {
+      projectId,
+      limit: limit ?? null,
+    });
+    return result.map(fromBackendEntry);
+  },
+};
@@ -1,6 +1,41 @@
 // API 封装层入口
 // 统一导出所有 API 模块
 
+export { activitiesApi } from './activities';
+export type { ActivityPage, ActivityQuery } from './activities';
+export { archiveApi } from './archive';
+export type { ArchiveKind, ArchivePage, ArchiveQuery, ArchivedItem } from './archive';
+export { attachmentsApi } from './attachments';
+export { backupsApi } from './backups';
+export type { BackupInfo } from './backups';
+export { checklistsApi } from './checklists';
+export { commentsApi } from './comments';
+export { isCommandError, parseVersionConflict } from './errors';
+export type { CommandError, ErrorCode, VersionConflict } from './errors';
+export { exportApi } from './export';
+export type { CsvFieldMapping, CsvImportReport, ExportFormat, TrelloImportReport } from './export';
+export { historyApi, PROJECT_LIST_SCOPE } from './history';
+export type { HistoryEntry } from './history';
 export { kanbanApi } from './kanban';
-export type { MoveCardParams } from './kanban';
+export type { BoardSaveSummary, ColumnCardOrder, MoveCardParams, MoveCardResult, RemovedCard } from './kanban';
+export { metricsApi } from './metrics';
+export type {
+  AgingCard,
+  ColumnFlowSeries,
+  CumulativeFlow,
+  CumulativeFlowQuery,
+  DurationStats,
+  MetricsQuery,
+  ProjectMetrics,
+  WeeklyThroughput,
+} from './metrics';
+export { prioritiesApi } from './priorities';
+export type { PriorityLevel, PriorityScale } from './priorities';
 export { projectsApi } from './projects';
+export { remindersApi } from './reminders';
+export type { ReminderSettings } from './reminders';
+export { searchApi } from './search';
+export type { CardSearchResult, SearchCardsParams } from './search';
+export { tagsApi } from './tags';
+export { trashApi } from './trash';
+export type { TrashItem, TrashSettings } from './trash';
@@ -12,8 +12,11 @@ function toBackendCard(card: Card): Record<string, unknown> {
     position: card.position,
     completed: card.completed,
     priority: card.priority,
+    tagIds: card.tagIds,
+    recurrence: card.recurrence ?? null,
     startDate: card.startDate instanceof Date ? card.startDate.toISOString() : card.startDate,
     dueDate: card.dueDate instanceof Date ? card.dueDate.toISOString() : card.dueDate,
+    version: card.version,
     createdAt: card.createdAt instanceof Date ? card.createdAt.toISOString() : card.createdAt,
     updatedAt: card.updatedAt instanceof Date ? card.updatedAt.toISOString() : card.updatedAt,
   };
@@ -26,13 +29,16 @@ function toBackendColumn(column: Column): Record<string, unknown> {
     position: column.position,
     cardIds: column.cardIds,
     backgroundColor: column.backgroundColor,
+    wipLimit: column.wipLimit ?? null,
+    wipMode: column.wipMode,
+    version: column.version,
     createdAt: column.createdAt instanceof Date ? column.createdAt.toISOString() : column.createdAt,
     updatedAt: column.updatedAt instanceof Date ? column.updatedAt.toISOString() : column.updatedAt,
   };
 }
 
 // 将后端格式转换为前端类型 (后端返回 camelCase)
-function fromBackendCard(data: Record<string, unknown>): Card {
+export function fromBackendCard(data: Record<string, unknown>): Card {
   return {
     id: data.id as string,
     title: data.title as string,
@@ -41,20 +47,31 @@ function fromBackendCard(data: Record<string, unknown>): Card {
     position: data.position as number,
     completed: data.completed as boolean | undefined,
     priority: data.priority as Card['priority'],
+    tagIds: (data.tagIds as string[] | null) ?? undefined,
+    checklistProgress: (data.checklistProgress as Card['checklistProgress'] | null) ?? undefined,
+    commentCount: (data.commentCount as number | null) ?? undefined,
+    recurrence: (data.recurrence as Card['recurrence'] | null) ?? undefined,
+    wipExceeded: (data.wipExceeded as boolean | null) ?? undefined,
     startDate: data.startDate ? new Date(data.startDate as string) : undefined,
     dueDate: data.dueDate ? new Date(data.dueDate as string) : undefined,
+    version: data.version as number,
+    archivedAt: data.archivedAt ? new Date(data.archivedAt as string) : undefined,
     createdAt: new Date(data.createdAt as string),
     updatedAt: new Date(data.updatedAt as string),
   };
 }
 
-function fromBackendColumn(data: Record<string, unknown>): Column {
+export function fromBackendColumn(data: Record<string, unknown>): Column {
   return {
     id: data.id as string,
     title: data.title as string,
     position: data.position as number,
     cardIds: data.cardIds as string[],
     backgroundColor: data.backgroundColor as string | undefined,
+    wipLimit: (data.wipLimit as number | null) ?? undefined,
+    wipMode: (data.wipMode as Column['wipMode']) ?? 'soft',
+    version: data.version as number,
+    archivedAt: data.archivedAt ? new Date(data.archivedAt as string) : undefined,
     createdAt: new Date(data.createdAt as string),
     updatedAt: new Date(data.updatedAt as string),
   };
@@ -80,17 +97,47 @@ export interface MoveCardParams {
   newPosition: number;
 }
 
+/** 列中卡片的顺序 */
+export interface ColumnCardOrder {
+  columnId: string;
+  cardIds: string[];
+}
+
+/** 移动卡片结果 (同列移动时 fromColumn 与 toColumn 相同) */
+export interface MoveCardResult {
+  card: Card;
+  fromColumn: ColumnCardOrder;
+  toColumn: ColumnCardOrder;
+}
+
+/** 保存看板时的变更统计 */
+export interface BoardSaveSummary {
+  columnsCreated: number;
+  columnsUpdated: number;
+  columnsDeleted: number;
+  cardsCreated: number;
+  cardsUpdated: number;
+  cardsDeleted: number;
+}
+
+/** 保存看板时移除的卡片及读取时的版本号 */
+export interface RemovedCard {
+  id: string;
+  version: number;
+}
+
 export const kanbanApi = {
-  /** 获取看板数据 */
-  async getBoard(projectId: string): Promise<Board> {
+  /** 获取看板数据, 默认不包含已归档的列和卡片 */
+  async getBoard(projectId: string, includeArchived = false): Promise<Board> {
     const result = await invoke<Record<string, unknown>>('get_board', {
       projectId,
+      includeArchived,
     });
     return fromBackendBoard(result);
   },
 
-  /** 保存看板数据 (全量) */
-  async saveBoard(projectId: string, board: Board): Promise<void> {
+  /** 保存看板数据, 后端只写入有变化的列和卡片并返回变更统计; 移除的卡片需给出读取时的版本号 */
+  async saveBoard(projectId: string, board: Board, removedCards: RemovedCard[] = []): Promise<BoardSaveSummary> {
     const backendBoard = {
       id: board.id,
       title: board.title,
@@ -99,7 +146,7 @@ export const kanbanApi = {
       createdAt: board.createdAt instanceof Date ? board.createdAt.toISOString() : board.createdAt,
       updatedAt: board.updatedAt instanceof Date ? board.updatedAt.toISOString() : board.updatedAt,
     };
-    await invoke('save_board', { projectId, board: backendBoard });
+    return invoke<BoardSaveSummary>('save_board', { projectId, board: backendBoard, removedCards });
   },
 
   /** 创建卡片 */
@@ -113,8 +160,11 @@ export const kanbanApi = {
       position: card.position ?? 0,
       completed: card.completed ?? false,
       priority: card.priority || 'low',
+      tagIds: card.tagIds ?? [],
+      recurrence: card.recurrence ?? null,
       startDate: card.startDate instanceof Date ? card.startDate.toISOString() : card.startDate || null,
       dueDate: card.dueDate instanceof Date ? card.dueDate.toISOString() : card.dueDate || null,
+      version: 0,
       createdAt: now.toISOString(),
       updatedAt: now.toISOString(),
     };
@@ -125,7 +175,7 @@ export const kanbanApi = {
     return fromBackendCard(result);
   },
 
-  /** 更新卡片 */
+  /** 更新卡片, card.version 需为读取时的版本号, 不一致时抛出版本冲突错误 */
   async updateCard(projectId: string, card: Card): Promise<Card> {
     const result = await invoke<Record<string, unknown>>('update_card', {
       projectId,
@@ -139,9 +189,9 @@ export const kanbanApi = {
     await invoke('delete_card', { projectId, cardId });
   },
 
-  /** 移动卡片 */
-  async moveCard(projectId: string, params: MoveCardParams): Promise<void> {
-    await invoke('move_card', {
+  /** 移动卡片, 返回后端重新编号后的源列和目标列卡片顺序 */
+  async moveCard(projectId: string, params: MoveCardParams): Promise<MoveCardResult> {
+    const result = await invoke<Record<string, unknown>>('move_card', {
       projectId,
       params: {
         card_id: params.cardId,
@@ -150,6 +200,11 @@ export const kanbanApi = {
         new_position: params.newPosition,
       },
     });
+    return {
+      card: fromBackendCard(result.card as Record<string, unknown>),
+      fromColumn: result.fromColumn as ColumnCardOrder,
+      toColumn: result.toColumn as ColumnCardOrder,
+    };
   },
 
   /** 创建列 */
@@ -161,6 +216,9 @@ export const kanbanApi = {
       position: column.position ?? 0,
       cardIds: column.cardIds || [],
       backgroundColor: column.backgroundColor || null,
+      wipLimit: column.wipLimit ?? null,
+      wipMode: column.wipMode ?? 'soft',
+      version: 0,
       createdAt: now.toISOString(),
       updatedAt: now.toISOString(),
     };
@@ -171,7 +229,7 @@ export const kanbanApi = {
     return fromBackendColumn(result);
   },
 
-  /** 更新列 */
+  /** 更新列, column.version 需为读取时的版本号, 不一致时抛出版本冲突错误 */
   async updateColumn(projectId: string, column: Column): Promise<Column> {
     const result = await invoke<Record<string, unknown>>('update_column', {
       projectId,
@@ -0,0 +1,127 @@
+// Tauri API 封装层 - 流动指标相关
+import { invoke } from '@tauri-apps/api/core';
+
+export interface MetricsQuery {
+  /** 统计范围起点 (包含), 默认为截止时间前 90 天 */
+  since?: Date;
+  /** 统计范围终点 (不包含), 默认为当前时间 */
+  until?: Date;
+  /** 开始列, 默认为第二列 */
+  startColumnId?: string;
+  /** 完成列, 默认为最后一列 */
+  endColumnId?: string;
+}
+
+/** 时长分布, 单位为天; 没有样本时各值为空 */
+export interface DurationStats {
+  count: number;
+  average?: number;
+  p50?: number;
+  p85?: number;
+  p95?: number;
+}
+
+export interface WeeklyThroughput {
+  /** 周一的日期 (YYYY-MM-DD) */
+  weekStart: string;
+  count: number;
+}
+
+export interface AgingCard {
+  cardId: string;
+  title: string;
+  columnId: string;
+  startedAt: Date;
+  ageDays: number;
+}
+
+export interface ProjectMetrics {
+  since: Date;
+  until: Date;
+  startColumnId: string;
+  endColumnId: string;
+  /** 前置时间: 创建到完成 */
+  leadTime: DurationStats;
+  /** 周期时间: 开始到完成 */
+  cycleTime: DurationStats;
+  throughput: WeeklyThroughput[];
+  /** 处于开始列与完成列之间的卡片, 按停留时间倒序 */
+  agingWip: AgingCard[];
+}
+
+export interface CumulativeFlowQuery {
+  /** 起始日期 (YYYY-MM-DD, 包含), 默认为结束日期前 29 天 */
+  since?: string;
+  /** 结束日期 (YYYY-MM-DD, 包含), 默认为今天 */
+  until?: string;
+}
+
+export interface ColumnFlowSeries {
+  columnId: string;
+  title: string;
+  /** 与 days 一一对应 */
+  counts: number[];
+}
+
+export interface CumulativeFlow {
+  /** 日期序列 (YYYY-MM-DD) */
+  days: string[];
+  /** 按看板顺序排列的各列数据 */
+  columns: ColumnFlowSeries[];
+}
+
+function fromBackendStats(data: Record<string, unknown>): DurationStats {
+  return {
+    count: data.count as number,
+    average: (data.average as number | null) ?? undefined,
+    p50: (data.p50 as number | null) ?? undefined,
+    p85: (data.p85 as number | null) ?? undefined,
+    p95: (data.p95 as number | null) ?? undefined,
+  };
+}
+
+function fromBackendMetrics(data: Record<string, unknown>): ProjectMetrics {
+  return {
+    since: new Date(data.since as string),
+    until: new Date(data.until as string),
+    startColumnId: data.startColumnId as string,
+    endColumnId: data.endColumnId as string,
+    leadTime: fromBackendStats(data.leadTime as Record<string, unknown>),
+    cycleTime: fromBackendStats(data.cycleTime as Record<string, unknown>),
+    throughput: data.throughput as WeeklyThroughput[],
+    agingWip: (data.agingWip as Record<string, unknown>[]).map((card) => ({
+      cardId: card.cardId as string,
+      title: card.title as string,
+      columnId: card.columnId as string,
+      startedAt: new Date(card.startedAt as string),
+      ageDays: card.ageDays as number,
+    })),
+  };
+}
+
+export const metricsApi = {
+  /** 获取项目的前置时间、周期时间、每周吞吐量和在制品停留时间 */
+  async getProjectMetrics(projectId: string, query: MetricsQuery = {}): Promise<ProjectMetrics> {
+    const result = await invoke<Record<string, unknown>>('get_project_metrics', {
+      projectId,
+      query: {
+        since: query.since?.toISOString() ?? null,
+        until: query.until?.toISOString() ?? null,
+        startColumnId: query.startColumnId ?? null,
+        endColumnId: query.endColumnId ?? null,
+      },
+    });
+    return fromBackendMetrics(result);
+  },
+
+  /** 获取项目每天各列的卡片数, 用于绘制累积流图 */
+  async getCumulativeFlow(projectId: string, query: CumulativeFlowQuery = {}): Promise<CumulativeFlow> {
+    return invoke<CumulativeFlow>('get_cumulative_flow', {
+      projectId,
+      query: {
+        since: query.since ?? null,
+        until: query.until ?? null,
+      },
+    });
+  },
+};
@@ -0,0 +1,27 @@
+// Tauri API 封装层 - 优先级刻度相关
+import { invoke } from '@tauri-apps/api/core';
+import type { Priority } from '@/types/kanban';
+
+export interface PriorityLevel {
+  /** 卡片中保存的标识 (小写字母、数字、- 或 _), 改名时保持不变 */
+  value: Priority;
+  /** 界面上显示的名称 */
+  label: string;
+}
+
+export interface PriorityScale {
+  /** 优先级, 从低到高; 可改名、增加和调整顺序, 卡片只能设置为其中之一 */
+  levels: PriorityLevel[];
+}
+
+export const prioritiesApi = {
+  /** 获取优先级刻度 */
+  async getScale(): Promise<PriorityScale> {
+    return invoke<PriorityScale>('get_priority_scale');
+  },
+
+  /** 更新优先级刻度 (不能为空, 标识和名称不能重复), 已使用被移除优先级的卡片保持不变 */
+  async updateScale(scale: PriorityScale): Promise<PriorityScale> {
+    return invoke<PriorityScale>('update_priority_scale', { scale });
+  },
+};
@@ -3,21 +3,23 @@ import { invoke } from '@tauri-apps/api/core';
 import type { Project } from '@/types/kanban';
 
 // 将后端格式转换为前端类型
-function fromBackendProject(data: Record<string, unknown>): Project {
+export function fromBackendProject(data: Record<string, unknown>): Project {
   return {
     id: data.id as string,
     name: data.name as string,
     description: data.description as string | undefined,
     position: data.position as number,
+    version: data.version as number,
+    archivedAt: data.archivedAt ? new Date(data.archivedAt as string) : undefined,
     createdAt: new Date(data.createdAt as string),
     updatedAt: new Date(data.updatedAt as string),
   };
 }
 
 export const projectsApi = {
-  /** 获取所有项目 */
-  async getAll(): Promise<Project[]> {
-    const result = await invoke<Record<string, unknown>[]>('get_projects');
+  /** 获取所有项目, 默认不包含已归档的项目 */
+  async getAll(includeArchived = false): Promise<Project[]> {
+    const result = await invoke<Record<string, unknown>[]>('get_projects', { includeArchived });
     return result.map(fromBackendProject);
   },
 
@@ -29,6 +31,7 @@ export const projectsApi = {
       name: project.name,
       description: project.description || null,
       position: project.position ?? 0,
+      version: 0,
       createdAt: now.toISOString(),
       updatedAt: now.toISOString(),
     };
@@ -38,13 +41,14 @@ export const projectsApi = {
     return fromBackendProject(result);
   },
 
-  /** 更新项目 */
+  /** 更新项目, project.version 需为读取时的版本号, 不一致时抛出版本冲突错误 */
   async update(project: Project): Promise<Project> {
     const backendProject = {
       id: project.id,
       name: project.name,
       description: project.description || null,
       position: project.position,
+      version: project.version,
       createdAt: project.createdAt instanceof Date ? project.createdAt.toISOString() : project.createdAt,
       updatedAt: new Date().toISOString(),
     };
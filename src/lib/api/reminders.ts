@@ -0,0 +1,20 @@
+// Tauri API 封装层 - 到期提醒相关
+import { invoke } from '@tauri-apps/api/core';
+
+export interface ReminderSettings {
+  enabled: boolean;
+  /** 提前提醒的分钟数, 0 表示到期时提醒 */
+  leadMinutes: number[];
+}
+
+export const remindersApi = {
+  /** 获取到期提醒设置 */
+  async getSettings(): Promise<ReminderSettings> {
+    return invoke<ReminderSettings>('get_reminder_settings');
+  },
+
+  /** 更新到期提醒设置 (后端会去重并按提前量从大到小排序) */
+  async updateSettings(settings: ReminderSettings): Promise<ReminderSettings> {
+    return invoke<ReminderSettings>('update_reminder_settings', { settings });
+  },
+};
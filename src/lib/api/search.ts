@@ -0,0 +1,73 @@
+// Tauri API 封装层 - 卡片搜索相关
+import { invoke } from '@tauri-apps/api/core';
+import type { Card, Priority } from '@/types/kanban';
+
+export interface SearchCardsParams {
+  query: string;
+  projectId?: string;
+  priorities?: Priority[];
+  /** 只返回不低于该优先级 (按刻度顺序) 的卡片 */
+  minPriority?: Priority;
+  completed?: boolean;
+  dueFrom?: Date;
+  dueTo?: Date;
+  limit?: number;
+}
+
+export interface CardSearchResult {
+  card: Card;
+  projectId: string;
+  projectName: string;
+  columnTitle: string;
+  /** 标题 (已转义 HTML), 命中部分以 <mark></mark> 包裹 */
+  titleHighlight: string;
+  /** 描述摘要 (已转义 HTML), 命中部分以 <mark></mark> 包裹 */
+  descriptionSnippet?: string;
+  score: number;
+}
+
+function fromBackendResult(data: Record<string, unknown>): CardSearchResult {
+  const card = data.card as Record<string, unknown>;
+  return {
+    card: {
+      id: card.id as string,
+      title: card.title as string,
+      description: (card.description as string | null) ?? undefined,
+      columnId: card.columnId as string,
+      position: card.position as number,
+      completed: card.completed as boolean | undefined,
+      priority: card.priority as Card['priority'],
+      tagIds: (card.tagIds as string[] | null) ?? undefined,
+      startDate: card.startDate ? new Date(card.startDate as string) : undefined,
+      dueDate: card.dueDate ? new Date(card.dueDate as string) : undefined,
+      version: card.version as number,
+      createdAt: new Date(card.createdAt as string),
+      updatedAt: new Date(card.updatedAt as string),
+    },
+    projectId: data.projectId as string,
+    projectName: data.projectName as string,
+    columnTitle: data.columnTitle as string,
+    titleHighlight: data.titleHighlight as string,
+    descriptionSnippet: (data.descriptionSnippet as string | null) ?? undefined,
+    score: data.score as number,
+  };
+}
+
+export const searchApi = {
+  /** 跨项目全文搜索卡片 */
+  async searchCards(params: SearchCardsParams): Promise<CardSearchResult[]> {
+    const result = await invoke<Record<string, unknown>[]>('search_cards', {
+      params: {
+        query: params.query,
+        project_id: params.projectId ?? null,
+        priorities: params.priorities ?? [],
+        min_priority: params.minPriority ?? null,
+        completed: params.completed ?? null,
+        due_from: params.dueFrom?.toISOString() ?? null,
+        due_to: params.dueTo?.toISOString() ?? null,
+        limit: params.limit ?? null,
+      },
+    });
+    return result.map(fromBackendResult);
+  },
+};
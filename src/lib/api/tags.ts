@@ -0,0 +1,64 @@
+// Tauri API 封装层 - 标签相关
+import { invoke } from '@tauri-apps/api/core';
+import type { Tag } from '@/types/kanban';
+
+function toBackendTag(tag: Tag): Record<string, unknown> {
+  return {
+    id: tag.id,
+    name: tag.name,
+    color: tag.color,
+    position: tag.position,
+    createdAt: tag.createdAt instanceof Date ? tag.createdAt.toISOString() : tag.createdAt,
+    updatedAt: tag.updatedAt instanceof Date ? tag.updatedAt.toISOString() : tag.updatedAt,
+  };
+}
+
+function fromBackendTag(data: Record<string, unknown>): Tag {
+  return {
+    id: data.id as string,
+    name: data.name as string,
+    color: data.color as string,
+    position: data.position as number,
+    createdAt: new Date(data.createdAt as string),
+    updatedAt: new Date(data.updatedAt as string),
+  };
+}
+
+export const tagsApi = {
+  /** 获取项目的所有标签 */
+  async getAll(projectId: string): Promise<Tag[]> {
+    const result = await invoke<Record<string, unknown>[]>('get_tags', { projectId });
+    return result.map(fromBackendTag);
+  },
+
+  /** 创建标签 */
+  async create(projectId: string, tag: Partial<Tag> & { name: string; color: string }): Promise<Tag> {
+    const now = new Date();
+    const result = await invoke<Record<string, unknown>>('create_tag', {
+      projectId,
+      tag: {
+        id: tag.id || '',
+        name: tag.name,
+        color: tag.color,
+        position: tag.position ?? 0,
+        createdAt: now.toISOString(),
+        updatedAt: now.toISOString(),
+      },
+    });
+    return fromBackendTag(result);
+  },
+
+  /** 更新标签 */
+  async update(projectId: string, tag: Tag): Promise<Tag> {
+    const result = await invoke<Record<string, unknown>>('update_tag', {
+      projectId,
+      tag: toBackendTag(tag),
+    });
+    return fromBackendTag(result);
+  },
+
+  /** 删除标签 */
+  async delete(projectId: string, tagId: string): Promise<void> {
+    await invoke('delete_tag', { projectId, tagId });
+  },
+};
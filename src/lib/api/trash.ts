@@ -0,0 +1,59 @@
+// Tauri API 封装层 - 回收站相关
+import { invoke } from '@tauri-apps/api/core';
+import type { ArchiveKind } from './archive';
+
+export interface TrashItem {
+  id: string;
+  kind: ArchiveKind;
+  /** 被删除的卡片、列或项目的ID */
+  entityId: string;
+  projectId: string;
+  /** 删除时的项目名称 */
+  projectName: string;
+  /** 卡片或列的标题, 项目名称 */
+  title: string;
+  deletedAt: Date;
+}
+
+export interface TrashSettings {
+  /** 删除后保留的天数, 超过后自动清除 */
+  retentionDays: number;
+}
+
+function fromBackendItem(data: Record<string, unknown>): TrashItem {
+  return {
+    id: data.id as string,
+    kind: data.kind as ArchiveKind,
+    entityId: data.entityId as string,
+    projectId: data.projectId as string,
+    projectName: data.projectName as string,
+    title: data.title as string,
+    deletedAt: new Date(data.deletedAt as string),
+  };
+}
+
+export const trashApi = {
+  /** 获取回收站内容, 按删除时间倒序; 指定项目时只返回该项目的内容 */
+  async list(projectId?: string): Promise<TrashItem[]> {
+    const result = await invoke<Record<string, unknown>[]>('list_trash', {
+      projectId: projectId ?? null,
+    });
+    return result.map(fromBackendItem);
+  },
+
+  /** 恢复到原来的列和位置; 所在的列或项目已删除时需先恢复它们 */
+  async restore(trashId: string): Promise<TrashItem> {
+    const result = await invoke<Record<string, unknown>>('restore_from_trash', { trashId });
+    return fromBackendItem(result);
+  },
+
+  /** 获取回收站设置 */
+  async getSettings(): Promise<TrashSettings> {
+    return invoke<TrashSettings>('get_trash_settings');
+  },
+
+  /** 更新回收站设置 (保留 1 到 365 天) */
+  async updateSettings(settings: TrashSettings): Promise<TrashSettings> {
+    return invoke<TrashSettings>('update_trash_settings', { settings });
+  },
+};
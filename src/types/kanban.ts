@@ -1,13 +1,15 @@
-// 优先级类型
-export type Priority = 'low' | 'normal' | 'urgent' | 'critical';
+// 优先级类型 - 优先级刻度中的标识，默认刻度为 low / normal / urgent / critical
+export type Priority = string;
 
-// 优先级配置 - 使用具体颜色值确保正确显示
-export const PRIORITY_CONFIG: Record<Priority, {
+export interface PriorityStyle {
   label: string;
   textColor: string;
   bgColor: string;
   borderColor: string;
-}> = {
+}
+
+// 默认优先级配置 - 使用具体颜色值确保正确显示
+export const PRIORITY_CONFIG: Record<string, PriorityStyle> = {
   low: {
     label: '较低',
     textColor: '#6b7280',      // gray-500
@@ -37,6 +39,12 @@ export const PRIORITY_CONFIG: Record<Priority, {
 // 优先级顺序（用于排序和显示）
 export const PRIORITY_ORDER: Priority[] = ['low', 'normal', 'urgent', 'critical'];
 
+// 获取优先级的显示配置，自定义优先级使用中性颜色并显示其标识
+export function getPriorityConfig(priority?: Priority): PriorityStyle {
+  const value = priority || 'low';
+  return PRIORITY_CONFIG[value] ?? { ...PRIORITY_CONFIG.low, label: value };
+}
+
 export interface Card {
   id: string;
   title: string;
@@ -45,18 +53,83 @@ export interface Card {
   position: number;
   completed?: boolean;
   priority?: Priority;
+  tagIds?: string[];
+  checklistProgress?: ChecklistProgress;
+  commentCount?: number;
+  recurrence?: Recurrence;
+  wipExceeded?: boolean; // 只读, 创建/更新/移动后所在列超出软性在制品上限
   startDate?: Date;
   dueDate?: Date;
+  version: number; // 版本号, 每次修改后递增, 更新时用于检测并发修改
+  archivedAt?: Date; // 只读, 归档时间, 通过归档命令修改
+  createdAt: Date;
+  updatedAt: Date;
+}
+
+export interface ChecklistItem {
+  id: string;
+  cardId: string;
+  text: string;
+  done: boolean;
+  position: number;
+  createdAt: Date;
+  updatedAt: Date;
+}
+
+// 重复设置, rule 为 RRULE 子集 (FREQ/INTERVAL/BYDAY/BYMONTHDAY/UNTIL)
+export interface Recurrence {
+  rule: string;
+  columnId?: string;
+  nextCardId?: string; // 只读, 已生成的下一张卡片
+}
+
+// 检查清单进度 (只读, 由后端随卡片返回)
+export interface ChecklistProgress {
+  done: number;
+  total: number;
+}
+
+// 卡片评论, body 为 Markdown
+export interface Comment {
+  id: string;
+  cardId: string;
+  body: string;
+  createdAt: Date;
+  updatedAt: Date;
+}
+
+// 卡片附件, 文件内容按哈希存放在应用数据目录的附件库中
+export interface Attachment {
+  id: string;
+  cardId: string;
+  fileName: string;
+  size: number;
+  blobHash: string;
+  createdAt: Date;
+}
+
+export interface Tag {
+  id: string;
+  name: string;
+  color: string;
+  position: number;
   createdAt: Date;
   updatedAt: Date;
 }
 
+// 在制品上限模式: soft 超出时仍可放入但会标记, hard 拒绝放入
+export type WipMode = 'soft' | 'hard';
+
 export interface Column {
   id: string;
   title: string;
   position: number;
   cardIds: string[];
   backgroundColor?: string;
+  wipLimit?: number; // 在制品上限, 为空表示不限制
+  wipMode: WipMode;
+  version: number; // 版本号, 每次修改后递增, 更新时用于检测并发修改
+  archivedAt?: Date; // 只读, 归档时间, 通过归档命令修改
   createdAt: Date;
   updatedAt: Date;
 }
@@ -72,7 +145,7 @@ export interface Board {
 
 export interface Activity {
   id: string;
-  type: 'card_created' | 'card_updated' | 'card_moved' | 'card_deleted' | 'card_completed' | 'card_uncompleted' | 'column_created' | 'column_updated' | 'column_deleted';
+  type: 'card_created' | 'card_updated' | 'card_moved' | 'card_deleted' | 'card_completed' | 'card_uncompleted' | 'card_archived' | 'card_unarchived' | 'column_created' | 'column_updated' | 'column_deleted' | 'column_archived' | 'column_unarchived' | 'project_created' | 'project_updated' | 'project_reordered' | 'project_archived' | 'project_unarchived';
   cardId?: string;
   columnId?: string;
   fromColumnId?: string;
@@ -101,6 +174,8 @@ export interface Project {
   name: string;
   description?: string;
   position: number;
+  version: number; // 版本号, 每次修改后递增, 更新时用于检测并发修改
+  archivedAt?: Date; // 只读, 归档时间, 通过归档命令修改
   createdAt: Date;
   updatedAt: Date;
 }